@@ -18,27 +18,251 @@ impl Options {
     }
 
     fn translate_type(&self, ty: Type) -> String {
-        let ty = match ty {
-            Type::Bool => return "bool".into(),
-            Type::String => return "String".into(),
-            Type::Length => "Length",
-            Type::Length2 => "Length2",
-            Type::Length3 => "Length3",
-            Type::Scalar => "Scalar",
-            Type::Scalar2 => "Scalar2",
-            Type::Scalar3 => "Scalar3",
-            Type::Angle => "Angle",
-            Type::Angle2 => "Angle2",
-            Type::Angle3 => "Angle3",
-            Type::VecLength2 => "VecLength2",
-            Type::VecLength3 => "VecLength3",
-            Type::FragmentResolution => "FragmentResolution",
-        };
+        match ty {
+            Type::Bool => "bool".into(),
+            Type::String => "String".into(),
+            _ => {
+                let rsolid = self.rsolid();
+                let ty = type_name(ty);
+                format!("{rsolid}::types::{ty}")
+            }
+        }
+    }
+
+    /// Returns the type of a parameter, which is a generated enum if it has any alternate types
+    fn param_type(&self, owner: &str, p_name: &str, param: &Parameter) -> String {
+        if param.alt.is_empty() {
+            self.translate_type(param.ty)
+        } else {
+            alt_enum_name(owner, p_name)
+        }
+    }
+
+    /// Returns the types that convert directly into the given type
+    ///
+    /// These are used for the alternate enums, where a blanket `Into` impl for each
+    /// variant would overlap.
+    fn sources(&self, ty: Type) -> Vec<String> {
         let rsolid = self.rsolid();
-        format!("{rsolid}::types::{ty}")
+        let ty_name = self.translate_type(ty);
+        let mut sources = match ty {
+            Type::Bool | Type::String => vec![],
+            Type::Scalar | Type::Length | Type::Angle => vec![
+                "f64".to_string(),
+                "i64".to_string(),
+                format!("{rsolid}::Var<{ty_name}>"),
+                format!("&{rsolid}::Var<{ty_name}>"),
+            ],
+            Type::FragmentResolution => vec!["f64".to_string(), "i64".to_string()],
+            Type::Scalar2
+            | Type::Length2
+            | Type::Angle2
+            | Type::Scalar3
+            | Type::Length3
+            | Type::Angle3 => {
+                let count = ty.arity().unwrap();
+                let inner = match ty {
+                    Type::Scalar2 | Type::Scalar3 => "Scalar",
+                    Type::Length2 | Type::Length3 => "Length",
+                    _ => "Angle",
+                };
+                vec![
+                    format!("[f64; {count}]"),
+                    format!("[i64; {count}]"),
+                    format!("[{rsolid}::types::{inner}; {count}]"),
+                ]
+            }
+            Type::Color => vec![
+                "&str".to_string(),
+                "String".to_string(),
+                "[f64; 3]".to_string(),
+                "[f64; 4]".to_string(),
+            ],
+            Type::VecScalar2 | Type::VecLength2 | Type::VecLength3 | Type::VecFace => vec![],
+        };
+        if ty == Type::String {
+            sources.push("&str".to_string());
+        }
+        sources.push(ty_name);
+        sources
+    }
+}
+
+fn type_name(ty: Type) -> &'static str {
+    match ty {
+        Type::Bool => "Bool",
+        Type::String => "String",
+        Type::Length => "Length",
+        Type::Length2 => "Length2",
+        Type::Length3 => "Length3",
+        Type::Scalar => "Scalar",
+        Type::Scalar2 => "Scalar2",
+        Type::Scalar3 => "Scalar3",
+        Type::Angle => "Angle",
+        Type::Angle2 => "Angle2",
+        Type::Angle3 => "Angle3",
+        Type::VecScalar2 => "VecScalar2",
+        Type::VecLength2 => "VecLength2",
+        Type::VecLength3 => "VecLength3",
+        Type::VecFace => "VecFace",
+        Type::FragmentResolution => "FragmentResolution",
+        Type::Color => "Color",
+    }
+}
+
+fn alt_enum_name(owner: &str, p_name: &str) -> String {
+    format!("{owner}{}", p_name.to_pascal_case())
+}
+
+/// Generates an enum for each parameter that accepts alternate types
+fn generate_alts<O: Write>(
+    options: &Options,
+    owner: &str,
+    parameters: &std::collections::BTreeMap<String, Parameter>,
+    out: &mut O,
+) -> io::Result<()> {
+    macro_rules! w {
+        ($($tt:tt)*) => {
+            writeln!(out, $($tt)*)?;
+        }
+    }
+
+    let rsolid = options.rsolid();
+
+    for (p_name, param) in parameters {
+        if param.alt.is_empty() {
+            continue;
+        }
+
+        let name = alt_enum_name(owner, p_name);
+        let types: Vec<_> = core::iter::once(param.ty)
+            .chain(param.alt.iter().copied())
+            .collect();
+
+        w!("/// Values accepted by [`{owner}::{p_name}`]");
+        if types.iter().all(|ty| ty.is_copy()) {
+            w!("#[derive(Clone, Copy, Debug, PartialEq)]");
+        } else {
+            w!("#[derive(Clone, Debug, PartialEq)]");
+        }
+        w!("pub enum {name} {{");
+        for ty in &types {
+            let variant = type_name(*ty);
+            let ty = options.translate_type(*ty);
+            w!("    {variant}({ty}),");
+        }
+        w!("}}");
+        w!();
+
+        // the first type to claim a source wins so scalars map to scalars and vectors to vectors
+        let mut seen = std::collections::HashSet::new();
+        for ty in &types {
+            let variant = type_name(*ty);
+            let ty_name = options.translate_type(*ty);
+            for source in options.sources(*ty) {
+                if !seen.insert(source.clone()) {
+                    continue;
+                }
+                let value = if source == ty_name {
+                    "value"
+                } else {
+                    "value.into()"
+                };
+                w!("impl From<{source}> for {name} {{");
+                w!("    #[inline]");
+                w!("    fn from(value: {source}) -> Self {{");
+                w!("        Self::{variant}({value})");
+                w!("    }}");
+                w!("}}");
+                w!();
+            }
+        }
+
+        w!("impl {rsolid}::scad::Scad for {name} {{");
+        w!("    fn assign(&self, f: &mut {rsolid}::scad::Formatter) -> {rsolid}::scad::Assignment {{");
+        w!("        match self {{");
+        for ty in &types {
+            let variant = type_name(*ty);
+            w!("            Self::{variant}(value) => {rsolid}::scad::Scad::assign(value, f),");
+        }
+        w!("        }}");
+        w!("    }}");
+        w!("}}");
+        w!();
     }
+
+    Ok(())
 }
 
+/// Generates the `(name, value)` arguments for a call
+fn generate_args<'a, O: Write>(
+    options: &Options,
+    owner: &str,
+    params: impl IntoIterator<Item = (&'a String, &'a Parameter)>,
+    positional: bool,
+    out: &mut O,
+) -> io::Result<()> {
+    macro_rules! w {
+        ($($tt:tt)*) => {
+            writeln!(out, $($tt)*)?;
+        }
+    }
+
+    let rsolid = options.rsolid();
+
+    for (p_name, param) in params {
+        let scad_name = if positional && param.position.is_some() {
+            ""
+        } else {
+            param.name.as_ref().unwrap_or(p_name)
+        };
+
+        // vector alternates that are passed as separate arguments
+        let split: Vec<_> = if param.split.is_empty() {
+            vec![]
+        } else {
+            param
+                .alt
+                .iter()
+                .copied()
+                .filter(|ty| ty.arity() == Some(param.split.len()))
+                .map(type_name)
+                .collect()
+        };
+
+        if split.is_empty() {
+            w!("        ({scad_name:?}, self.{p_name}.as_ref().map(|value| {{");
+            w!("            {rsolid}::scad::Scad::assign(value, f)");
+            w!("        }})),");
+            continue;
+        }
+
+        let name = alt_enum_name(owner, p_name);
+        let variants = split
+            .iter()
+            .map(|variant| format!("{name}::{variant}(_)"))
+            .collect::<Vec<_>>()
+            .join(" | ");
+        w!("        ({scad_name:?}, match self.{p_name}.as_ref() {{");
+        w!("            Some({variants}) | None => None,");
+        w!("            Some(value) => Some({rsolid}::scad::Scad::assign(value, f)),");
+        w!("        }}),");
+
+        for (idx, arg) in param.split.iter().enumerate() {
+            w!("        ({arg:?}, match self.{p_name}.as_ref() {{");
+            for variant in &split {
+                w!("            Some({name}::{variant}(value)) => Some({rsolid}::scad::Scad::assign(&value.0[{idx}], f)),");
+            }
+            w!("            _ => None,");
+            w!("        }}),");
+        }
+    }
+
+    Ok(())
+}
+
+static DEFAULT_CONSTRUCTOR: &str = "_default";
+
 pub fn generate<O: Write>(options: &Options, defs: &[Definitions], out: &mut O) -> io::Result<()> {
     macro_rules! w {
         ($($tt:tt)*) => {
@@ -50,7 +274,7 @@ pub fn generate<O: Write>(options: &Options, defs: &[Definitions], out: &mut O)
 
     for def in defs {
         for (name, m) in def.modules.iter() {
-            let upper = name.to_pascal_case();
+            let upper = m.builder.clone().unwrap_or_else(|| name.to_pascal_case());
             let (dim_gen, dim_gen_constraint, dim_gen_arg) = if m.dimensions == 0 {
                 (
                     "const DIMENSIONS: usize, ",
@@ -61,9 +285,9 @@ pub fn generate<O: Write>(options: &Options, defs: &[Definitions], out: &mut O)
                 ("", "", "")
             };
 
-            let is_copy = m.parameters.values().all(|p| p.ty.is_copy());
+            let is_copy = m.parameters.values().all(|p| p.is_copy());
 
-            for line in m.docs.lines() {
+            for line in docs(&m.docs) {
                 w!("/// {line}");
             }
 
@@ -73,18 +297,24 @@ pub fn generate<O: Write>(options: &Options, defs: &[Definitions], out: &mut O)
                 w!("{}", "#[derive(Clone, Default)]");
             }
             w!("#[must_use = \"Objects must be returned in order to be rendered\"]");
+            // the fields are shared with the rest of the crate, e.g. for evaluating meshes
+            let vis = if options.main_crate {
+                "pub(crate) "
+            } else {
+                ""
+            };
             w!("pub struct {upper} {dim_gen_constraint} {{");
             for (p_name, param) in m.parameters.iter() {
-                let ty = options.translate_type(param.ty);
-                w!("    {p_name}: Option<{ty}>,");
+                let ty = options.param_type(&upper, p_name, param);
+                w!("    {vis}{p_name}: Option<{ty}>,");
             }
             w!("}}");
             w!();
 
-            static DEFAULT_CONSTRUCTOR: &str = "_default";
+            generate_alts(options, &upper, &m.parameters, out)?;
 
             if !m.constructors.contains_key(DEFAULT_CONSTRUCTOR) {
-                for line in m.docs.lines() {
+                for line in docs(&m.docs) {
                     w!("/// {line}");
                 }
                 w!("#[inline]");
@@ -93,7 +323,7 @@ pub fn generate<O: Write>(options: &Options, defs: &[Definitions], out: &mut O)
                 // if we only have one argument then just make it the default
                 if m.parameters.len() == 1 {
                     for (arg, param) in &m.parameters {
-                        let ty = options.translate_type(param.ty);
+                        let ty = options.param_type(&upper, arg, param);
                         w!("    {arg}: impl Into<{ty}>,");
                     }
                 }
@@ -115,7 +345,7 @@ pub fn generate<O: Write>(options: &Options, defs: &[Definitions], out: &mut O)
                 } else {
                     cname
                 };
-                for line in c.docs.lines() {
+                for line in docs(&c.docs) {
                     w!("/// {line}");
                 }
                 w!("#[inline]");
@@ -125,7 +355,7 @@ pub fn generate<O: Write>(options: &Options, defs: &[Definitions], out: &mut O)
                         .parameters
                         .get(arg)
                         .unwrap_or_else(|| panic!("invalid argument {arg} in constructor {cname}"));
-                    let ty = options.translate_type(param.ty);
+                    let ty = options.param_type(&upper, arg, param);
                     w!("    {arg}: impl Into<{ty}>,");
                 }
                 w!(") -> {upper} {dim_gen_arg} {{");
@@ -144,8 +374,8 @@ pub fn generate<O: Write>(options: &Options, defs: &[Definitions], out: &mut O)
                     if !core::mem::take(&mut first) {
                         w!();
                     }
-                    let ty = options.translate_type(param.ty);
-                    for line in param.docs.lines() {
+                    let ty = options.param_type(&upper, p_name, param);
+                    for line in docs(&param.docs) {
                         w!("    /// {line}");
                     }
                     w!("    #[inline]");
@@ -213,12 +443,7 @@ pub fn generate<O: Write>(options: &Options, defs: &[Definitions], out: &mut O)
                 w!("        let name = {name:?};");
             };
             w!("        let args = [");
-            for (p_name, param) in m.parameters.iter() {
-                let scad_name = param.name.as_ref().unwrap_or(p_name);
-                w!("        ({scad_name:?}, self.{p_name}.as_ref().map(|value| {{");
-                w!("            {rsolid}::scad::Scad::assign(value, f)");
-                w!("        }})),");
-            }
+            generate_args(options, &upper, &m.parameters, false, out)?;
             w!("        ];");
             w!("        f.call(name, args, {})", m.operator);
             w!("    }}");
@@ -232,6 +457,39 @@ pub fn generate<O: Write>(options: &Options, defs: &[Definitions], out: &mut O)
             w!("}}");
             w!();
 
+            w!("impl{dim_gen_constraint} {rsolid}::Node for {upper}{dim_gen_arg} {{");
+            w!("    fn name(&self) -> &str {{");
+            w!("        {name:?}");
+            w!("    }}");
+            w!();
+            if m.operator {
+                w!("    fn kind(&self) -> {rsolid}::NodeKind {{");
+                w!("        {rsolid}::NodeKind::Operator");
+                w!("    }}");
+                w!();
+            }
+            w!("    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {{");
+            if m.parameters.is_empty() {
+                w!("        Vec::new()");
+            } else {
+                w!("        let mut out: Vec<(&'static str, &dyn ::core::any::Any)> = Vec::new();");
+                for p_name in m.parameters.keys() {
+                    w!("        if let Some(value) = self.{p_name}.as_ref() {{");
+                    w!("            out.push(({p_name:?}, value));");
+                    w!("        }}");
+                }
+                w!("        out");
+            }
+            w!("    }}");
+            if let Some(report) = m.report.as_ref() {
+                w!();
+                w!("    fn report(&self, f: &mut {rsolid}::scad::Formatter) {{");
+                w!("        {rsolid}::{report}(self, f)");
+                w!("    }}");
+            }
+            w!("}}");
+            w!();
+
             let (dim_out, dim_in) = if m.dimensions == 0 {
                 ("DIMENSIONS".to_string(), "DIMENSIONS".to_string())
             } else {
@@ -289,7 +547,166 @@ pub fn generate<O: Write>(options: &Options, defs: &[Definitions], out: &mut O)
             w!("}}");
             w!();
         }
+
+        for (name, func) in def.functions.iter() {
+            let upper = name.to_pascal_case();
+            let scad_name = func.name.as_deref().unwrap_or(name);
+            let is_copy = func.parameters.values().all(|p| p.is_copy());
+
+            for line in docs(&func.docs) {
+                w!("/// {line}");
+            }
+
+            if is_copy {
+                w!("{}", "#[derive(Clone, Copy, Default)]");
+            } else {
+                w!("{}", "#[derive(Clone, Default)]");
+            }
+            w!("#[must_use = \"Values must be used in order to be rendered\"]");
+            w!("pub struct {upper} {{");
+            for (p_name, param) in func.parameters.iter() {
+                let ty = options.param_type(&upper, p_name, param);
+                w!("    {p_name}: Option<{ty}>,");
+            }
+            w!("}}");
+            w!();
+
+            generate_alts(options, &upper, &func.parameters, out)?;
+
+            let mut constructors: Vec<_> = func
+                .constructors
+                .iter()
+                .map(|(cname, c)| (cname.as_str(), c.docs.as_str(), c.arguments.clone()))
+                .collect();
+
+            if !func.constructors.contains_key(DEFAULT_CONSTRUCTOR) {
+                // default to taking all of the positional arguments, in order
+                let mut arguments: Vec<_> = func
+                    .parameters
+                    .iter()
+                    .filter_map(|(p_name, param)| Some((param.position?, p_name.clone())))
+                    .collect();
+                arguments.sort();
+                let arguments = arguments.into_iter().map(|(_, arg)| arg).collect();
+                constructors.push((DEFAULT_CONSTRUCTOR, &func.docs, arguments));
+            }
+
+            for (cname, cdocs, arguments) in constructors {
+                let cname = if cname == DEFAULT_CONSTRUCTOR {
+                    name
+                } else {
+                    cname
+                };
+                for line in docs(cdocs) {
+                    w!("/// {line}");
+                }
+                w!("#[inline]");
+                w!("pub fn {cname}(");
+                for arg in &arguments {
+                    let param = func
+                        .parameters
+                        .get(arg)
+                        .unwrap_or_else(|| panic!("invalid argument {arg} in constructor {cname}"));
+                    let ty = options.param_type(&upper, arg, param);
+                    w!("    {arg}: impl Into<{ty}>,");
+                }
+                w!(") -> {upper} {{");
+                w!("    {upper}::default()");
+                for arg in &arguments {
+                    w!("        .{arg}({arg})");
+                }
+                w!("}}");
+                w!();
+            }
+
+            w!("impl {upper} {{");
+            {
+                let mut first = true;
+                for (p_name, param) in func.parameters.iter() {
+                    if !core::mem::take(&mut first) {
+                        w!();
+                    }
+                    let ty = options.param_type(&upper, p_name, param);
+                    for line in docs(&param.docs) {
+                        w!("    /// {line}");
+                    }
+                    w!("    #[inline]");
+                    w!("    pub fn {p_name}<T: Into<{ty}>>(mut self, {p_name}: T) -> Self {{");
+                    w!("        self.{p_name} = Some({p_name}.into());");
+                    w!("        self");
+                    w!("    }}");
+                }
+            }
+            w!("}}");
+            w!();
+
+            w!("impl ::core::fmt::Debug for {upper} {{");
+            w!("    #[allow(clippy::write_literal)]");
+            w!("    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {{");
+            w!("        let mut s = f.debug_struct({scad_name:?});");
+            for (p_name, param) in func.parameters.iter() {
+                w!("        if let Some(value) = self.{p_name}.as_ref() {{");
+                let scad_name = param.name.as_ref().unwrap_or(p_name);
+                w!("             s.field({scad_name:?}, value);");
+                w!("        }}");
+            }
+            w!("        s.finish()");
+            w!("    }}");
+            w!("}}");
+            w!();
+
+            // positional arguments go first, followed by the named ones
+            let mut args: Vec<_> = func.parameters.iter().collect();
+            args.sort_by_key(|(_, param)| param.position.map_or((1, 0), |pos| (0, pos)));
+
+            w!("impl {rsolid}::scad::Scad for {upper} {{");
+            w!("    fn assign(&self, f: &mut {rsolid}::scad::Formatter) -> {rsolid}::scad::Assignment {{");
+            for inc in &func.imports {
+                if let Some(inc) = inc.strip_prefix("use ") {
+                    w!("        f.uses({inc:?});");
+                } else if let Some(inc) = inc.strip_prefix("include ") {
+                    w!("        f.includes({inc:?});");
+                } else {
+                    w!("        f.uses({inc:?});");
+                }
+            }
+            w!("        let name = {scad_name:?};");
+            w!("        let args = [");
+            generate_args(options, &upper, args, true, out)?;
+            w!("        ];");
+            w!("        f.function(name, args)");
+            w!("    }}");
+            w!("}}");
+            w!();
+
+            w!("impl ::core::fmt::Display for {upper} {{");
+            w!("    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {{");
+            w!("        f.write_str(&{rsolid}::scad::Scad::to_scad(self))");
+            w!("    }}");
+            w!("}}");
+            w!();
+
+            // functions are only useful as values, so each one converts into what it returns
+            assert!(
+                matches!(func.returns, Type::Scalar | Type::Length | Type::Angle),
+                "function {name} can only return a scalar, length or angle"
+            );
+            let ty = options.translate_type(func.returns);
+            w!("impl From<{upper}> for {ty} {{");
+            w!("    #[inline]");
+            w!("    fn from(value: {upper}) -> Self {{");
+            w!("        Self({rsolid}::Expr::value(value))");
+            w!("    }}");
+            w!("}}");
+            w!();
+        }
     }
 
     Ok(())
 }
+
+/// Returns the doc lines to emit, skipping docs that are entirely blank
+fn docs(docs: &str) -> core::str::Lines<'_> {
+    let docs = if docs.trim().is_empty() { "" } else { docs };
+    docs.lines()
+}
@@ -23,6 +23,11 @@ pub struct Function {
     pub parameters: BTreeMap<String, Parameter>,
     #[serde(default)]
     pub constructors: BTreeMap<String, Constructor>,
+    #[serde(default)]
+    pub imports: Vec<String>,
+    /// The type of the returned value, which the generated builder converts into so the function
+    /// can be used as a parameter or in expressions
+    pub returns: Type,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -30,6 +35,9 @@ pub struct Function {
 pub struct Module {
     #[serde(default)]
     pub name: Option<String>,
+    /// Overrides the name of the generated builder, which defaults to the pascal-cased module name
+    #[serde(default)]
+    pub builder: Option<String>,
     #[serde(default)]
     pub docs: String,
     pub dimensions: u8,
@@ -47,6 +55,10 @@ pub struct Module {
     pub constructors: BTreeMap<String, Constructor>,
     #[serde(default)]
     pub imports: Vec<String>,
+    /// Path of a crate function, like `extrude::report`, that reports problems with the builder's
+    /// parameters when it's exported
+    #[serde(default)]
+    pub report: Option<String>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -62,9 +74,25 @@ pub struct Parameter {
     pub ty: Type,
     #[serde(default)]
     pub alt: Vec<Type>,
+    /// Passes each component of a vector alternate as a separate argument with the given names
+    ///
+    /// For example, `cylinder` takes a pair of radii as `r1` and `r2`.
+    #[serde(default)]
+    pub split: Vec<String>,
+    /// Passes the parameter by position instead of by name
+    ///
+    /// This is mostly needed for builtin functions, which don't accept named arguments.
+    #[serde(default)]
+    pub position: Option<usize>,
+}
+
+impl Parameter {
+    pub fn is_copy(&self) -> bool {
+        self.ty.is_copy() && self.alt.iter().all(|ty| ty.is_copy())
+    }
 }
 
-#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum Type {
     Bool,
@@ -78,14 +106,48 @@ pub enum Type {
     Angle2,
     Angle3,
     FragmentResolution,
+    Color,
     String,
+    VecScalar2,
     VecLength2,
     VecLength3,
+    VecFace,
 }
 
 impl Type {
+    /// Returns true if the type holds an `Expr`, so that it can be set to a variable
+    pub fn is_expr(&self) -> bool {
+        match self {
+            Self::Scalar
+            | Self::Scalar2
+            | Self::Scalar3
+            | Self::Length
+            | Self::Length2
+            | Self::Length3
+            | Self::Angle
+            | Self::Angle2
+            | Self::Angle3
+            | Self::FragmentResolution
+            | Self::VecScalar2
+            | Self::VecLength2
+            | Self::VecLength3 => true,
+            Self::Bool | Self::Color | Self::String | Self::VecFace => false,
+        }
+    }
+
+    /// Returns true if the type is `Copy`, which is the case for the ones that don't hold an
+    /// `Expr` or a heap allocation
     pub fn is_copy(&self) -> bool {
-        !matches!(self, Self::String | Self::VecLength2 | Self::VecLength3)
+        !self.is_expr() && !matches!(self, Self::String | Self::VecFace)
+    }
+
+    /// Returns the number of components in a fixed-size vector type
+    pub fn arity(&self) -> Option<usize> {
+        match self {
+            Self::Scalar2 | Self::Length2 | Self::Angle2 => Some(2),
+            Self::Scalar3 | Self::Length3 | Self::Angle3 => Some(3),
+            _ => None,
+        }
     }
 }
 
@@ -0,0 +1,357 @@
+//! Named anchors on objects and attaching objects to each other, like BOSL2's `attach`
+//!
+//! An [`Anchor`] is a direction from the center of an object, so `TOP` is the middle of the
+//! top face, `TOP + LEFT` the middle of the top left edge and `TOP + LEFT + FRONT` a corner.
+//! The points are worked out in Rust from the object's geometry, so spheres and cylinders
+//! are anchored on their curved surfaces and everything else on its bounding box.
+//!
+//! ```
+//! use rsolid::{anchor::*, *};
+//!
+//! let base = cube([20, 20, 4]).into_object();
+//! let post = cylinder(10, 2) >> attach(&base, TOP, BOTTOM);
+//! let knob = sphere(3) >> attach(&post, TOP, CENTER);
+//!
+//! let _ = base + post + knob;
+//! ```
+
+use crate::{
+    math::{self, length, num, vec2, Affine, Vec3},
+    operator::Wrapped,
+    primitive::*,
+    AnyObject, IntoObject, Object, Operator,
+};
+use core::{fmt, ops};
+
+/// A direction from the center of an object, with components in the range `-1.0..=1.0`
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Anchor(pub [f64; 3]);
+
+pub const CENTER: Anchor = Anchor([0.0, 0.0, 0.0]);
+pub const TOP: Anchor = Anchor([0.0, 0.0, 1.0]);
+pub const BOTTOM: Anchor = Anchor([0.0, 0.0, -1.0]);
+pub const LEFT: Anchor = Anchor([-1.0, 0.0, 0.0]);
+pub const RIGHT: Anchor = Anchor([1.0, 0.0, 0.0]);
+pub const FRONT: Anchor = Anchor([0.0, -1.0, 0.0]);
+pub const BACK: Anchor = Anchor([0.0, 1.0, 0.0]);
+
+impl Anchor {
+    /// Drops the components that the given dimensions don't have
+    #[inline]
+    fn vec<const DIMENSIONS: usize>(self) -> Vec3 {
+        let [x, y, z] = self.0;
+        if DIMENSIONS == 2 {
+            Vec3::new(x, y, 0.0)
+        } else {
+            Vec3::new(x, y, z)
+        }
+    }
+}
+
+impl ops::Add for Anchor {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, other: Self) -> Self {
+        Self(core::array::from_fn(|axis| self.0[axis] + other.0[axis]))
+    }
+}
+
+impl ops::Sub for Anchor {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, other: Self) -> Self {
+        Self(core::array::from_fn(|axis| self.0[axis] - other.0[axis]))
+    }
+}
+
+impl ops::Neg for Anchor {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        Self(self.0.map(|v| -v))
+    }
+}
+
+impl From<[f64; 3]> for Anchor {
+    #[inline]
+    fn from(value: [f64; 3]) -> Self {
+        Self(value)
+    }
+}
+
+impl<const DIMENSIONS: usize> Object<DIMENSIONS> {
+    /// Returns the position of the anchor on the object, or `None` if the object can't be
+    /// measured
+    pub fn anchor(&self, anchor: Anchor) -> Option<[f64; DIMENSIONS]> {
+        let p = position(&self.into(), anchor.vec::<DIMENSIONS>())?;
+        let p = [p.x, p.y, p.z];
+        Some(core::array::from_fn(|axis| p[axis]))
+    }
+}
+
+fn position(object: &AnyObject, anchor: Vec3) -> Option<Vec3> {
+    let dir = anchor.unit();
+
+    if let Some(sphere) = object.downcast_ref::<Sphere>() {
+        let r = length(&sphere.radius, 1.0).ok()?;
+        return Some(dir * r);
+    }
+
+    if let Some(circle) = object.downcast_ref::<Circle>() {
+        let r = length(&circle.radius, 1.0).ok()?;
+        return Some(dir * r);
+    }
+
+    if let Some(cylinder) = object.downcast_ref::<Cylinder>() {
+        let (r1, r2) = match &cylinder.radius {
+            Some(CylinderRadius::Length(r)) => (num(&r.0).ok()?, num(&r.0).ok()?),
+            Some(CylinderRadius::Length2(r)) => vec2(r).ok()?.into(),
+            None => (1.0, 1.0),
+        };
+        let r1 = length(&cylinder.radius_bottom, r1).ok()?;
+        let r2 = length(&cylinder.radius_top, r2).ok()?;
+        let h = length(&cylinder.height, 1.0).ok()?;
+        let z = if cylinder.center == Some(true) {
+            0.0
+        } else {
+            h / 2.0
+        };
+
+        // the radius changes along the height of a cone
+        let t = (anchor.z.clamp(-1.0, 1.0) + 1.0) / 2.0;
+        let r = r1 + (r2 - r1) * t;
+        let side = Vec3::new(anchor.x, anchor.y, 0.0).unit();
+        return Some(side * r + Vec3::new(0.0, 0.0, z + anchor.z.clamp(-1.0, 1.0) * h / 2.0));
+    }
+
+    // translations keep the anchors of curved children
+    macro_rules! translated {
+        ($($dim:literal),*) => {
+            $(
+                if let Some(wrapped) = object.downcast_ref::<Wrapped<$dim, $dim>>() {
+                    if let Ok(Some(m)) = math::transform(&wrapped.parent) {
+                        if is_translation(&m) {
+                            let child = position(&(&wrapped.child).into(), anchor)?;
+                            return Some(m.apply(child));
+                        }
+                    }
+                }
+            )*
+        };
+    }
+
+    translated!(2, 3);
+
+    let bounds = object_bounds(object)?;
+    let (min, max) = (Vec3::from(bounds.0), Vec3::from(bounds.1));
+    let center = (min + max) * 0.5;
+    let half = (max - min) * 0.5;
+    Some(Vec3::new(
+        center.x + anchor.x * half.x,
+        center.y + anchor.y * half.y,
+        center.z + anchor.z * half.z,
+    ))
+}
+
+fn object_bounds(object: &AnyObject) -> Option<([f64; 3], [f64; 3])> {
+    if let Some(object) = object.downcast::<3>() {
+        let bounds = object.bounds()?;
+        return Some((bounds.min, bounds.max));
+    }
+    let bounds = object.downcast::<2>()?.bounds()?;
+    let [min, max] = [bounds.min, bounds.max].map(|[x, y]| [x, y, 0.0]);
+    Some((min, max))
+}
+
+fn is_translation(m: &Affine) -> bool {
+    (0..3).all(|row| (0..3).all(|col| m.0[row][col] == if row == col { 1.0 } else { 0.0 }))
+}
+
+/// Positions the children so their `anchor` touches the `at` anchor of `parent`
+///
+/// The children are also rotated so the direction of their anchor points back into the
+/// parent, e.g. attaching `BOTTOM` to the `RIGHT` of a part makes the children stick out to
+/// the right. Only the children are returned, so they still need to be added to the parent.
+///
+/// Applying it panics if either anchor can't be worked out, which [`try_attach`] reports as an
+/// error instead.
+#[inline]
+pub fn attach<const DIMENSIONS: usize>(
+    parent: impl IntoObject<DIMENSIONS>,
+    at: Anchor,
+    anchor: Anchor,
+) -> Attach<DIMENSIONS> {
+    Attach {
+        parent: parent.into_object(),
+        at,
+        anchor,
+    }
+}
+
+#[derive(Clone, Debug)]
+#[must_use = "Operators must be applied to an object in order to be rendered"]
+pub struct Attach<const DIMENSIONS: usize> {
+    parent: Object<DIMENSIONS>,
+    at: Anchor,
+    anchor: Anchor,
+}
+
+impl<const DIMENSIONS: usize> Attach<DIMENSIONS> {
+    fn attached(&self, object: &Object<DIMENSIONS>) -> Result<Object<DIMENSIONS>, AttachError> {
+        let at = self.at.vec::<DIMENSIONS>();
+        let anchor = self.anchor.vec::<DIMENSIONS>();
+
+        let unknown = |object: &Object<DIMENSIONS>| AttachError {
+            object: object.name().to_string(),
+        };
+        let target = position(&(&self.parent).into(), at).ok_or_else(|| unknown(&self.parent))?;
+        let origin = position(&object.into(), anchor).ok_or_else(|| unknown(object))?;
+
+        let mut out = object.clone();
+        if origin != Vec3::default() {
+            out = out >> translate(vec(-origin));
+        }
+
+        // turn the child's anchor to face the opposite way to the parent's anchor
+        let (from, to) = (anchor.unit(), -at.unit());
+        if from != Vec3::default() && to != Vec3::default() {
+            let cos = from.dot(to).clamp(-1.0, 1.0);
+            let mut axis = from.cross(to);
+            if axis.length() < 1e-9 && cos < 0.0 {
+                // opposite directions can be turned around any perpendicular axis, so prefer
+                // the Z axis which also works in 2D
+                let z = Vec3::new(0.0, 0.0, 1.0);
+                axis = z - from * from.dot(z);
+                if axis.length() < 1e-9 {
+                    let x = Vec3::new(1.0, 0.0, 0.0);
+                    axis = x - from * from.dot(x);
+                }
+            }
+            let angle = math::round(cos.acos().to_degrees());
+            if angle != 0.0 {
+                out = out >> rotate(angle).value(vec(axis.unit()));
+            }
+        }
+
+        if target != Vec3::default() {
+            out = out >> translate(vec(target));
+        }
+
+        Ok(out)
+    }
+}
+
+impl<const DIMENSIONS: usize> Operator<DIMENSIONS> for Attach<DIMENSIONS> {
+    type Output = Object<DIMENSIONS>;
+
+    /// # Panics
+    ///
+    /// Panics if either of the anchors can't be worked out, e.g. for imports. Use
+    /// [`try_attach`] to handle that instead.
+    fn apply(self, object: &Object<DIMENSIONS>) -> Self::Output {
+        self.attached(object).unwrap_or_else(|err| panic!("{err}"))
+    }
+}
+
+/// Like [`attach`], but gives an error instead of panicking when the anchors of either object
+/// can't be worked out
+///
+/// ```
+/// use rsolid::{anchor::*, *};
+///
+/// assert!((cube(1) >> try_attach(stl("part.stl"), TOP, BOTTOM)).is_err());
+/// ```
+#[inline]
+pub fn try_attach<const DIMENSIONS: usize>(
+    parent: impl IntoObject<DIMENSIONS>,
+    at: Anchor,
+    anchor: Anchor,
+) -> TryAttach<DIMENSIONS> {
+    TryAttach(attach(parent, at, anchor))
+}
+
+#[derive(Clone, Debug)]
+#[must_use = "Operators must be applied to an object in order to be rendered"]
+pub struct TryAttach<const DIMENSIONS: usize>(Attach<DIMENSIONS>);
+
+impl<const DIMENSIONS: usize> Operator<DIMENSIONS> for TryAttach<DIMENSIONS> {
+    type Output = Result<Object<DIMENSIONS>, AttachError>;
+
+    #[inline]
+    fn apply(self, object: &Object<DIMENSIONS>) -> Self::Output {
+        self.0.attached(object)
+    }
+}
+
+/// An object whose anchors can't be worked out in Rust, like an import
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AttachError {
+    /// The name of the object's node
+    pub object: String,
+}
+
+impl fmt::Display for AttachError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "can't work out the anchors of {}", self.object)
+    }
+}
+
+impl std::error::Error for AttachError {}
+
+fn vec(v: Vec3) -> [f64; 3] {
+    [v.x, v.y, v.z].map(math::round)
+}
+
+#[test]
+fn anchor_test() {
+    use crate::*;
+
+    let part = cube([10, 20, 30]).into_object();
+    assert_eq!(part.anchor(TOP), Some([5.0, 10.0, 30.0]));
+    assert_eq!(part.anchor(BOTTOM + LEFT + FRONT), Some([0.0, 0.0, 0.0]));
+    assert_eq!(part.up(5).anchor(CENTER), Some([5.0, 10.0, 20.0]));
+
+    let ball = sphere(2).right(10).into_object();
+    let corner = ball.anchor(TOP + RIGHT).unwrap();
+    assert!((corner[0] - 10.0 - 2f64.sqrt()).abs() < 1e-9, "{corner:?}");
+
+    let cone = cylinder(10, [4, 2]).into_object();
+    assert_eq!(cone.anchor(RIGHT), Some([3.0, 0.0, 5.0]));
+    assert_eq!(cone.anchor(TOP + BACK), Some([0.0, 2.0, 10.0]));
+
+    assert_eq!(
+        square([4, 2]).into_object().anchor(BACK + TOP),
+        Some([2.0, 2.0])
+    );
+    assert_eq!(stl("part.stl").anchor(TOP), None);
+}
+
+#[test]
+fn attach_test() {
+    use crate::*;
+
+    let base = cube([20, 20, 4]).into_object();
+    let post = cylinder(10, 2) >> attach(&base, TOP, BOTTOM);
+    assert_eq!(post.anchor(BOTTOM), Some([10.0, 10.0, 4.0]));
+
+    let side = cylinder(10, 2) >> attach(&base, RIGHT, BOTTOM);
+    let bounds = side.bounds().unwrap();
+    assert_eq!(bounds.min[0], 20.0);
+    assert_eq!(bounds.max[0], 30.0);
+
+    let under = cube(2) >> attach(&base, BOTTOM, BOTTOM);
+    assert_eq!(under.bounds().unwrap().max[2], 0.0);
+
+    let tab = square(2) >> attach(square(10), RIGHT, LEFT);
+    assert_eq!(tab.anchor(LEFT), Some([10.0, 5.0]));
+
+    let err = (cube(1) >> try_attach(stl("part.stl"), TOP, BOTTOM)).unwrap_err();
+    assert_eq!(err.to_string(), "can't work out the anchors of import");
+    assert!((stl("part.stl") >> try_attach(&base, TOP, BOTTOM)).is_err());
+    assert!((cube(1) >> try_attach(&base, TOP, BOTTOM)).is_ok());
+
+    insta::assert_snapshot!(base.clone() + post + side + under);
+}
@@ -0,0 +1,236 @@
+//! Keyframed animations driven by OpenSCAD's `$t`
+//!
+//! A [`Track`] interpolates between keyframes at times in the range `0.0..=1.0` and
+//! emits the interpolation as an expression on `$t`, so the animation is played back
+//! by OpenSCAD itself, either in the GUI or with [`export::animation`](crate::export::animation).
+//!
+//! ```
+//! use rsolid::{animation::*, *};
+//!
+//! let slide = Track::<Length3>::new([0, 0, 0])
+//!     .key(0.5, [20, 0, 0])
+//!     .key_with(1.0, [20, 0, 10], Easing::EaseInOut);
+//!
+//! let _ = cube(10) >> slide.translate();
+//! ```
+
+use crate::{
+    expr::Condition,
+    primitive::{rotate, translate, Rotate, Translate},
+    types::{Angle, Angle3, Length, Length2, Length3, Scalar, Scalar2, Scalar3},
+    var::t,
+};
+
+/// Shapes the progress between two keyframes
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Easing {
+    #[default]
+    Linear,
+    /// Starts slowly and speeds up
+    EaseIn,
+    /// Starts quickly and slows down
+    EaseOut,
+    /// Starts and ends slowly
+    EaseInOut,
+    /// Holds the previous keyframe until the next one is reached
+    Step,
+}
+
+impl Easing {
+    /// Maps the progress `u` in the range `0.0..=1.0` to the eased progress
+    pub fn apply(self, u: impl Into<Scalar>) -> Scalar {
+        let u = u.into();
+        match self {
+            Self::Linear => u,
+            Self::EaseIn => u.clone() * u,
+            Self::EaseOut => u.clone() * (Scalar::from(2) - u),
+            Self::EaseInOut => {
+                let early = Scalar::from(2) * u.clone() * u.clone();
+                let late =
+                    Scalar::from(-1) + (Scalar::from(4) - Scalar::from(2) * u.clone()) * u.clone();
+                u.lt(0.5).select(early, late)
+            }
+            Self::Step => u.lt(1).select(0, 1),
+        }
+    }
+}
+
+/// A value that can be interpolated between keyframes
+pub trait Keyframe: Clone {
+    /// Returns the value at `u` of the way from `a` to `b`
+    fn lerp(a: &Self, b: &Self, u: &Scalar) -> Self;
+
+    /// Returns `a` if the condition holds, otherwise `b`
+    fn select(cond: &Condition, a: Self, b: Self) -> Self;
+}
+
+macro_rules! impl_keyframe {
+    ($name:ident) => {
+        impl Keyframe for $name {
+            #[inline]
+            fn lerp(a: &Self, b: &Self, u: &Scalar) -> Self {
+                a.clone() + (b.clone() - a.clone()) * u.clone()
+            }
+
+            #[inline]
+            fn select(cond: &Condition, a: Self, b: Self) -> Self {
+                cond.clone().select(a, b)
+            }
+        }
+    };
+    ($name:ident, $count:literal) => {
+        impl Keyframe for $name {
+            #[inline]
+            fn lerp(a: &Self, b: &Self, u: &Scalar) -> Self {
+                Self(core::array::from_fn(|idx| {
+                    Keyframe::lerp(&a.0[idx], &b.0[idx], u)
+                }))
+            }
+
+            #[inline]
+            fn select(cond: &Condition, a: Self, b: Self) -> Self {
+                let mut b = b.0.into_iter();
+                Self(a.0.map(|a| Keyframe::select(cond, a, b.next().unwrap())))
+            }
+        }
+    };
+}
+
+impl_keyframe!(Angle);
+impl_keyframe!(Length);
+impl_keyframe!(Scalar);
+impl_keyframe!(Angle3, 3);
+impl_keyframe!(Length2, 2);
+impl_keyframe!(Length3, 3);
+impl_keyframe!(Scalar2, 2);
+impl_keyframe!(Scalar3, 3);
+
+/// A sequence of keyframes, interpolated over the animation time `$t`
+#[derive(Clone, Debug)]
+#[must_use = "Tracks must be converted into a value or operator in order to be used"]
+pub struct Track<T> {
+    start: T,
+    keys: Vec<(f64, T, Easing)>,
+    easing: Easing,
+}
+
+impl<T: Keyframe> Track<T> {
+    /// Creates a track that starts at `value`
+    #[inline]
+    pub fn new(value: impl Into<T>) -> Self {
+        Self {
+            start: value.into(),
+            keys: Vec::new(),
+            easing: Easing::Linear,
+        }
+    }
+
+    /// Sets the easing used by keyframes that are added afterwards with [`Track::key`]
+    #[inline]
+    pub fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Moves to `value` by `time`, using the track's easing
+    #[inline]
+    pub fn key(self, time: f64, value: impl Into<T>) -> Self {
+        let easing = self.easing;
+        self.key_with(time, value, easing)
+    }
+
+    /// Moves to `value` by `time`, using the given easing
+    ///
+    /// # Panics
+    ///
+    /// Panics if `time` is outside of the range `0.0..=1.0` or before the previous keyframe.
+    pub fn key_with(mut self, time: f64, value: impl Into<T>, easing: Easing) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&time),
+            "keyframe time {time} is outside of the range 0.0..=1.0"
+        );
+        if let Some((prev, ..)) = self.keys.last() {
+            assert!(
+                *prev <= time,
+                "keyframe at {time} comes before the previous keyframe at {prev}"
+            );
+        }
+        self.keys.push((time, value.into(), easing));
+        self
+    }
+
+    /// Returns the value of the track at `$t`
+    #[inline]
+    pub fn value(&self) -> T {
+        self.at(t())
+    }
+
+    /// Returns the value of the track at the given time
+    pub fn at(&self, time: impl Into<Scalar>) -> T {
+        let time = time.into();
+
+        // build the segments from the end, so each one falls through to the next
+        let mut value = self.keys.last().map_or(&self.start, |(_, v, _)| v).clone();
+
+        let mut prev_time = 0.0;
+        let mut segments = Vec::with_capacity(self.keys.len());
+        let mut from = &self.start;
+        for (end, to, easing) in &self.keys {
+            segments.push((prev_time, *end, from, to, *easing));
+            prev_time = *end;
+            from = to;
+        }
+
+        for (start, end, from, to, easing) in segments.into_iter().rev() {
+            let segment = if end > start {
+                let u = (time.clone() - start) / (end - start);
+                T::lerp(from, to, &easing.apply(u))
+            } else {
+                // a jump between two keyframes at the same time
+                to.clone()
+            };
+            value = T::select(&time.clone().lt(end), segment, value);
+        }
+
+        value
+    }
+}
+
+impl Track<Length3> {
+    /// Translates the children by the value of the track
+    #[inline]
+    pub fn translate<const DIMENSIONS: usize>(&self) -> Translate<DIMENSIONS> {
+        translate(self.value())
+    }
+}
+
+impl Track<Angle3> {
+    /// Rotates the children by the value of the track
+    #[inline]
+    pub fn rotate<const DIMENSIONS: usize>(&self) -> Rotate<DIMENSIONS> {
+        rotate(self.value())
+    }
+}
+
+impl Track<Angle> {
+    /// Rotates the children around the Z axis by the value of the track
+    #[inline]
+    pub fn rotate<const DIMENSIONS: usize>(&self) -> Rotate<DIMENSIONS> {
+        rotate(self.value())
+    }
+}
+
+#[test]
+fn track_test() {
+    use crate::*;
+
+    let slide = Track::<Length3>::new([0, 0, 0])
+        .key(0.5, [20, 0, 0])
+        .key_with(1.0, [20, 0, 10], Easing::EaseIn);
+    let spin = Track::<Angle>::new(0)
+        .easing(Easing::EaseInOut)
+        .key(1.0, 360);
+
+    let parts = cube(10) >> slide.translate() >> spin.rotate();
+    insta::assert_snapshot!(parts);
+}
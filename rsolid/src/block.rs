@@ -9,24 +9,12 @@ macro_rules! impl_block {
         // done
     };
     ($head:ident($h_value:tt), $($tail:ident($t_value:tt), )* [$($acc:ident($a_value:tt),)*]) => {
-        impl<$head: IntoObject<DIM> $(, $acc: IntoObject<DIM>)*, const DIM: usize> fmt::Display for ($($acc, )* $head ,) {
-            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-                f.write_str(&self.to_scad())
-            }
-        }
-
-        impl<$head: IntoObject<DIM> $(, $acc: IntoObject<DIM>)*, const DIM: usize> Scad for ($($acc, )* $head ,) {
-            fn assign(&self, f: &mut scad::Formatter) -> scad::Assignment {
-
-                todo!()
-            }
-        }
-
         impl<$head: IntoObject<DIM> $(, $acc: IntoObject<DIM>)*, const DIM: usize> IntoObject<DIM> for ($($acc, )* $head ,) {
+            #[inline]
             fn into_object(self) -> Object<DIM> {
-                Object::new(($(
+                Block(vec![$(
                     self.$a_value.into_object(),
-                )* self.$h_value.into_object(),))
+                )* self.$h_value.into_object()]).into_object()
             }
         }
 
@@ -35,9 +23,8 @@ macro_rules! impl_block {
 }
 
 impl_block!(
-    // A(0),
-    //B(1),
-    /*
+    A(0),
+    B(1),
     C(2),
     D(3),
     E(4),
@@ -68,13 +55,26 @@ impl_block!(
     AD(29),
     AE(30),
     AF(31),
-    AG(32),
-    */
     []
 );
 
+impl<const DIMENSIONS: usize, T: IntoObject<DIMENSIONS>> IntoObject<DIMENSIONS> for Vec<T> {
+    #[inline]
+    fn into_object(self) -> Object<DIMENSIONS> {
+        self.into_iter().collect()
+    }
+}
+
+impl<const DIMENSIONS: usize, T: IntoObject<DIMENSIONS>> FromIterator<T> for Object<DIMENSIONS> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Block(iter.into_iter().map(IntoObject::into_object).collect()).into_object()
+    }
+}
+
+/// A group of objects that are implicitly unioned together
 #[derive(Clone, Debug)]
-pub(crate) struct Block<const DIMENSIONS: usize>(pub Object<DIMENSIONS>, pub Object<DIMENSIONS>);
+pub(crate) struct Block<const DIMENSIONS: usize>(pub Vec<Object<DIMENSIONS>>);
 
 impl<const DIMENSIONS: usize> fmt::Display for Block<DIMENSIONS> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -84,12 +84,15 @@ impl<const DIMENSIONS: usize> fmt::Display for Block<DIMENSIONS> {
 
 impl<const DIMENSIONS: usize> Scad for Block<DIMENSIONS> {
     fn assign(&self, f: &mut scad::Formatter) -> scad::Assignment {
-        let a = self.0.assign(f);
-        let b = self.1.assign(f);
-        //f.emit(format_args!("{{ {a}; {b}; }}"), true)
-        scad::Assignment::Inline {
-            code: format!("{{ {a}; {b}; }}"),
+        let mut code = "{ ".to_string();
+        for child in &self.0 {
+            let child = child.assign(f).to_string();
+            if !child.is_empty() {
+                code += &format!("{child}; ");
+            }
         }
+        code.push('}');
+        scad::Assignment::Inline { code }
     }
 }
 
@@ -99,3 +102,137 @@ impl<const DIMENSIONS: usize> IntoObject<DIMENSIONS> for Block<DIMENSIONS> {
         Object::new(self)
     }
 }
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum BooleanKind {
+    Union,
+    Difference,
+    Intersection,
+    Minkowski,
+}
+
+impl BooleanKind {
+    /// Returns `true` if nested operations of this kind can be merged on both sides
+    ///
+    /// `difference` can only be merged on the left side, since `(a - b) - c` is the same as
+    /// `difference() { a; b; c; }` but `a - (b - c)` is not.
+    #[inline]
+    fn is_associative(self) -> bool {
+        !matches!(self, Self::Difference)
+    }
+
+    #[inline]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Union => "union",
+            Self::Difference => "difference",
+            Self::Intersection => "intersection",
+            Self::Minkowski => "minkowski",
+        }
+    }
+
+    #[inline]
+    fn operator<const DIMENSIONS: usize>(self) -> Object<DIMENSIONS> {
+        match self {
+            Self::Union => crate::union().into(),
+            Self::Difference => crate::difference().into(),
+            Self::Intersection => crate::intersection().into(),
+            Self::Minkowski => crate::minkowski().into(),
+        }
+    }
+}
+
+/// A boolean operation over a flat list of children
+///
+/// Chains of the same operation are merged into a single node so `a + b + c` is emitted as
+/// `union() { a; b; c; }` instead of nested `union()` calls.
+#[derive(Clone, Debug)]
+pub(crate) struct Boolean<const DIMENSIONS: usize> {
+    pub kind: BooleanKind,
+    pub children: Vec<Object<DIMENSIONS>>,
+}
+
+impl<const DIMENSIONS: usize> Boolean<DIMENSIONS> {
+    pub fn join(
+        kind: BooleanKind,
+        a: Object<DIMENSIONS>,
+        b: Object<DIMENSIONS>,
+    ) -> Object<DIMENSIONS> {
+        let mut children = vec![];
+        Self::extend(kind, &mut children, a);
+
+        if kind.is_associative() {
+            Self::extend(kind, &mut children, b);
+        } else {
+            children.push(b);
+        }
+
+        Self { kind, children }.into_object()
+    }
+
+    fn extend(
+        kind: BooleanKind,
+        children: &mut Vec<Object<DIMENSIONS>>,
+        object: Object<DIMENSIONS>,
+    ) {
+        match object.downcast_ref::<Self>() {
+            Some(value) if value.kind == kind => children.extend(value.children.iter().cloned()),
+            _ => children.push(object),
+        }
+    }
+}
+
+impl<const DIMENSIONS: usize> fmt::Display for Boolean<DIMENSIONS> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.to_scad())
+    }
+}
+
+impl<const DIMENSIONS: usize> Scad for Boolean<DIMENSIONS> {
+    fn assign(&self, f: &mut scad::Formatter) -> scad::Assignment {
+        let wrapped: crate::operator::Wrapped<DIMENSIONS, DIMENSIONS> = crate::operator::Wrapped {
+            parent: self.kind.operator(),
+            child: Block(self.children.clone()).into_object(),
+        };
+        wrapped.assign(f)
+    }
+}
+
+impl<const DIMENSIONS: usize> IntoObject<DIMENSIONS> for Boolean<DIMENSIONS> {
+    #[inline]
+    fn into_object(self) -> Object<DIMENSIONS> {
+        Object::new(self)
+    }
+}
+
+#[test]
+fn tuple_test() {
+    use crate::*;
+
+    let parts = (cube(10), sphere(5).up(10), cylinder(20, 2)).into_object();
+    insta::assert_snapshot!(parts);
+}
+
+#[test]
+fn iter_test() {
+    use crate::*;
+
+    let parts: Object<2> = (0..4).map(|idx| circle(1).right(idx * 5)).collect();
+    insta::assert_snapshot!(parts);
+}
+
+#[test]
+fn union_test() {
+    use crate::*;
+
+    let parts = cube(10) + sphere(5) + cylinder(20, 2) + (cube(1) + cube(2));
+    insta::assert_snapshot!(parts);
+}
+
+#[test]
+fn difference_test() {
+    use crate::*;
+
+    let parts = cube(10) - sphere(5) - cylinder(20, 2) - (cube(1) - cube(2));
+    insta::assert_snapshot!(parts);
+}
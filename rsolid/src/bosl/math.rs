@@ -0,0 +1,453 @@
+/// Limit value `v` to be between `minval` and `maxval`, inclusive.
+#[derive(Clone, Default)]
+#[must_use = "Values must be used in order to be rendered"]
+pub struct Constrain {
+    max: Option<crate::types::Scalar>,
+    min: Option<crate::types::Scalar>,
+    value: Option<crate::types::Scalar>,
+}
+
+#[inline]
+pub fn constrain(
+    value: impl Into<crate::types::Scalar>,
+    min: impl Into<crate::types::Scalar>,
+    max: impl Into<crate::types::Scalar>,
+) -> Constrain {
+    Constrain::default().value(value).min(min).max(max)
+}
+
+impl Constrain {
+    /// Maximum value to return, if out of range.
+    #[inline]
+    pub fn max<T: Into<crate::types::Scalar>>(mut self, max: T) -> Self {
+        self.max = Some(max.into());
+        self
+    }
+
+    /// Minimum value to return, if out of range.
+    #[inline]
+    pub fn min<T: Into<crate::types::Scalar>>(mut self, min: T) -> Self {
+        self.min = Some(min.into());
+        self
+    }
+
+    /// Value to constrain.
+    #[inline]
+    pub fn value<T: Into<crate::types::Scalar>>(mut self, value: T) -> Self {
+        self.value = Some(value.into());
+        self
+    }
+}
+
+impl ::core::fmt::Debug for Constrain {
+    #[allow(clippy::write_literal)]
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        let mut s = f.debug_struct("constrain");
+        if let Some(value) = self.max.as_ref() {
+            s.field("maxval", value);
+        }
+        if let Some(value) = self.min.as_ref() {
+            s.field("minval", value);
+        }
+        if let Some(value) = self.value.as_ref() {
+            s.field("v", value);
+        }
+        s.finish()
+    }
+}
+
+impl crate::scad::Scad for Constrain {
+    fn assign(&self, f: &mut crate::scad::Formatter) -> crate::scad::Assignment {
+        f.includes("<BOSL/constants.scad>");
+        f.uses("<BOSL/math.scad>");
+        let name = "constrain";
+        let args = [
+            (
+                "maxval",
+                self.max
+                    .as_ref()
+                    .map(|value| crate::scad::Scad::assign(value, f)),
+            ),
+            (
+                "minval",
+                self.min
+                    .as_ref()
+                    .map(|value| crate::scad::Scad::assign(value, f)),
+            ),
+            (
+                "v",
+                self.value
+                    .as_ref()
+                    .map(|value| crate::scad::Scad::assign(value, f)),
+            ),
+        ];
+        f.function(name, args)
+    }
+}
+
+impl ::core::fmt::Display for Constrain {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        f.write_str(&crate::scad::Scad::to_scad(self))
+    }
+}
+
+impl From<Constrain> for crate::types::Scalar {
+    #[inline]
+    fn from(value: Constrain) -> Self {
+        Self(crate::Expr::value(value))
+    }
+}
+
+/// Calculate hypotenuse length of a 2D or 3D triangle.
+#[derive(Clone, Default)]
+#[must_use = "Values must be used in order to be rendered"]
+pub struct Hypot {
+    x: Option<crate::types::Length>,
+    y: Option<crate::types::Length>,
+    z: Option<crate::types::Length>,
+}
+
+#[inline]
+pub fn hypot(x: impl Into<crate::types::Length>, y: impl Into<crate::types::Length>) -> Hypot {
+    Hypot::default().x(x).y(y)
+}
+
+impl Hypot {
+    /// Length on the X axis.
+    #[inline]
+    pub fn x<T: Into<crate::types::Length>>(mut self, x: T) -> Self {
+        self.x = Some(x.into());
+        self
+    }
+
+    /// Length on the Y axis.
+    #[inline]
+    pub fn y<T: Into<crate::types::Length>>(mut self, y: T) -> Self {
+        self.y = Some(y.into());
+        self
+    }
+
+    /// Length on the Z axis.
+    #[inline]
+    pub fn z<T: Into<crate::types::Length>>(mut self, z: T) -> Self {
+        self.z = Some(z.into());
+        self
+    }
+}
+
+impl ::core::fmt::Debug for Hypot {
+    #[allow(clippy::write_literal)]
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        let mut s = f.debug_struct("hypot");
+        if let Some(value) = self.x.as_ref() {
+            s.field("x", value);
+        }
+        if let Some(value) = self.y.as_ref() {
+            s.field("y", value);
+        }
+        if let Some(value) = self.z.as_ref() {
+            s.field("z", value);
+        }
+        s.finish()
+    }
+}
+
+impl crate::scad::Scad for Hypot {
+    fn assign(&self, f: &mut crate::scad::Formatter) -> crate::scad::Assignment {
+        f.includes("<BOSL/constants.scad>");
+        f.uses("<BOSL/math.scad>");
+        let name = "hypot";
+        let args = [
+            (
+                "x",
+                self.x
+                    .as_ref()
+                    .map(|value| crate::scad::Scad::assign(value, f)),
+            ),
+            (
+                "y",
+                self.y
+                    .as_ref()
+                    .map(|value| crate::scad::Scad::assign(value, f)),
+            ),
+            (
+                "z",
+                self.z
+                    .as_ref()
+                    .map(|value| crate::scad::Scad::assign(value, f)),
+            ),
+        ];
+        f.function(name, args)
+    }
+}
+
+impl ::core::fmt::Display for Hypot {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        f.write_str(&crate::scad::Scad::to_scad(self))
+    }
+}
+
+impl From<Hypot> for crate::types::Length {
+    #[inline]
+    fn from(value: Hypot) -> Self {
+        Self(crate::Expr::value(value))
+    }
+}
+
+/// Interpolate between two values or vectors.
+#[derive(Clone, Default)]
+#[must_use = "Values must be used in order to be rendered"]
+pub struct Lerp {
+    a: Option<crate::types::Scalar>,
+    b: Option<crate::types::Scalar>,
+    u: Option<crate::types::Scalar>,
+}
+
+#[inline]
+pub fn lerp(
+    a: impl Into<crate::types::Scalar>,
+    b: impl Into<crate::types::Scalar>,
+    u: impl Into<crate::types::Scalar>,
+) -> Lerp {
+    Lerp::default().a(a).b(b).u(u)
+}
+
+impl Lerp {
+    /// First value.
+    #[inline]
+    pub fn a<T: Into<crate::types::Scalar>>(mut self, a: T) -> Self {
+        self.a = Some(a.into());
+        self
+    }
+
+    /// Second value.
+    #[inline]
+    pub fn b<T: Into<crate::types::Scalar>>(mut self, b: T) -> Self {
+        self.b = Some(b.into());
+        self
+    }
+
+    /// The proportion from `a` to `b` to calculate. Valid range is 0.0 to 1.0, inclusive.
+    #[inline]
+    pub fn u<T: Into<crate::types::Scalar>>(mut self, u: T) -> Self {
+        self.u = Some(u.into());
+        self
+    }
+}
+
+impl ::core::fmt::Debug for Lerp {
+    #[allow(clippy::write_literal)]
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        let mut s = f.debug_struct("lerp");
+        if let Some(value) = self.a.as_ref() {
+            s.field("a", value);
+        }
+        if let Some(value) = self.b.as_ref() {
+            s.field("b", value);
+        }
+        if let Some(value) = self.u.as_ref() {
+            s.field("u", value);
+        }
+        s.finish()
+    }
+}
+
+impl crate::scad::Scad for Lerp {
+    fn assign(&self, f: &mut crate::scad::Formatter) -> crate::scad::Assignment {
+        f.includes("<BOSL/constants.scad>");
+        f.uses("<BOSL/math.scad>");
+        let name = "lerp";
+        let args = [
+            (
+                "a",
+                self.a
+                    .as_ref()
+                    .map(|value| crate::scad::Scad::assign(value, f)),
+            ),
+            (
+                "b",
+                self.b
+                    .as_ref()
+                    .map(|value| crate::scad::Scad::assign(value, f)),
+            ),
+            (
+                "u",
+                self.u
+                    .as_ref()
+                    .map(|value| crate::scad::Scad::assign(value, f)),
+            ),
+        ];
+        f.function(name, args)
+    }
+}
+
+impl ::core::fmt::Display for Lerp {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        f.write_str(&crate::scad::Scad::to_scad(self))
+    }
+}
+
+impl From<Lerp> for crate::types::Scalar {
+    #[inline]
+    fn from(value: Lerp) -> Self {
+        Self(crate::Expr::value(value))
+    }
+}
+
+/// Returns the positive modulo of a value.
+#[derive(Clone, Default)]
+#[must_use = "Values must be used in order to be rendered"]
+pub struct Posmod {
+    m: Option<crate::types::Scalar>,
+    x: Option<crate::types::Scalar>,
+}
+
+#[inline]
+pub fn posmod(x: impl Into<crate::types::Scalar>, m: impl Into<crate::types::Scalar>) -> Posmod {
+    Posmod::default().x(x).m(m)
+}
+
+impl Posmod {
+    /// Modulo value.
+    #[inline]
+    pub fn m<T: Into<crate::types::Scalar>>(mut self, m: T) -> Self {
+        self.m = Some(m.into());
+        self
+    }
+
+    /// The value to constrain.
+    #[inline]
+    pub fn x<T: Into<crate::types::Scalar>>(mut self, x: T) -> Self {
+        self.x = Some(x.into());
+        self
+    }
+}
+
+impl ::core::fmt::Debug for Posmod {
+    #[allow(clippy::write_literal)]
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        let mut s = f.debug_struct("posmod");
+        if let Some(value) = self.m.as_ref() {
+            s.field("m", value);
+        }
+        if let Some(value) = self.x.as_ref() {
+            s.field("x", value);
+        }
+        s.finish()
+    }
+}
+
+impl crate::scad::Scad for Posmod {
+    fn assign(&self, f: &mut crate::scad::Formatter) -> crate::scad::Assignment {
+        f.includes("<BOSL/constants.scad>");
+        f.uses("<BOSL/math.scad>");
+        let name = "posmod";
+        let args = [
+            (
+                "m",
+                self.m
+                    .as_ref()
+                    .map(|value| crate::scad::Scad::assign(value, f)),
+            ),
+            (
+                "x",
+                self.x
+                    .as_ref()
+                    .map(|value| crate::scad::Scad::assign(value, f)),
+            ),
+        ];
+        f.function(name, args)
+    }
+}
+
+impl ::core::fmt::Display for Posmod {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        f.write_str(&crate::scad::Scad::to_scad(self))
+    }
+}
+
+impl From<Posmod> for crate::types::Scalar {
+    #[inline]
+    fn from(value: Posmod) -> Self {
+        Self(crate::Expr::value(value))
+    }
+}
+
+/// Returns `x` quantized to the nearest integer multiple of `y`.
+#[derive(Clone, Default)]
+#[must_use = "Values must be used in order to be rendered"]
+pub struct Quant {
+    x: Option<crate::types::Scalar>,
+    y: Option<crate::types::Scalar>,
+}
+
+#[inline]
+pub fn quant(x: impl Into<crate::types::Scalar>, y: impl Into<crate::types::Scalar>) -> Quant {
+    Quant::default().x(x).y(y)
+}
+
+impl Quant {
+    /// The value to quantize.
+    #[inline]
+    pub fn x<T: Into<crate::types::Scalar>>(mut self, x: T) -> Self {
+        self.x = Some(x.into());
+        self
+    }
+
+    /// The multiple to quantize to.
+    #[inline]
+    pub fn y<T: Into<crate::types::Scalar>>(mut self, y: T) -> Self {
+        self.y = Some(y.into());
+        self
+    }
+}
+
+impl ::core::fmt::Debug for Quant {
+    #[allow(clippy::write_literal)]
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        let mut s = f.debug_struct("quant");
+        if let Some(value) = self.x.as_ref() {
+            s.field("x", value);
+        }
+        if let Some(value) = self.y.as_ref() {
+            s.field("y", value);
+        }
+        s.finish()
+    }
+}
+
+impl crate::scad::Scad for Quant {
+    fn assign(&self, f: &mut crate::scad::Formatter) -> crate::scad::Assignment {
+        f.includes("<BOSL/constants.scad>");
+        f.uses("<BOSL/math.scad>");
+        let name = "quant";
+        let args = [
+            (
+                "x",
+                self.x
+                    .as_ref()
+                    .map(|value| crate::scad::Scad::assign(value, f)),
+            ),
+            (
+                "y",
+                self.y
+                    .as_ref()
+                    .map(|value| crate::scad::Scad::assign(value, f)),
+            ),
+        ];
+        f.function(name, args)
+    }
+}
+
+impl ::core::fmt::Display for Quant {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        f.write_str(&crate::scad::Scad::to_scad(self))
+    }
+}
+
+impl From<Quant> for crate::types::Scalar {
+    #[inline]
+    fn from(value: Quant) -> Self {
+        Self(crate::Expr::value(value))
+    }
+}
@@ -1,13 +1,13 @@
 /// Makes a very simple screw model, useful for making screwholes.
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Default)]
 #[must_use = "Objects must be returned in order to be rendered"]
 pub struct Screw {
-    countersunk: Option<bool>,
-    head_len: Option<crate::types::Length>,
-    head_size: Option<crate::types::Length>,
-    len: Option<crate::types::Length>,
-    pitch: Option<crate::types::Scalar>,
-    size: Option<crate::types::Length>,
+    pub(crate) countersunk: Option<bool>,
+    pub(crate) head_len: Option<crate::types::Length>,
+    pub(crate) head_size: Option<crate::types::Length>,
+    pub(crate) len: Option<crate::types::Length>,
+    pub(crate) pitch: Option<crate::types::Scalar>,
+    pub(crate) size: Option<crate::types::Length>,
 }
 
 /// Makes a very simple screw model, useful for making screwholes.
@@ -138,6 +138,35 @@ impl ::core::fmt::Display for Screw {
     }
 }
 
+impl crate::Node for Screw {
+    fn name(&self) -> &str {
+        "screw"
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        let mut out: Vec<(&'static str, &dyn ::core::any::Any)> = Vec::new();
+        if let Some(value) = self.countersunk.as_ref() {
+            out.push(("countersunk", value));
+        }
+        if let Some(value) = self.head_len.as_ref() {
+            out.push(("head_len", value));
+        }
+        if let Some(value) = self.head_size.as_ref() {
+            out.push(("head_size", value));
+        }
+        if let Some(value) = self.len.as_ref() {
+            out.push(("len", value));
+        }
+        if let Some(value) = self.pitch.as_ref() {
+            out.push(("pitch", value));
+        }
+        if let Some(value) = self.size.as_ref() {
+            out.push(("size", value));
+        }
+        out
+    }
+}
+
 impl<T: crate::IntoObject<3>> ::core::ops::Add<T> for Screw {
     type Output = crate::Object<3>;
 
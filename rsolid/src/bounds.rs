@@ -0,0 +1,479 @@
+//! Bounding boxes worked out from the parameters of an object
+//!
+//! ```
+//! use rsolid::*;
+//!
+//! let part = cube(10) + cylinder(5, 2).right(5).back(5).up(10);
+//! let bounds = part.bounds().unwrap();
+//! assert_eq!(bounds.size(), [10.0, 10.0, 15.0]);
+//! ```
+
+use crate::{
+    block::{Block, Boolean, BooleanKind},
+    extension::*,
+    math::{self, length, num, vec2, vec3, Affine, Vec3},
+    operator::Wrapped,
+    primitive::*,
+    AnyObject, Object,
+};
+
+/// An axis-aligned bounding box
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Aabb<const DIMENSIONS: usize = 3> {
+    pub min: [f64; DIMENSIONS],
+    pub max: [f64; DIMENSIONS],
+}
+
+impl<const DIMENSIONS: usize> Aabb<DIMENSIONS> {
+    #[inline]
+    pub const fn new(min: [f64; DIMENSIONS], max: [f64; DIMENSIONS]) -> Self {
+        Self { min, max }
+    }
+
+    #[inline]
+    pub fn size(&self) -> [f64; DIMENSIONS] {
+        core::array::from_fn(|axis| self.max[axis] - self.min[axis])
+    }
+
+    #[inline]
+    pub fn center(&self) -> [f64; DIMENSIONS] {
+        core::array::from_fn(|axis| (self.min[axis] + self.max[axis]) / 2.0)
+    }
+
+    /// Returns the smallest box that contains both boxes
+    #[inline]
+    pub fn union(&self, other: &Self) -> Self {
+        Self {
+            min: core::array::from_fn(|axis| self.min[axis].min(other.min[axis])),
+            max: core::array::from_fn(|axis| self.max[axis].max(other.max[axis])),
+        }
+    }
+
+    /// Returns the overlap of both boxes, or `None` if they don't touch
+    #[inline]
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let out = Self {
+            min: core::array::from_fn(|axis| self.min[axis].max(other.min[axis])),
+            max: core::array::from_fn(|axis| self.max[axis].min(other.max[axis])),
+        };
+        (0..DIMENSIONS)
+            .all(|axis| out.min[axis] <= out.max[axis])
+            .then_some(out)
+    }
+}
+
+impl Aabb<3> {
+    fn from_points(points: impl IntoIterator<Item = Vec3>) -> Option<Self> {
+        points
+            .into_iter()
+            .map(|p| Self::new([p.x, p.y, p.z], [p.x, p.y, p.z]))
+            .reduce(|a, b| a.union(&b))
+    }
+
+    /// A box on the XY plane, which is how 2D objects are bounded
+    fn flat(min: [f64; 2], max: [f64; 2]) -> Self {
+        Self::new([min[0], min[1], 0.0], [max[0], max[1], 0.0])
+    }
+
+    fn corners(&self) -> [Vec3; 8] {
+        core::array::from_fn(|idx| {
+            let [x, y, z] = core::array::from_fn(|axis| {
+                if idx & (1 << axis) == 0 {
+                    self.min[axis]
+                } else {
+                    self.max[axis]
+                }
+            });
+            Vec3::new(x, y, z)
+        })
+    }
+
+    fn transform(&self, m: &Affine) -> Self {
+        Self::from_points(self.corners().map(|p| m.apply(p))).unwrap()
+    }
+}
+
+impl<const DIMENSIONS: usize> Object<DIMENSIONS> {
+    /// Returns the bounding box of the object, or `None` if it can't be worked out
+    ///
+    /// Curved shapes are bounded by their true curves rather than their fragments, rotated
+    /// objects by their rotated bounding box and text approximately. Imports, shapes with
+    /// symbolic parameters and modules without a known size can't be bounded.
+    pub fn bounds(&self) -> Option<Aabb<DIMENSIONS>> {
+        let out = bounds(&self.into())?;
+        Some(Aabb {
+            min: core::array::from_fn(|axis| out.min[axis]),
+            max: core::array::from_fn(|axis| out.max[axis]),
+        })
+    }
+}
+
+fn bounds(object: &AnyObject) -> Option<Aabb<3>> {
+    if let Some(cube) = object.downcast_ref::<Cube>() {
+        let size = match &cube.size {
+            Some(CubeSize::Length3(size)) => vec3(size).ok()?,
+            Some(CubeSize::Length(size)) => {
+                let size = num(&size.0).ok()?;
+                Vec3::new(size, size, size)
+            }
+            None => Vec3::new(1.0, 1.0, 1.0),
+        };
+        let min = if cube.center == Some(true) {
+            size * -0.5
+        } else {
+            Vec3::default()
+        };
+        return Aabb::from_points([min, min + size]);
+    }
+
+    if let Some(sphere) = object.downcast_ref::<Sphere>() {
+        let r = length(&sphere.radius, 1.0).ok()?;
+        return Some(Aabb::new([-r; 3], [r; 3]));
+    }
+
+    if let Some(cylinder) = object.downcast_ref::<Cylinder>() {
+        let (r1, r2) = match &cylinder.radius {
+            Some(CylinderRadius::Length(r)) => (num(&r.0).ok()?, num(&r.0).ok()?),
+            Some(CylinderRadius::Length2(r)) => vec2(r).ok()?.into(),
+            None => (1.0, 1.0),
+        };
+        let r1 = length(&cylinder.radius_bottom, r1).ok()?;
+        let r2 = length(&cylinder.radius_top, r2).ok()?;
+        let h = length(&cylinder.height, 1.0).ok()?;
+        let z = if cylinder.center == Some(true) {
+            -h / 2.0
+        } else {
+            0.0
+        };
+        let r = r1.max(r2);
+        return Some(Aabb::new([-r, -r, z], [r, r, z + h]));
+    }
+
+    if let Some(polyhedron) = object.downcast_ref::<Polyhedron>() {
+        let points = polyhedron.points.as_ref()?;
+        let points: Vec<_> = points.0.iter().map(vec3).collect::<Result<_, _>>().ok()?;
+        return Aabb::from_points(points);
+    }
+
+    if let Some(square) = object.downcast_ref::<Square>() {
+        let [x, y] = match &square.size {
+            Some(SquareSize::Length2(size)) => vec2(size).ok()?,
+            Some(SquareSize::Length(size)) => [num(&size.0).ok()?; 2],
+            None => [1.0, 1.0],
+        };
+        let min = if square.center == Some(true) {
+            [-x / 2.0, -y / 2.0]
+        } else {
+            [0.0, 0.0]
+        };
+        return Some(Aabb::flat(min, [min[0] + x, min[1] + y]));
+    }
+
+    if let Some(circle) = object.downcast_ref::<Circle>() {
+        let r = length(&circle.radius, 1.0).ok()?;
+        return Some(Aabb::flat([-r; 2], [r; 2]));
+    }
+
+    if let Some(polygon) = object.downcast_ref::<crate::primitive::Polygon>() {
+        let points = polygon.points.as_ref()?;
+        let points: Vec<_> = points.0.iter().map(vec2).collect::<Result<_, _>>().ok()?;
+        return Aabb::from_points(points.into_iter().map(|[x, y]| Vec3::new(x, y, 0.0)));
+    }
+
+    if let Some(text) = object.downcast_ref::<Text>() {
+        return self::text(text);
+    }
+
+    macro_rules! dimensions {
+        ($($dim:literal),*) => {
+            $(
+                if let Some(block) = object.downcast_ref::<Block<$dim>>() {
+                    return boolean(BooleanKind::Union, &block.0);
+                }
+
+                if let Some(boolean) = object.downcast_ref::<Boolean<$dim>>() {
+                    return self::boolean(boolean.kind, &boolean.children);
+                }
+
+                if let Some(wrapped) = object.downcast_ref::<Wrapped<$dim, $dim>>() {
+                    return operator(&wrapped.parent, &wrapped.child);
+                }
+            )*
+        };
+    }
+
+    dimensions!(2, 3);
+
+    if let Some(wrapped) = object.downcast_ref::<Wrapped<2, 3>>() {
+        let child = bounds(&(&wrapped.child).into())?;
+
+        if let Some(extrude) = wrapped.parent.downcast_ref::<LinearExtrude>() {
+            return linear_extrude(extrude, &child);
+        }
+
+        if wrapped.parent.downcast_ref::<RotateExtrude>().is_some() {
+            // the profile is revolved around the Z axis, with Y becoming Z
+            let r = child.max[0].max(-child.min[0]);
+            return Some(Aabb::new([-r, -r, child.min[1]], [r, r, child.max[1]]));
+        }
+
+        return None;
+    }
+
+    if let Some(wrapped) = object.downcast_ref::<Wrapped<3, 2>>() {
+        if wrapped.parent.downcast_ref::<Projection>().is_some() {
+            let child = bounds(&(&wrapped.child).into())?;
+            return Some(Aabb::flat(
+                [child.min[0], child.min[1]],
+                [child.max[0], child.max[1]],
+            ));
+        }
+    }
+
+    None
+}
+
+fn boolean<const DIMENSIONS: usize>(
+    kind: BooleanKind,
+    children: &[Object<DIMENSIONS>],
+) -> Option<Aabb<3>> {
+    let mut children = children.iter().map(|child| bounds(&child.into()));
+    match kind {
+        BooleanKind::Union => children.reduce(|a, b| Some(a?.union(&b?)))?,
+        // only the first child adds any material
+        BooleanKind::Difference => children.next()?,
+        // children that can't be bounded are still bounded by the others
+        BooleanKind::Intersection => children
+            .flatten()
+            .map(Some)
+            .reduce(|a, b| a?.intersection(&b?))?,
+        BooleanKind::Minkowski => children.reduce(|a, b| {
+            let (a, b) = (a?, b?);
+            Some(Aabb::new(
+                core::array::from_fn(|axis| a.min[axis] + b.min[axis]),
+                core::array::from_fn(|axis| a.max[axis] + b.max[axis]),
+            ))
+        })?,
+    }
+}
+
+fn operator<const DIMENSIONS: usize>(
+    parent: &Object<DIMENSIONS>,
+    child: &Object<DIMENSIONS>,
+) -> Option<Aabb<3>> {
+    macro_rules! op {
+        ($ty:ident, $out:expr) => {
+            if parent.downcast_ref::<$ty<DIMENSIONS>>().is_some() {
+                return $out;
+            }
+        };
+    }
+
+    if let Some(m) = math::transform(parent).ok()? {
+        return Some(bounds(&child.into())?.transform(&m));
+    }
+
+    // operators with more than one child get them in a block
+    let children = match child.downcast_ref::<Block<DIMENSIONS>>() {
+        Some(block) => block.0.clone(),
+        None => vec![child.clone()],
+    };
+
+    if let Some(op) = parent.downcast_ref::<Resize<DIMENSIONS>>() {
+        let size = op.size.as_ref().map_or(Ok(Vec3::default()), vec3).ok()?;
+        let child = bounds(&child.into())?;
+        let current = child.size();
+        let size = [size.x, size.y, size.z];
+        // zero sizes leave that axis unchanged
+        let scale: [f64; 3] = core::array::from_fn(|axis| {
+            if size[axis] > 0.0 && current[axis] > 0.0 {
+                size[axis] / current[axis]
+            } else {
+                1.0
+            }
+        });
+        return Some(child.transform(&Affine::scale(scale.into())));
+    }
+
+    if let Some(op) = parent.downcast_ref::<Offset<DIMENSIONS>>() {
+        let child = bounds(&child.into())?;
+        let grow = match (&op.radius, &op.delta) {
+            (Some(r), _) => num(&r.0).ok()?,
+            (None, Some(delta)) => num(&delta.0).ok()?,
+            (None, None) => 1.0,
+        };
+        let out = Aabb::new(
+            [child.min[0] - grow, child.min[1] - grow, child.min[2]],
+            [child.max[0] + grow, child.max[1] + grow, child.max[2]],
+        );
+        return (out.min[0] <= out.max[0] && out.min[1] <= out.max[1]).then_some(out);
+    }
+
+    op!(Union, boolean(BooleanKind::Union, &children));
+    op!(Difference, boolean(BooleanKind::Difference, &children));
+    op!(Intersection, boolean(BooleanKind::Intersection, &children));
+    op!(Minkowski, boolean(BooleanKind::Minkowski, &children));
+    // the hull can't reach outside of the box around the children
+    op!(Hull, boolean(BooleanKind::Union, &children));
+
+    op!(ColorOperator, bounds(&child.into()));
+    op!(Dbg, bounds(&child.into()));
+    op!(Root, bounds(&child.into()));
+    op!(FragmentAngle, bounds(&child.into()));
+    op!(FragmentCount, bounds(&child.into()));
+    op!(FragmentSize, bounds(&child.into()));
+
+    None
+}
+
+fn linear_extrude(extrude: &LinearExtrude, child: &Aabb<3>) -> Option<Aabb<3>> {
+    extrude.validate().ok()?;
+    let direction = match &extrude.vector {
+        Some(v) => vec3(v).ok()?,
+        None => Vec3::new(0.0, 0.0, 1.0),
+    };
+    let default_height = if extrude.vector.is_some() {
+        direction.length()
+    } else {
+        100.0
+    };
+    let height = length(&extrude.height, default_height).ok()?;
+    if direction.z <= 0.0 || height <= 0.0 {
+        return None;
+    }
+    let offset = direction / direction.length() * height;
+    let start = if extrude.center == Some(true) {
+        offset * -0.5
+    } else {
+        Vec3::default()
+    };
+
+    let twist = extrude.twist.as_ref().map_or(Ok(0.0), |t| num(&t.0)).ok()?;
+    let [sx, sy] = crate::extrude::scale(extrude).ok()?;
+    let top = child.transform(&Affine::scale(Vec3::new(sx, sy, 1.0)));
+    let layer = |layer: Aabb<3>| {
+        if twist == 0.0 {
+            layer
+        } else {
+            // the twisted profile stays within the circle around its furthest corner
+            let r = layer
+                .corners()
+                .into_iter()
+                .map(|p| p.x.hypot(p.y))
+                .fold(0.0, f64::max);
+            Aabb::flat([-r; 2], [r; 2])
+        }
+    };
+
+    // the layers in between are scaled linearly, so they're within the two ends
+    let bottom = layer(*child).transform(&Affine::translate(start));
+    let top = layer(top).transform(&Affine::translate(start + offset));
+    Some(bottom.union(&top))
+}
+
+fn text(text: &Text) -> Option<Aabb<3>> {
+    let size = text.size.as_ref().map_or(Ok(10.0), |s| num(&s.0)).ok()?;
+    let chars = text.text.as_deref().unwrap_or_default().chars().count() as f64;
+
+    // assume an average advance of 0.7em and descenders of 0.3em
+    let width = chars * size * 0.7;
+    let (ascent, descent) = (size, size * 0.3);
+
+    let x = match text.halign.as_deref() {
+        Some("center") => -width / 2.0,
+        Some("right") => -width,
+        _ => 0.0,
+    };
+    let y = match text.valign.as_deref() {
+        Some("top") => -ascent,
+        Some("center") => -ascent / 2.0,
+        Some("bottom") => descent,
+        _ => 0.0,
+    };
+    Some(Aabb::flat([x, y - descent], [x + width, y + ascent]))
+}
+
+#[test]
+fn bounds_test() {
+    use crate::*;
+
+    let bounds = |object: Object| object.bounds().unwrap();
+
+    assert_eq!(
+        bounds(cube([1, 2, 3]).into_object()),
+        Aabb::new([0.0; 3], [1.0, 2.0, 3.0])
+    );
+    assert_eq!(
+        bounds(cube(2).center(true).up(1).left(2)),
+        Aabb::new([-3.0, -1.0, 0.0], [-1.0, 1.0, 2.0])
+    );
+    assert_eq!(
+        bounds(sphere(2) >> scale([1, 2, 3])),
+        Aabb::new([-2.0, -4.0, -6.0], [2.0, 4.0, 6.0])
+    );
+    assert_eq!(
+        bounds(cylinder(10, [2, 4]).center(true) >> mirror([0, 0, 1])),
+        Aabb::new([-4.0, -4.0, -5.0], [4.0, 4.0, 5.0])
+    );
+    assert_eq!(
+        bounds(cube(10) - cube(20).right(5)),
+        Aabb::new([0.0; 3], [10.0; 3])
+    );
+    assert_eq!(
+        bounds(cube(10).intersection(cube(10).right(5))),
+        Aabb::new([5.0, 0.0, 0.0], [10.0; 3])
+    );
+    assert_eq!(
+        bounds(cube([1, 2, 3]) >> resize([2, 0, 6])),
+        Aabb::new([0.0; 3], [2.0, 2.0, 6.0])
+    );
+
+    let rotated = bounds(cube(1) >> rotate(90)).size();
+    assert!(
+        rotated.iter().all(|v| (v - 1.0).abs() < 1e-9),
+        "{rotated:?}"
+    );
+
+    let extruded = square([2, 4]).center(true) >> linear_extrude(3).center(true);
+    assert_eq!(
+        bounds(extruded),
+        Aabb::new([-1.0, -2.0, -1.5], [1.0, 2.0, 1.5])
+    );
+    assert_eq!(
+        bounds(square(2) >> linear_extrude(3).scale([2.0, 0.5])),
+        Aabb::new([0.0; 3], [4.0, 2.0, 3.0])
+    );
+    // a scalar vector is broadcast like in the emitted code, so this extrudes along [1, 1, 1]
+    let slanted = bounds(square(2) >> linear_extrude(3f64.sqrt()).vector(1));
+    assert!(
+        slanted
+            .max
+            .iter()
+            .zip([3.0, 3.0, 1.0])
+            .all(|(a, b)| (a - b).abs() < 1e-9),
+        "{slanted:?}"
+    );
+    assert_eq!(
+        bounds(circle(1).right(3) >> rotate_extrude()),
+        Aabb::new([-4.0, -4.0, -1.0], [4.0, 4.0, 1.0])
+    );
+
+    assert_eq!(
+        polygon([[0, 0], [3, 1], [1, 4]]).into_object().bounds(),
+        Some(Aabb::new([0.0, 0.0], [3.0, 4.0]))
+    );
+    assert_eq!(
+        (circle(1) + square(4).back(2)).bounds(),
+        Some(Aabb::new([-1.0, -1.0], [4.0, 6.0]))
+    );
+    assert!(text("abc").into_object().bounds().is_some());
+
+    assert_eq!(stl("part.stl").bounds(), None);
+    assert_eq!((cube(1) + stl("part.stl")).bounds(), None);
+    assert_eq!(
+        cube(param::<Length>("size", 10).var())
+            .into_object()
+            .bounds(),
+        None
+    );
+}
@@ -0,0 +1,537 @@
+//! Rendering models with the `openscad` command line
+//!
+//! ```no_run
+//! use rsolid::{export::*, *};
+//!
+//! let part = cube(10) - sphere(6);
+//! let out = Exporter::new()
+//!     .out_dir("models")
+//!     .define("$fn", 64)
+//!     .export(&part, "part", &[&Mesh::StlAscii])
+//!     .unwrap();
+//!
+//! for diagnostic in &out.diagnostics {
+//!     eprintln!("{diagnostic}");
+//! }
+//! ```
+
+mod batch;
+mod format;
+
+pub use batch::{Batch, BatchExport, Manifest, ManifestEntry};
+pub use format::{
+    Camera, Drawing, ExportFormat, Format, Mesh, Png, Projection, RenderSettings, View,
+};
+
+use crate::{scad::Scad, IntoObject};
+use core::fmt;
+use std::{
+    io,
+    path::{Path, PathBuf},
+    process::{Command, ExitStatus},
+};
+
+/// Writes models to `.scad` files and renders them with OpenSCAD
+#[derive(Clone, Debug)]
+pub struct Exporter {
+    out_dir: PathBuf,
+    openscad: PathBuf,
+    defines: Vec<(String, String)>,
+}
+
+impl Default for Exporter {
+    fn default() -> Self {
+        Self {
+            out_dir: "target/rsolid".into(),
+            openscad: "openscad".into(),
+            defines: vec![],
+        }
+    }
+}
+
+impl Exporter {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The directory the files are written to, which defaults to `target/rsolid`
+    #[inline]
+    pub fn out_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.out_dir = dir.into();
+        self
+    }
+
+    /// The OpenSCAD binary to run, which defaults to `openscad` on the `PATH`
+    #[inline]
+    pub fn openscad(mut self, path: impl Into<PathBuf>) -> Self {
+        self.openscad = path.into();
+        self
+    }
+
+    /// Overrides a top-level variable with `-D name=value`, where the value is an OpenSCAD
+    /// expression, like `10` or `"\"label\""`
+    #[inline]
+    pub fn define(mut self, name: impl Into<String>, value: impl fmt::Display) -> Self {
+        self.defines.push((name.into(), value.to_string()));
+        self
+    }
+
+    /// Writes the model to `<out_dir>/<path>.scad` and renders it next to it to each format,
+    /// which must fit the model's dimensions
+    ///
+    /// ```no_run
+    /// use rsolid::{export::*, *};
+    ///
+    /// let exporter = Exporter::new();
+    /// let png = Png::snapshot();
+    ///
+    /// exporter.export(&cube(10), "cube", &[&Mesh::ThreeMf, &png]).unwrap();
+    /// exporter.export(&square(10), "square", &[&Drawing::Dxf, &png]).unwrap();
+    /// ```
+    pub fn export<const DIMENSIONS: usize, V: IntoObject<DIMENSIONS> + Clone>(
+        &self,
+        v: &V,
+        path: impl AsRef<Path>,
+        formats: &[&dyn Format<DIMENSIONS>],
+    ) -> Result<Export, ExportError> {
+        let scad = self.out_dir.join(path).with_extension("scad");
+        let (source, warnings) = v.clone().into_object().to_scad_with_warnings();
+        write(&scad, &source)?;
+
+        let mut out = Export {
+            scad,
+            renders: vec![],
+            diagnostics: warnings.into_iter().map(Diagnostic::warning).collect(),
+        };
+
+        for format in formats {
+            let format = format.export_format();
+            for diagnostic in self.render_to(&out.scad, &source, &format)? {
+                if !out.diagnostics.contains(&diagnostic) {
+                    out.diagnostics.push(diagnostic);
+                }
+            }
+            out.renders.push(out.scad.with_extension(format.ext()));
+        }
+
+        Ok(out)
+    }
+
+    /// Renders an animation to numbered PNG frames
+    ///
+    /// The model is written to `<out_dir>/<name>/<name>.scad` and OpenSCAD is run with
+    /// `--animate <frames>`, which steps `$t` from `0` towards `1`. The frames are written to
+    /// `<out_dir>/<name>/frames/` and returned in order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `frames` is zero.
+    pub fn animation<V: Scad>(
+        &self,
+        v: &V,
+        name: impl AsRef<Path>,
+        frames: usize,
+    ) -> Result<Animation, ExportError> {
+        assert!(frames > 0, "an animation needs at least one frame");
+
+        let name = name.as_ref();
+        let dir = self.out_dir.join(name);
+        let frames_dir = dir.join("frames");
+
+        // remove frames from any previous render with a different frame count
+        if frames_dir.exists() {
+            std::fs::remove_dir_all(&frames_dir)
+                .map_err(|err| ExportError::io(&frames_dir, err))?;
+        }
+        std::fs::create_dir_all(&frames_dir).map_err(|err| ExportError::io(&frames_dir, err))?;
+
+        let stem = name.file_name().unwrap_or(name.as_os_str());
+        let scad = dir.join(stem).with_extension("scad");
+        let (source, warnings) = v.to_scad_with_warnings();
+        write(&scad, &source)?;
+
+        let mut cmd = self.command();
+        cmd.arg("-o")
+            .arg(frames_dir.join("frame.png"))
+            .arg("--animate")
+            .arg(frames.to_string())
+            .arg(&scad);
+        let mut diagnostics: Vec<_> = warnings.into_iter().map(Diagnostic::warning).collect();
+        diagnostics.extend(self.run(cmd, &scad, &source)?);
+
+        // OpenSCAD numbers the frames with zero padding, so they sort in order
+        let mut out = vec![];
+        for entry in
+            std::fs::read_dir(&frames_dir).map_err(|err| ExportError::io(&frames_dir, err))?
+        {
+            let path = entry
+                .map_err(|err| ExportError::io(&frames_dir, err))?
+                .path();
+            if path.extension().is_some_and(|ext| ext == "png") {
+                out.push(path);
+            }
+        }
+        out.sort();
+
+        Ok(Animation {
+            scad,
+            frames: out,
+            diagnostics,
+        })
+    }
+
+    /// Renders an emitted `.scad` file to the file next to it with the given extension
+    fn render_to(
+        &self,
+        scad: &Path,
+        source: &str,
+        format: &ExportFormat,
+    ) -> Result<Vec<Diagnostic>, ExportError> {
+        self.run(self.render_command(scad, format), scad, source)
+    }
+
+    fn render_command(&self, scad: &Path, format: &ExportFormat) -> Command {
+        let mut cmd = self.command();
+        cmd.arg("-o")
+            .arg(scad.with_extension(format.ext()))
+            .args(format.args())
+            .arg(scad);
+        cmd
+    }
+
+    fn command(&self) -> Command {
+        let mut cmd = Command::new(&self.openscad);
+        for (name, value) in &self.defines {
+            cmd.arg("-D").arg(format!("{name}={value}"));
+        }
+        cmd
+    }
+
+    fn run(
+        &self,
+        mut cmd: Command,
+        scad: &Path,
+        source: &str,
+    ) -> Result<Vec<Diagnostic>, ExportError> {
+        let output = cmd.output().map_err(|source| ExportError::Spawn {
+            program: self.openscad.clone(),
+            source,
+        })?;
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let diagnostics = diagnostics(&stderr, scad, source);
+
+        if !output.status.success() {
+            return Err(ExportError::Render {
+                status: output.status,
+                diagnostics,
+            });
+        }
+        Ok(diagnostics)
+    }
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), ExportError> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|err| ExportError::io(dir, err))?;
+    }
+    std::fs::write(path, contents).map_err(|err| ExportError::io(path, err))
+}
+
+/// The files written by [`Exporter::export`]
+#[derive(Clone, Debug)]
+pub struct Export {
+    pub scad: PathBuf,
+    /// The rendered files, in the order they were requested
+    pub renders: Vec<PathBuf>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// The files written by [`Exporter::animation`]
+#[derive(Clone, Debug)]
+pub struct Animation {
+    pub scad: PathBuf,
+    pub frames: Vec<PathBuf>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Debug)]
+pub enum ExportError {
+    /// A file couldn't be read or written
+    Io { path: PathBuf, source: io::Error },
+    /// OpenSCAD couldn't be started
+    Spawn { program: PathBuf, source: io::Error },
+    /// OpenSCAD failed to render the model
+    Render {
+        status: ExitStatus,
+        diagnostics: Vec<Diagnostic>,
+    },
+}
+
+impl ExportError {
+    #[inline]
+    fn io(path: &Path, source: io::Error) -> Self {
+        Self::Io {
+            path: path.to_path_buf(),
+            source,
+        }
+    }
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io { path, source } => write!(f, "can't write {}: {source}", path.display()),
+            Self::Spawn { program, source } => {
+                write!(f, "can't run {}: {source}", program.display())
+            }
+            Self::Render {
+                status,
+                diagnostics,
+            } => {
+                write!(f, "openscad exited with {status}")?;
+                for diagnostic in diagnostics {
+                    if diagnostic.level == Level::Error {
+                        write!(f, "\n{diagnostic}")?;
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ExportError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { source, .. } | Self::Spawn { source, .. } => Some(source),
+            Self::Render { .. } => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    Echo,
+    Trace,
+    Deprecated,
+    Warning,
+    Error,
+}
+
+impl Level {
+    const ALL: [Self; 5] = [
+        Self::Echo,
+        Self::Trace,
+        Self::Deprecated,
+        Self::Warning,
+        Self::Error,
+    ];
+
+    /// The prefix OpenSCAD uses for messages of this level
+    #[inline]
+    pub fn prefix(self) -> &'static str {
+        match self {
+            Self::Echo => "ECHO",
+            Self::Trace => "TRACE",
+            Self::Deprecated => "DEPRECATED",
+            Self::Warning => "WARNING",
+            Self::Error => "ERROR",
+        }
+    }
+}
+
+/// A message printed by OpenSCAD, like a warning or an `echo()`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub level: Level,
+    pub message: String,
+    /// The file the message refers to, as printed by OpenSCAD
+    pub file: Option<PathBuf>,
+    /// The line the message refers to, starting from 1
+    pub line: Option<usize>,
+    /// The line of the emitted `.scad` file the message refers to
+    pub source: Option<String>,
+}
+
+impl Diagnostic {
+    /// A warning about the emitted code itself, rather than one printed by OpenSCAD
+    pub(crate) fn warning(message: String) -> Self {
+        Self {
+            level: Level::Warning,
+            message,
+            file: None,
+            line: None,
+            source: None,
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.level.prefix(), self.message)?;
+        if let Some(source) = &self.source {
+            write!(f, "\n  | {source}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses the messages OpenSCAD printed to stderr, mapping the ones in the emitted file back
+/// to its source
+fn diagnostics(stderr: &str, scad: &Path, source: &str) -> Vec<Diagnostic> {
+    let lines: Vec<_> = source.lines().collect();
+
+    stderr
+        .lines()
+        .filter_map(|line| {
+            let (level, message) = Level::ALL.into_iter().find_map(|level| {
+                let message = line.strip_prefix(level.prefix())?.strip_prefix(':')?;
+                Some((level, message.trim()))
+            })?;
+
+            let (file, line) = location(message);
+            let emitted = match &file {
+                Some(file) => file.file_name() == scad.file_name(),
+                None => line.is_some(),
+            };
+            let source = line
+                .filter(|_| emitted)
+                .and_then(|line| lines.get(line.checked_sub(1)?))
+                .map(|line| line.trim().to_string());
+
+            Some(Diagnostic {
+                level,
+                message: message.to_string(),
+                file,
+                line,
+                source,
+            })
+        })
+        .collect()
+}
+
+/// Finds the `in file <file>, line <line>` that OpenSCAD adds to messages
+fn location(message: &str) -> (Option<PathBuf>, Option<usize>) {
+    let line = message.rfind("line ").and_then(|idx| {
+        let digits: String = message[idx + 5..]
+            .chars()
+            .take_while(char::is_ascii_digit)
+            .collect();
+        digits.parse().ok()
+    });
+
+    let file = message.rfind("in file ").and_then(|idx| {
+        let rest = &message[idx + 8..];
+        let file = rest
+            .split(", line")
+            .next()?
+            .trim()
+            .trim_matches(['"', '\'']);
+        (!file.is_empty()).then(|| PathBuf::from(file))
+    });
+
+    (file, line)
+}
+
+/// Renders an animation to numbered PNG frames
+///
+/// The model is written to `target/rsolid/<name>/<name>.scad` and OpenSCAD is run with
+/// `--animate <frames>`, which steps `$t` from `0` towards `1`. The frames are written to
+/// `target/rsolid/<name>/frames/` and returned in order.
+///
+/// # Panics
+///
+/// Panics if the files can't be written or if OpenSCAD fails. Use [`Exporter::animation`] to
+/// handle errors instead.
+pub fn animation<V: Scad>(v: &V, name: impl AsRef<Path>, frames: usize) -> Vec<PathBuf> {
+    let name = name.as_ref();
+    eprintln!("rendering {frames} frames of {}...", name.display());
+
+    let out = Exporter::new()
+        .animation(v, name, frames)
+        .unwrap_or_else(|err| panic!("{err}"));
+    for diagnostic in &out.diagnostics {
+        eprintln!("{diagnostic}");
+    }
+    eprintln!("  done");
+
+    out.frames
+}
+
+#[test]
+fn diagnostics_test() {
+    let source = "cube(1);\nmodule _v0() { sphere(r=foo); }\n\n_v0();";
+    let stderr = "\
+Compiling design (CSG Tree generation)...
+ECHO: \"size\", 10
+WARNING: Ignoring unknown variable 'foo' in file part.scad, line 2
+WARNING: Ignoring unknown module 'bar' in file /lib/other.scad, line 7
+ERROR: Parser error in file \"/tmp/out/part.scad\", line 4: syntax error
+Execution aborted";
+
+    let diagnostics = diagnostics(stderr, Path::new("target/rsolid/part.scad"), source);
+    let levels: Vec<_> = diagnostics.iter().map(|d| d.level).collect();
+    assert_eq!(
+        levels,
+        [Level::Echo, Level::Warning, Level::Warning, Level::Error]
+    );
+
+    assert_eq!(diagnostics[0].message, "\"size\", 10");
+    assert_eq!(diagnostics[0].line, None);
+
+    assert_eq!(diagnostics[1].line, Some(2));
+    assert_eq!(
+        diagnostics[1].source.as_deref(),
+        Some("module _v0() { sphere(r=foo); }")
+    );
+
+    // other files can't be mapped back
+    assert_eq!(diagnostics[2].file, Some(PathBuf::from("/lib/other.scad")));
+    assert_eq!(diagnostics[2].source, None);
+
+    assert_eq!(diagnostics[3].line, Some(4));
+    assert_eq!(diagnostics[3].source.as_deref(), Some("_v0();"));
+    assert_eq!(
+        diagnostics[3].to_string(),
+        "ERROR: Parser error in file \"/tmp/out/part.scad\", line 4: syntax error\n  | _v0();"
+    );
+}
+
+#[test]
+fn exporter_test() {
+    use crate::*;
+    use std::ffi::OsStr;
+
+    let dir = std::env::temp_dir().join("rsolid-exporter-test");
+    let exporter = Exporter::new().out_dir(&dir).define("size", 10);
+
+    let out = exporter.export(&cube(10), "nested/part", &[]).unwrap();
+    assert_eq!(out.scad, dir.join("nested/part.scad"));
+    assert!(out.renders.is_empty());
+    assert_eq!(
+        std::fs::read_to_string(&out.scad).unwrap(),
+        cube(10).to_scad()
+    );
+
+    let size = |default| Length::from(param::<Length>("size", default).var());
+    let out = exporter
+        .export(&(cube(size(1)) + cube(size(2))), "part", &[])
+        .unwrap();
+    assert_eq!(out.diagnostics.len(), 1);
+    assert_eq!(out.diagnostics[0].level, Level::Warning);
+
+    // the scad file comes last, after the format's own options
+    let scad = dir.join("part.scad");
+    let cmd = exporter.render_command(&scad, &Png::snapshot().into());
+    let args: Vec<_> = cmd.get_args().collect();
+    assert_eq!(args.last(), Some(&scad.as_os_str()));
+    assert!(!args.contains(&OsStr::new("--render")), "{args:?}");
+
+    let err = exporter
+        .openscad(dir.join("missing-openscad"))
+        .export(&cube(10), "part", &[&Mesh::StlAscii])
+        .unwrap_err();
+    assert!(matches!(err, ExportError::Spawn { .. }), "{err}");
+}
@@ -0,0 +1,416 @@
+use super::{write, Diagnostic, Export, ExportError, ExportFormat, Exporter, Format};
+use crate::{scad::Scad, IntoObject};
+use core::fmt;
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+impl Exporter {
+    /// Starts a batch of parts that are rendered together with this exporter's settings
+    #[inline]
+    pub fn batch(&self) -> Batch {
+        Batch {
+            exporter: self.clone(),
+            manifest: "manifest.tsv".into(),
+            threads: None,
+            parts: vec![],
+        }
+    }
+}
+
+/// A set of named parts rendered across threads
+///
+/// Each render is skipped when the emitted SCAD, the format and the defines hash to the same
+/// value as in the manifest of the previous run and the rendered file still exists.
+///
+/// ```no_run
+/// use rsolid::{export::*, *};
+///
+/// let out = Exporter::new()
+///     .batch()
+///     .part("parts/base", &cube(10), &[&Mesh::StlAscii])
+///     .part("parts/lid", &(cylinder(2, 5) >> fragment_count(64)), &[&Mesh::StlAscii])
+///     .part("drawings/outline", &square(10), &[&Drawing::Dxf])
+///     .run()
+///     .unwrap();
+///
+/// for (path, err) in out.errors() {
+///     eprintln!("{}: {err}", path.display());
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Batch {
+    exporter: Exporter,
+    manifest: PathBuf,
+    threads: Option<usize>,
+    parts: Vec<Part>,
+}
+
+#[derive(Clone, Debug)]
+struct Part {
+    path: PathBuf,
+    source: String,
+    warnings: Vec<String>,
+    formats: Vec<ExportFormat>,
+}
+
+impl Batch {
+    /// The manifest file, relative to the output directory, which defaults to `manifest.tsv`
+    #[inline]
+    pub fn manifest(mut self, path: impl Into<PathBuf>) -> Self {
+        self.manifest = path.into();
+        self
+    }
+
+    /// The number of renders to run at once, which defaults to the available parallelism
+    #[inline]
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = Some(threads.max(1));
+        self
+    }
+
+    /// Adds a part written to `<out_dir>/<path>.scad` and rendered to each format, which must
+    /// fit the part's dimensions
+    #[inline]
+    pub fn part<const DIMENSIONS: usize, V: IntoObject<DIMENSIONS> + Clone>(
+        mut self,
+        path: impl Into<PathBuf>,
+        v: &V,
+        formats: &[&dyn Format<DIMENSIONS>],
+    ) -> Self {
+        let (source, warnings) = v.clone().into_object().to_scad_with_warnings();
+        self.parts.push(Part {
+            path: path.into(),
+            source,
+            warnings,
+            formats: formats
+                .iter()
+                .map(|format| format.export_format())
+                .collect(),
+        });
+        self
+    }
+
+    /// Writes and renders all the parts, then writes the manifest
+    ///
+    /// A part that fails doesn't stop the others from rendering, and is left out of the
+    /// manifest so it's rendered again next time. The error is only for the manifest itself.
+    pub fn run(&self) -> Result<BatchExport, ExportError> {
+        let exporter = &self.exporter;
+        let manifest_path = exporter.out_dir.join(&self.manifest);
+        let previous = match Manifest::read(&manifest_path) {
+            Ok(manifest) => manifest,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Manifest::default(),
+            Err(err) => return Err(ExportError::io(&manifest_path, err)),
+        };
+
+        let mut results: Vec<_> = self
+            .parts
+            .iter()
+            .map(|part| {
+                let scad = exporter.out_dir.join(&part.path).with_extension("scad");
+                write(&scad, &part.source)?;
+                Ok(Export {
+                    scad,
+                    renders: vec![],
+                    diagnostics: part
+                        .warnings
+                        .iter()
+                        .cloned()
+                        .map(Diagnostic::warning)
+                        .collect(),
+                })
+            })
+            .collect();
+
+        let mut manifest = Manifest::default();
+        let mut cached = vec![];
+        let mut jobs = vec![];
+        for (idx, part) in self.parts.iter().enumerate() {
+            let Ok(out) = &results[idx] else {
+                continue;
+            };
+
+            for format in &part.formats {
+                let dest = out.scad.with_extension(format.ext());
+                let entry = ManifestEntry {
+                    path: dest
+                        .strip_prefix(&exporter.out_dir)
+                        .unwrap_or(&dest)
+                        .to_path_buf(),
+                    format: format.name().to_string(),
+                    hash: exporter.hash(&part.source, format),
+                };
+
+                if previous.entries.contains(&entry) && dest.exists() {
+                    manifest.entries.push(entry);
+                    cached.push(dest);
+                } else {
+                    jobs.push((idx, format, entry));
+                }
+            }
+        }
+
+        for (idx, entry, result) in self.render(&results, jobs) {
+            let Ok(out) = &mut results[idx] else {
+                continue;
+            };
+
+            match result {
+                Ok(diagnostics) => {
+                    for diagnostic in diagnostics {
+                        if !out.diagnostics.contains(&diagnostic) {
+                            out.diagnostics.push(diagnostic);
+                        }
+                    }
+                    manifest.entries.push(entry);
+                }
+                Err(err) => results[idx] = Err(err),
+            }
+        }
+
+        // parts list cached renders too, and a part that failed any render is left out of the
+        // manifest entirely
+        for (part, out) in self.parts.iter().zip(&mut results) {
+            if let Ok(out) = out {
+                out.renders = part
+                    .formats
+                    .iter()
+                    .map(|format| out.scad.with_extension(format.ext()))
+                    .collect();
+            }
+        }
+        manifest.entries.retain(|entry| {
+            let dest = exporter.out_dir.join(&entry.path);
+            results
+                .iter()
+                .flatten()
+                .any(|out| out.renders.contains(&dest))
+        });
+        manifest.entries.sort_by(|a, b| a.path.cmp(&b.path));
+        write(&manifest_path, &manifest.to_string())?;
+
+        Ok(BatchExport {
+            manifest,
+            cached,
+            parts: self
+                .parts
+                .iter()
+                .map(|part| part.path.clone())
+                .zip(results)
+                .collect(),
+        })
+    }
+
+    /// Runs the renders on a pool of threads, returning the results in the order they finish
+    fn render(
+        &self,
+        results: &[Result<Export, ExportError>],
+        jobs: Vec<(usize, &ExportFormat, ManifestEntry)>,
+    ) -> Vec<(usize, ManifestEntry, Result<Vec<Diagnostic>, ExportError>)> {
+        let threads = self
+            .threads
+            .or_else(|| thread::available_parallelism().ok().map(usize::from))
+            .unwrap_or(1)
+            .min(jobs.len());
+        let next = AtomicUsize::new(0);
+
+        thread::scope(|scope| {
+            let workers: Vec<_> = (0..threads)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut done = vec![];
+                        while let Some((idx, format, entry)) =
+                            jobs.get(next.fetch_add(1, Ordering::Relaxed))
+                        {
+                            let Ok(out) = &results[*idx] else {
+                                continue;
+                            };
+                            let source = &self.parts[*idx].source;
+                            let result = self.exporter.render_to(&out.scad, source, format);
+                            done.push((*idx, entry.clone(), result));
+                        }
+                        done
+                    })
+                })
+                .collect();
+
+            workers
+                .into_iter()
+                .flat_map(|worker| worker.join().unwrap())
+                .collect()
+        })
+    }
+}
+
+impl Exporter {
+    /// Hashes everything that affects a render with 64-bit FNV-1a, which unlike the standard
+    /// library's hasher is stable between runs and toolchains
+    fn hash(&self, source: &str, format: &ExportFormat) -> u64 {
+        let mut hash = 0xcbf2_9ce4_8422_2325_u64;
+        let mut write = |bytes: &[u8]| {
+            for byte in bytes.iter().chain([&0]) {
+                hash ^= u64::from(*byte);
+                hash = hash.wrapping_mul(0x0100_0000_01b3);
+            }
+        };
+
+        write(source.as_bytes());
+        for arg in format.args() {
+            write(arg.as_bytes());
+        }
+        for (name, value) in &self.defines {
+            write(name.as_bytes());
+            write(value.as_bytes());
+        }
+        hash
+    }
+}
+
+/// The results of [`Batch::run`]
+#[derive(Debug)]
+pub struct BatchExport {
+    pub manifest: Manifest,
+    /// The rendered files that were up to date and skipped
+    pub cached: Vec<PathBuf>,
+    /// Each part's path and result, in the order they were added
+    pub parts: Vec<(PathBuf, Result<Export, ExportError>)>,
+}
+
+impl BatchExport {
+    /// The parts that failed to export
+    pub fn errors(&self) -> impl Iterator<Item = (&Path, &ExportError)> {
+        self.parts
+            .iter()
+            .filter_map(|(path, result)| Some((path.as_path(), result.as_ref().err()?)))
+    }
+}
+
+/// The rendered files of a batch, written as tab separated `hash`, `format` and `path` lines
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Manifest {
+    pub entries: Vec<ManifestEntry>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ManifestEntry {
+    /// The rendered file, relative to the output directory
+    pub path: PathBuf,
+    pub format: String,
+    /// The hash of the emitted SCAD, the format and the defines
+    pub hash: u64,
+}
+
+impl Manifest {
+    /// Reads a manifest, skipping any lines it doesn't understand
+    pub fn read(path: impl AsRef<Path>) -> io::Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        let entries = text
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.splitn(3, '\t');
+                let hash = u64::from_str_radix(fields.next()?, 16).ok()?;
+                let format = fields.next()?.to_string();
+                let path = fields.next()?.into();
+                Some(ManifestEntry { path, format, hash })
+            })
+            .collect();
+
+        Ok(Self { entries })
+    }
+}
+
+impl fmt::Display for Manifest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for entry in &self.entries {
+            writeln!(
+                f,
+                "{:016x}\t{}\t{}",
+                entry.hash,
+                entry.format,
+                entry.path.display()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn batch_test() {
+    use crate::{export::Mesh, *};
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = std::env::temp_dir().join("rsolid-batch-test");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+
+    // stands in for openscad, touching the file passed to `-o` and failing on `fail.scad`
+    let openscad = dir.join("openscad.sh");
+    std::fs::write(
+        &openscad,
+        "#!/bin/sh\n\
+         for arg; do prev=\"$out\"; out=\"$arg\"; [ \"$prev\" = -o ] && dest=\"$arg\"; done\n\
+         case \"$out\" in *fail.scad) echo 'ERROR: failed' >&2; exit 1;; esac\n\
+         echo 'WARNING: slow' >&2\n\
+         touch \"$dest\"\n",
+    )
+    .unwrap();
+    std::fs::set_permissions(&openscad, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    let exporter = Exporter::new().out_dir(dir.join("out")).openscad(&openscad);
+    let formats: &[&dyn Format<3>] = &[&Mesh::StlAscii, &Mesh::Off];
+    let run = |size: f64| {
+        exporter
+            .batch()
+            .threads(2)
+            .part("base", &cube(size), formats)
+            .part("parts/lid", &cylinder(2, 5), formats)
+            .part("fail", &sphere(1), formats)
+            .run()
+            .unwrap()
+    };
+
+    let out = run(10.0);
+    assert!(out.cached.is_empty());
+    assert_eq!(
+        out.errors().map(|(path, _)| path).collect::<Vec<_>>(),
+        [Path::new("fail")]
+    );
+
+    let base = out.parts[0].1.as_ref().unwrap();
+    assert_eq!(
+        base.renders,
+        [dir.join("out/base.stl"), dir.join("out/base.off")]
+    );
+    assert_eq!(base.diagnostics.len(), 1);
+
+    let paths: Vec<_> = out.manifest.entries.iter().map(|e| &e.path).collect();
+    assert_eq!(
+        paths,
+        [
+            Path::new("base.off"),
+            Path::new("base.stl"),
+            Path::new("parts/lid.off"),
+            Path::new("parts/lid.stl")
+        ]
+    );
+    assert_eq!(
+        Manifest::read(dir.join("out/manifest.tsv")).unwrap(),
+        out.manifest
+    );
+
+    // nothing changed, so only the failed part is tried again
+    let out = run(10.0);
+    assert_eq!(out.cached.len(), 4);
+    assert_eq!(out.errors().count(), 1);
+
+    let out = run(20.0);
+    assert_eq!(
+        out.cached,
+        [dir.join("out/parts/lid.stl"), dir.join("out/parts/lid.off")]
+    );
+}
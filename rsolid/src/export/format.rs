@@ -0,0 +1,400 @@
+use core::fmt;
+
+/// A file format OpenSCAD can render to
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExportFormat {
+    Mesh(Mesh),
+    Drawing(Drawing),
+    Png(Png),
+    /// Any other `--export-format`, which is also used as the file extension
+    Other(String),
+}
+
+/// A format for 3D objects
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Mesh {
+    StlAscii,
+    StlBinary,
+    ThreeMf,
+    Off,
+    Obj,
+    Amf,
+}
+
+/// A format for 2D objects
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Drawing {
+    Svg,
+    Dxf,
+    Pdf,
+}
+
+/// A rendered image, which works for both 2D and 3D objects
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Png {
+    pub settings: RenderSettings,
+}
+
+/// How a model is drawn to an image
+///
+/// Unset options are left to OpenSCAD's defaults, which can change between versions, so set
+/// them all or start from [`RenderSettings::snapshot`] for images that don't change.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RenderSettings {
+    /// The width and height in pixels, with `--imgsize`
+    pub size: Option<[u32; 2]>,
+    /// The camera position, with `--camera`
+    pub camera: Option<Camera>,
+    /// Moves the camera to look at the center of the model, with `--autocenter`
+    pub autocenter: bool,
+    /// Moves the camera back until the whole model is in view, with `--viewall`
+    pub viewall: bool,
+    /// The name of one of OpenSCAD's color schemes, like `"Tomorrow Night"`
+    pub colorscheme: Option<String>,
+    pub projection: Option<Projection>,
+    /// Extra things to draw over the model
+    pub view: Vec<View>,
+}
+
+/// Where the camera is placed
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Camera {
+    /// Rotates about the origin by degrees, then moves back by the distance
+    Gimbal {
+        translate: [f64; 3],
+        rotate: [f64; 3],
+        distance: f64,
+    },
+    /// Looks from the eye towards the center
+    Eye { eye: [f64; 3], center: [f64; 3] },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Projection {
+    Perspective,
+    Orthographic,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum View {
+    Axes,
+    Crosshairs,
+    Edges,
+    Scales,
+    Wireframe,
+}
+
+/// A format that can render objects with the given number of dimensions
+///
+/// This is what lets [`Exporter::export`](super::Exporter::export) reject a format that doesn't
+/// fit the object when compiling:
+///
+/// ```compile_fail
+/// use rsolid::{export::{Drawing, Exporter}, *};
+///
+/// let _ = Exporter::new().export(&cube(1), "cube", &[&Drawing::Svg]);
+/// ```
+pub trait Format<const DIMENSIONS: usize> {
+    fn export_format(&self) -> ExportFormat;
+}
+
+impl Format<3> for Mesh {
+    #[inline]
+    fn export_format(&self) -> ExportFormat {
+        ExportFormat::Mesh(*self)
+    }
+}
+
+impl Format<2> for Drawing {
+    #[inline]
+    fn export_format(&self) -> ExportFormat {
+        ExportFormat::Drawing(*self)
+    }
+}
+
+impl<const DIMENSIONS: usize> Format<DIMENSIONS> for Png {
+    #[inline]
+    fn export_format(&self) -> ExportFormat {
+        ExportFormat::Png(self.clone())
+    }
+}
+
+impl ExportFormat {
+    /// The extension of the rendered file
+    pub fn ext(&self) -> &str {
+        match self {
+            Self::Mesh(Mesh::StlAscii | Mesh::StlBinary) => "stl",
+            Self::Mesh(Mesh::ThreeMf) => "3mf",
+            Self::Mesh(Mesh::Off) => "off",
+            Self::Mesh(Mesh::Obj) => "obj",
+            Self::Mesh(Mesh::Amf) => "amf",
+            Self::Drawing(Drawing::Svg) => "svg",
+            Self::Drawing(Drawing::Dxf) => "dxf",
+            Self::Drawing(Drawing::Pdf) => "pdf",
+            Self::Png(_) => "png",
+            Self::Other(format) => format,
+        }
+    }
+
+    /// The name OpenSCAD's `--export-format` uses
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Mesh(Mesh::StlAscii) => "asciistl",
+            Self::Mesh(Mesh::StlBinary) => "binstl",
+            _ => self.ext(),
+        }
+    }
+
+    /// The arguments passed to OpenSCAD to select this format and its options
+    pub fn args(&self) -> Vec<String> {
+        let mut args = vec!["--export-format".to_string(), self.name().to_string()];
+        if let Self::Png(png) = self {
+            args.extend(png.settings.args());
+        }
+        args
+    }
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.args().join(" "))
+    }
+}
+
+impl From<&str> for ExportFormat {
+    /// Picks the format for an extension or `--export-format` name, with the default options
+    fn from(value: &str) -> Self {
+        match value {
+            "stl" | "asciistl" => Self::Mesh(Mesh::StlAscii),
+            "binstl" => Self::Mesh(Mesh::StlBinary),
+            "3mf" => Self::Mesh(Mesh::ThreeMf),
+            "off" => Self::Mesh(Mesh::Off),
+            "obj" => Self::Mesh(Mesh::Obj),
+            "amf" => Self::Mesh(Mesh::Amf),
+            "svg" => Self::Drawing(Drawing::Svg),
+            "dxf" => Self::Drawing(Drawing::Dxf),
+            "pdf" => Self::Drawing(Drawing::Pdf),
+            "png" => Self::Png(Png::default()),
+            _ => Self::Other(value.to_string()),
+        }
+    }
+}
+
+impl From<String> for ExportFormat {
+    #[inline]
+    fn from(value: String) -> Self {
+        value.as_str().into()
+    }
+}
+
+impl From<Mesh> for ExportFormat {
+    #[inline]
+    fn from(value: Mesh) -> Self {
+        Self::Mesh(value)
+    }
+}
+
+impl From<Drawing> for ExportFormat {
+    #[inline]
+    fn from(value: Drawing) -> Self {
+        Self::Drawing(value)
+    }
+}
+
+impl From<Png> for ExportFormat {
+    #[inline]
+    fn from(value: Png) -> Self {
+        Self::Png(value)
+    }
+}
+
+impl Png {
+    #[inline]
+    pub fn new(settings: RenderSettings) -> Self {
+        Self { settings }
+    }
+
+    /// An image with the [`RenderSettings::snapshot`] settings, which doesn't change for the
+    /// same model
+    #[inline]
+    pub fn snapshot() -> Self {
+        Self::new(RenderSettings::snapshot())
+    }
+}
+
+impl From<RenderSettings> for Png {
+    #[inline]
+    fn from(settings: RenderSettings) -> Self {
+        Self::new(settings)
+    }
+}
+
+impl RenderSettings {
+    /// Settings that give the same image for the same model: a 512×512 orthographic view from
+    /// OpenSCAD's default angle that fits the whole model, in the `Cornfield` color scheme
+    pub fn snapshot() -> Self {
+        Self::default()
+            .size(512, 512)
+            .camera(Camera::Gimbal {
+                translate: [0.0; 3],
+                rotate: [55.0, 0.0, 25.0],
+                distance: 500.0,
+            })
+            .autocenter()
+            .viewall()
+            .projection(Projection::Orthographic)
+            .colorscheme("Cornfield")
+    }
+
+    #[inline]
+    pub fn size(mut self, width: u32, height: u32) -> Self {
+        self.size = Some([width, height]);
+        self
+    }
+
+    #[inline]
+    pub fn camera(mut self, camera: Camera) -> Self {
+        self.camera = Some(camera);
+        self
+    }
+
+    #[inline]
+    pub fn autocenter(mut self) -> Self {
+        self.autocenter = true;
+        self
+    }
+
+    #[inline]
+    pub fn viewall(mut self) -> Self {
+        self.viewall = true;
+        self
+    }
+
+    #[inline]
+    pub fn colorscheme(mut self, colorscheme: impl Into<String>) -> Self {
+        self.colorscheme = Some(colorscheme.into());
+        self
+    }
+
+    #[inline]
+    pub fn projection(mut self, projection: Projection) -> Self {
+        self.projection = Some(projection);
+        self
+    }
+
+    #[inline]
+    pub fn view(mut self, view: View) -> Self {
+        self.view.push(view);
+        self
+    }
+
+    /// The arguments passed to OpenSCAD for these settings
+    pub fn args(&self) -> Vec<String> {
+        let mut args = vec![];
+        if let Some([width, height]) = self.size {
+            args.push(format!("--imgsize={width},{height}"));
+        }
+        if let Some(camera) = &self.camera {
+            args.push(format!("--camera={camera}"));
+        }
+        if self.autocenter {
+            args.push("--autocenter".to_string());
+        }
+        if self.viewall {
+            args.push("--viewall".to_string());
+        }
+        if let Some(colorscheme) = &self.colorscheme {
+            args.push(format!("--colorscheme={colorscheme}"));
+        }
+        if let Some(projection) = self.projection {
+            args.push(format!("--projection={projection}"));
+        }
+        if !self.view.is_empty() {
+            let view: Vec<_> = self.view.iter().map(ToString::to_string).collect();
+            args.push(format!("--view={}", view.join(",")));
+        }
+        args
+    }
+}
+
+impl fmt::Display for Camera {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let values: Vec<f64> = match self {
+            Self::Gimbal {
+                translate,
+                rotate,
+                distance,
+            } => [&translate[..], &rotate[..], &[*distance]].concat(),
+            Self::Eye { eye, center } => [&eye[..], &center[..]].concat(),
+        };
+        let values: Vec<_> = values.iter().map(ToString::to_string).collect();
+        f.write_str(&values.join(","))
+    }
+}
+
+impl fmt::Display for Projection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::Perspective => "perspective",
+            Self::Orthographic => "ortho",
+        })
+    }
+}
+
+impl fmt::Display for View {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::Axes => "axes",
+            Self::Crosshairs => "crosshairs",
+            Self::Edges => "edges",
+            Self::Scales => "scales",
+            Self::Wireframe => "wireframe",
+        })
+    }
+}
+
+#[test]
+fn format_test() {
+    assert_eq!(ExportFormat::from("stl"), Mesh::StlAscii.into());
+    assert_eq!(ExportFormat::from("3mf").ext(), "3mf");
+    assert_eq!(ExportFormat::from("csg"), ExportFormat::Other("csg".into()));
+    assert_eq!(Mesh::StlBinary.export_format().ext(), "stl");
+
+    let png = RenderSettings::default()
+        .size(800, 600)
+        .camera(Camera::Eye {
+            eye: [50.0, -50.0, 40.0],
+            center: [0.0, 0.0, 5.0],
+        })
+        .colorscheme("Tomorrow Night")
+        .projection(Projection::Orthographic)
+        .view(View::Axes)
+        .view(View::Edges);
+    assert_eq!(
+        ExportFormat::from(Png::new(png)).args(),
+        [
+            "--export-format",
+            "png",
+            "--imgsize=800,600",
+            "--camera=50,-50,40,0,0,5",
+            "--colorscheme=Tomorrow Night",
+            "--projection=ortho",
+            "--view=axes,edges",
+        ]
+    );
+    assert_eq!(
+        RenderSettings::snapshot().args(),
+        [
+            "--imgsize=512,512",
+            "--camera=0,0,0,55,0,25,500",
+            "--autocenter",
+            "--viewall",
+            "--colorscheme=Cornfield",
+            "--projection=ortho",
+        ]
+    );
+    assert_eq!(
+        Mesh::StlBinary.export_format().to_string(),
+        "--export-format binstl"
+    );
+}
@@ -0,0 +1,495 @@
+use crate::scad::{self, Scad};
+use core::fmt::{self, Write};
+use std::sync::Arc;
+
+/// A numeric value that is either a constant or a symbolic OpenSCAD expression
+///
+/// Expressions are folded when all of their operands are constants, so plain numbers
+/// are still emitted as literals.
+#[derive(Clone, Debug)]
+pub enum Expr {
+    Const(f64),
+    Bool(bool),
+    Var(Variable),
+    /// The result of a generated function, like [`norm`](crate::norm)
+    Value(Arc<dyn Value>),
+    Unary(UnaryOp, Arc<Expr>),
+    Binary(BinaryOp, Arc<Expr>, Arc<Expr>),
+    /// A call to one of OpenSCAD's builtin math functions
+    Call(&'static str, Vec<Expr>),
+    /// `cond ? a : b`
+    Ternary(Arc<[Expr; 3]>),
+}
+
+/// A function result that can be used as part of an expression
+pub trait Value: 'static + Scad + Send + Sync + fmt::Debug + fmt::Display {}
+
+impl<T: 'static + Scad + Send + Sync + fmt::Debug + fmt::Display> Value for T {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnaryOp {
+    Neg,
+    Not,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne,
+    And,
+    Or,
+}
+
+impl BinaryOp {
+    fn symbol(self) -> &'static str {
+        match self {
+            Self::Add => "+",
+            Self::Sub => "-",
+            Self::Mul => "*",
+            Self::Div => "/",
+            Self::Rem => "%",
+            Self::Lt => "<",
+            Self::Le => "<=",
+            Self::Gt => ">",
+            Self::Ge => ">=",
+            Self::Eq => "==",
+            Self::Ne => "!=",
+            Self::And => "&&",
+            Self::Or => "||",
+        }
+    }
+
+    fn precedence(self) -> u8 {
+        match self {
+            Self::Or => 1,
+            Self::And => 2,
+            Self::Eq | Self::Ne => 3,
+            Self::Lt | Self::Le | Self::Gt | Self::Ge => 4,
+            Self::Add | Self::Sub => 5,
+            Self::Mul | Self::Div | Self::Rem => 6,
+        }
+    }
+
+    fn fold(self, a: &Expr, b: &Expr) -> Option<Expr> {
+        use Expr::{Bool, Const};
+
+        Some(match (self, a, b) {
+            (Self::Add, Const(a), Const(b)) => Const(a + b),
+            (Self::Sub, Const(a), Const(b)) => Const(a - b),
+            (Self::Mul, Const(a), Const(b)) => Const(a * b),
+            (Self::Div, Const(a), Const(b)) => Const(a / b),
+            (Self::Rem, Const(a), Const(b)) => Const(a % b),
+            (Self::Lt, Const(a), Const(b)) => Bool(a < b),
+            (Self::Le, Const(a), Const(b)) => Bool(a <= b),
+            (Self::Gt, Const(a), Const(b)) => Bool(a > b),
+            (Self::Ge, Const(a), Const(b)) => Bool(a >= b),
+            (Self::Eq, Const(a), Const(b)) => Bool(a == b),
+            (Self::Ne, Const(a), Const(b)) => Bool(a != b),
+            (Self::And, Bool(a), Bool(b)) => Bool(*a && *b),
+            (Self::Or, Bool(a), Bool(b)) => Bool(*a || *b),
+            // identities that show up when interpolating between equal values
+            (Self::Add, Const(z), x) | (Self::Add | Self::Sub, x, Const(z)) if *z == 0.0 => {
+                x.clone()
+            }
+            (Self::Mul, Const(z), _) | (Self::Mul, _, Const(z)) if *z == 0.0 => Const(0.0),
+            (Self::Mul, Const(one), x) | (Self::Mul | Self::Div, x, Const(one)) if *one == 1.0 => {
+                x.clone()
+            }
+            _ => return None,
+        })
+    }
+}
+
+impl Expr {
+    /// Wraps the result of a function so it can be used in an expression
+    #[inline]
+    pub fn value<V: Value>(value: V) -> Self {
+        Self::Value(Arc::new(value))
+    }
+
+    /// Returns the value if the expression is a constant
+    #[inline]
+    pub fn as_const(&self) -> Option<f64> {
+        match self {
+            Self::Const(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn binary(op: BinaryOp, a: Self, b: Self) -> Self {
+        op.fold(&a, &b)
+            .unwrap_or_else(|| Self::Binary(op, Arc::new(a), Arc::new(b)))
+    }
+
+    /// Calls one of OpenSCAD's builtin math functions, folding it if all of the
+    /// arguments are constant
+    pub fn call(name: &'static str, args: Vec<Self>) -> Self {
+        let consts: Option<Vec<f64>> = args.iter().map(Self::as_const).collect();
+
+        // OpenSCAD's trig functions work in degrees
+        let folded = consts.and_then(|consts| match (name, consts.as_slice()) {
+            ("sin", [a]) => Some(a.to_radians().sin()),
+            ("cos", [a]) => Some(a.to_radians().cos()),
+            ("tan", [a]) => Some(a.to_radians().tan()),
+            ("asin", [a]) => Some(a.asin().to_degrees()),
+            ("acos", [a]) => Some(a.acos().to_degrees()),
+            ("atan", [a]) => Some(a.atan().to_degrees()),
+            ("atan2", [y, x]) => Some(y.atan2(*x).to_degrees()),
+            ("abs", [a]) => Some(a.abs()),
+            ("sqrt", [a]) => Some(a.sqrt()),
+            ("pow", [a, b]) => Some(a.powf(*b)),
+            ("min", [a, b]) => Some(a.min(*b)),
+            ("max", [a, b]) => Some(a.max(*b)),
+            _ => None,
+        });
+
+        match folded {
+            Some(value) => Self::Const(value),
+            None => Self::Call(name, args),
+        }
+    }
+
+    /// `cond ? if_true : if_false`
+    pub fn ternary(cond: Self, if_true: Self, if_false: Self) -> Self {
+        match cond {
+            Self::Bool(true) => if_true,
+            Self::Bool(false) => if_false,
+            _ if if_true == if_false => if_true,
+            cond => Self::Ternary(Arc::new([cond, if_true, if_false])),
+        }
+    }
+
+    fn precedence(&self) -> u8 {
+        match self {
+            Self::Ternary(_) => 0,
+            Self::Binary(op, ..) => op.precedence(),
+            Self::Unary(..) => 7,
+            // non-finite literals are written as divisions by zero
+            Self::Const(value) if !value.is_finite() => BinaryOp::Div.precedence(),
+            // negative literals are wrapped like unary expressions
+            Self::Const(value) if value.is_sign_negative() => 7,
+            _ => 8,
+        }
+    }
+
+    /// Writes the expression, assigning any function values and declaring any
+    /// parameters if a formatter is given
+    fn write(&self, out: &mut String, mut f: Option<&mut scad::Formatter>) {
+        // wraps sub-expressions that bind looser than their position allows
+        fn child(out: &mut String, expr: &Expr, min: u8, f: Option<&mut scad::Formatter>) {
+            if expr.precedence() < min {
+                out.push('(');
+                expr.write(out, f);
+                out.push(')');
+            } else {
+                expr.write(out, f);
+            }
+        }
+
+        match self {
+            // OpenSCAD has no literals for infinity or NaN
+            Self::Const(value) if value.is_nan() => out.push_str("0 / 0"),
+            Self::Const(value) if value.is_infinite() => {
+                out.push_str(if *value > 0.0 { "1 / 0" } else { "-1 / 0" })
+            }
+            Self::Const(value) => {
+                let _ = write!(out, "{value}");
+            }
+            Self::Bool(value) => {
+                let _ = write!(out, "{value}");
+            }
+            Self::Var(var) => {
+                if let Some(f) = f {
+                    var.declare(f);
+                }
+                out.push_str(var.name());
+            }
+            Self::Value(value) => {
+                let _ = match f {
+                    Some(f) => write!(out, "{}", value.assign(f)),
+                    None => write!(out, "{value}"),
+                };
+            }
+            Self::Unary(op, value) => {
+                out.push(match op {
+                    UnaryOp::Neg => '-',
+                    UnaryOp::Not => '!',
+                });
+                // `--x` would be parsed differently, so wrap anything that isn't an atom
+                child(out, value, 8, f);
+            }
+            Self::Binary(op, a, b) => {
+                let precedence = op.precedence();
+                child(out, a, precedence, f.as_deref_mut());
+                let _ = write!(out, " {} ", op.symbol());
+                // everything is left associative, so the right side needs to bind tighter
+                child(out, b, precedence + 1, f);
+            }
+            Self::Call(name, args) => {
+                out.push_str(name);
+                out.push('(');
+                for (idx, arg) in args.iter().enumerate() {
+                    if idx != 0 {
+                        out.push_str(", ");
+                    }
+                    arg.write(out, f.as_deref_mut());
+                }
+                out.push(')');
+            }
+            Self::Ternary(args) => {
+                let [cond, a, b] = &**args;
+                child(out, cond, 1, f.as_deref_mut());
+                out.push_str(" ? ");
+                child(out, a, 1, f.as_deref_mut());
+                out.push_str(" : ");
+                child(out, b, 0, f);
+            }
+        }
+    }
+}
+
+impl Default for Expr {
+    #[inline]
+    fn default() -> Self {
+        Self::Const(0.0)
+    }
+}
+
+impl PartialEq for Expr {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Const(a), Self::Const(b)) => a == b,
+            (Self::Bool(a), Self::Bool(b)) => a == b,
+            (Self::Var(a), Self::Var(b)) => a == b,
+            (Self::Value(a), Self::Value(b)) => a.to_scad() == b.to_scad(),
+            (Self::Unary(op_a, a), Self::Unary(op_b, b)) => op_a == op_b && a == b,
+            (Self::Binary(op_a, a1, a2), Self::Binary(op_b, b1, b2)) => {
+                op_a == op_b && a1 == b1 && a2 == b2
+            }
+            (Self::Call(name_a, a), Self::Call(name_b, b)) => name_a == name_b && a == b,
+            (Self::Ternary(a), Self::Ternary(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl From<f64> for Expr {
+    #[inline]
+    fn from(value: f64) -> Self {
+        Self::Const(value)
+    }
+}
+
+impl From<i64> for Expr {
+    #[inline]
+    fn from(value: i64) -> Self {
+        Self::Const(value as f64)
+    }
+}
+
+impl From<bool> for Expr {
+    #[inline]
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl core::ops::Neg for Expr {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        match self {
+            Self::Const(value) => Self::Const(-value),
+            Self::Unary(UnaryOp::Neg, value) => Arc::unwrap_or_clone(value),
+            value => Self::Unary(UnaryOp::Neg, Arc::new(value)),
+        }
+    }
+}
+
+impl core::ops::Not for Expr {
+    type Output = Self;
+
+    #[inline]
+    fn not(self) -> Self {
+        match self {
+            Self::Bool(value) => Self::Bool(!value),
+            Self::Unary(UnaryOp::Not, value) => Arc::unwrap_or_clone(value),
+            value => Self::Unary(UnaryOp::Not, Arc::new(value)),
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut out = String::new();
+        self.write(&mut out, None);
+        f.write_str(&out)
+    }
+}
+
+impl Scad for Expr {
+    fn assign(&self, f: &mut scad::Formatter) -> scad::Assignment {
+        let mut out = String::new();
+        self.write(&mut out, Some(f));
+        f.value(out)
+    }
+}
+
+/// A boolean expression, used to pick between values with [`ternary`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Condition(pub Expr);
+
+impl Condition {
+    /// `self ? if_true : if_false`
+    #[inline]
+    pub fn select<T>(self, if_true: impl Into<T>, if_false: impl Into<T>) -> T
+    where
+        T: From<Expr> + Into<Expr>,
+    {
+        ternary(self, if_true, if_false)
+    }
+}
+
+impl From<bool> for Condition {
+    #[inline]
+    fn from(value: bool) -> Self {
+        Self(value.into())
+    }
+}
+
+impl core::ops::Not for Condition {
+    type Output = Self;
+
+    #[inline]
+    fn not(self) -> Self {
+        Self(!self.0)
+    }
+}
+
+impl<R: Into<Condition>> core::ops::BitAnd<R> for Condition {
+    type Output = Self;
+
+    #[inline]
+    fn bitand(self, other: R) -> Self {
+        Self(Expr::binary(BinaryOp::And, self.0, other.into().0))
+    }
+}
+
+impl<R: Into<Condition>> core::ops::BitOr<R> for Condition {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, other: R) -> Self {
+        Self(Expr::binary(BinaryOp::Or, self.0, other.into().0))
+    }
+}
+
+impl fmt::Display for Condition {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Picks between two values, emitted as OpenSCAD's `cond ? if_true : if_false`
+///
+/// ```
+/// use rsolid::*;
+///
+/// // use a coarser model while previewing
+/// let segments: Scalar = ternary(preview(), 16, 64);
+/// ```
+pub fn ternary<T>(cond: impl Into<Condition>, if_true: impl Into<T>, if_false: impl Into<T>) -> T
+where
+    T: From<Expr> + Into<Expr>,
+{
+    T::from(Expr::ternary(
+        cond.into().0,
+        if_true.into().into(),
+        if_false.into().into(),
+    ))
+}
+
+/// A reference to a variable in the emitted OpenSCAD
+#[derive(Clone, Debug)]
+pub enum Variable {
+    /// A builtin variable, like `$t` or `$preview`
+    Builtin(&'static str),
+    /// A top-level customizer parameter
+    Parameter(Arc<dyn scad::Parameter>),
+}
+
+impl Variable {
+    #[inline]
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Builtin(name) => name,
+            Self::Parameter(param) => param.name(),
+        }
+    }
+
+    fn declare(&self, f: &mut scad::Formatter) {
+        if let Self::Parameter(param) = self {
+            f.parameter(param.clone());
+        }
+    }
+}
+
+impl PartialEq for Variable {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.name() == other.name()
+    }
+}
+
+impl fmt::Display for Variable {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[test]
+fn expr_test() {
+    use crate::*;
+
+    let spin = param::<Angle>("spin", 30).var();
+    let width = param::<Length>("width", 10).var();
+
+    let x = (Length::from(&width) + 2) * Angle::from(&spin).cos();
+    let y = -(Length::from(&width) - Length::from(1) - 2) / 2;
+    let z = ternary::<Length>(preview(), x.clone().max(1), Length::from(5) * 2);
+    let folded = Length::from(4) * Angle::from(60).cos() + 1;
+
+    assert_eq!(folded, Length::from(3.0000000000000004));
+    insta::assert_snapshot!(format!("{x}\n{y}\n{z}"));
+
+    let offset = Length::from(&width) * Scalar::from(norm([1, 2, 3])) * Angle::from(&spin).sin();
+    insta::assert_snapshot!("expr_test_scad", offset.to_scad());
+}
+
+#[test]
+fn non_finite_test() {
+    use crate::*;
+
+    let inf = Length::from(1) / 0;
+    assert_eq!(inf.0.to_string(), "1 / 0");
+    assert_eq!((-inf.clone()).0.to_string(), "-1 / 0");
+    assert_eq!((Length::from(0) / 0).0.to_string(), "0 / 0");
+    // the division has to be wrapped when it's the right side of another division
+    let width = param::<Length>("width", 10).var();
+    assert_eq!(
+        (Length::from(&width) / inf).0.to_string(),
+        "width / (1 / 0)"
+    );
+}
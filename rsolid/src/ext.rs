@@ -1,32 +1,44 @@
 use crate::{
     extension as extensions, primitive as primitives,
-    types::{Angle3, Length, Length3, Scalar3},
+    types::{Angle3, Color, Length, Length3, Scalar3},
     IntoObject, Object,
 };
 
 pub trait ObjectExt<const DIMENSIONS: usize>: IntoObject<DIMENSIONS> + Sized {
     #[inline]
     fn union<B: IntoObject<DIMENSIONS>>(self, b: B) -> Object<DIMENSIONS> {
-        crate::block::Block(self.into_object(), b.into_object()).into_object()
-            >> primitives::union()
+        crate::block::Boolean::join(
+            crate::block::BooleanKind::Union,
+            self.into_object(),
+            b.into_object(),
+        )
     }
 
     #[inline]
     fn difference<B: IntoObject<DIMENSIONS>>(self, b: B) -> Object<DIMENSIONS> {
-        crate::block::Block(self.into_object(), b.into_object()).into_object()
-            >> primitives::difference()
+        crate::block::Boolean::join(
+            crate::block::BooleanKind::Difference,
+            self.into_object(),
+            b.into_object(),
+        )
     }
 
     #[inline]
     fn intersection<B: IntoObject<DIMENSIONS>>(self, b: B) -> Object<DIMENSIONS> {
-        crate::block::Block(self.into_object(), b.into_object()).into_object()
-            >> primitives::intersection()
+        crate::block::Boolean::join(
+            crate::block::BooleanKind::Intersection,
+            self.into_object(),
+            b.into_object(),
+        )
     }
 
     #[inline]
     fn minkowski<B: IntoObject<DIMENSIONS>>(self, b: B) -> Object<DIMENSIONS> {
-        crate::block::Block(self.into_object(), b.into_object()).into_object()
-            >> primitives::minkowski()
+        crate::block::Boolean::join(
+            crate::block::BooleanKind::Minkowski,
+            self.into_object(),
+            b.into_object(),
+        )
     }
 
     #[inline]
@@ -61,7 +73,7 @@ pub trait ObjectExt<const DIMENSIONS: usize>: IntoObject<DIMENSIONS> + Sized {
 
     #[inline]
     fn scale<V: Into<Scalar3>>(self, v: V) -> Object<DIMENSIONS> {
-        self.into_object() >> primitives::scale(v)
+        self.into_object() >> primitives::scale(v.into())
     }
 
     #[inline]
@@ -76,7 +88,12 @@ pub trait ObjectExt<const DIMENSIONS: usize>: IntoObject<DIMENSIONS> + Sized {
 
     #[inline]
     fn rotate<V: Into<Angle3>>(self, v: V) -> Object<DIMENSIONS> {
-        self.into_object() >> primitives::rotate(v)
+        self.into_object() >> primitives::rotate(v.into())
+    }
+
+    #[inline]
+    fn color<C: Into<Color>>(self, c: C) -> Object<DIMENSIONS> {
+        self.into_object() >> primitives::color(c)
     }
 
     /// Use this subtree as usual in the rendering process but also draw it unmodified in transparent pink.
@@ -33,6 +33,20 @@ impl<const DIMENSIONS: usize> ::core::fmt::Display for MirrorX<DIMENSIONS> {
     }
 }
 
+impl<const DIMENSIONS: usize> crate::Node for MirrorX<DIMENSIONS> {
+    fn name(&self) -> &str {
+        "mirror_x"
+    }
+
+    fn kind(&self) -> crate::NodeKind {
+        crate::NodeKind::Operator
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        Vec::new()
+    }
+}
+
 impl<const DIMENSIONS: usize> crate::Operator<DIMENSIONS> for MirrorX<DIMENSIONS> {
     type Output = crate::Object<DIMENSIONS>;
 
@@ -138,6 +152,20 @@ impl<const DIMENSIONS: usize> ::core::fmt::Display for MirrorY<DIMENSIONS> {
     }
 }
 
+impl<const DIMENSIONS: usize> crate::Node for MirrorY<DIMENSIONS> {
+    fn name(&self) -> &str {
+        "mirror_y"
+    }
+
+    fn kind(&self) -> crate::NodeKind {
+        crate::NodeKind::Operator
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        Vec::new()
+    }
+}
+
 impl<const DIMENSIONS: usize> crate::Operator<DIMENSIONS> for MirrorY<DIMENSIONS> {
     type Output = crate::Object<DIMENSIONS>;
 
@@ -243,6 +271,20 @@ impl<const DIMENSIONS: usize> ::core::fmt::Display for MirrorZ<DIMENSIONS> {
     }
 }
 
+impl<const DIMENSIONS: usize> crate::Node for MirrorZ<DIMENSIONS> {
+    fn name(&self) -> &str {
+        "mirror_z"
+    }
+
+    fn kind(&self) -> crate::NodeKind {
+        crate::NodeKind::Operator
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        Vec::new()
+    }
+}
+
 impl<const DIMENSIONS: usize> crate::Operator<DIMENSIONS> for MirrorZ<DIMENSIONS> {
     type Output = crate::Object<DIMENSIONS>;
 
@@ -37,6 +37,20 @@ impl<const DIMENSIONS: usize> ::core::fmt::Display for Bg<DIMENSIONS> {
     }
 }
 
+impl<const DIMENSIONS: usize> crate::Node for Bg<DIMENSIONS> {
+    fn name(&self) -> &str {
+        "bg"
+    }
+
+    fn kind(&self) -> crate::NodeKind {
+        crate::NodeKind::Operator
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        Vec::new()
+    }
+}
+
 impl<const DIMENSIONS: usize> crate::Operator<DIMENSIONS> for Bg<DIMENSIONS> {
     type Output = crate::Object<DIMENSIONS>;
 
@@ -142,6 +156,20 @@ impl<const DIMENSIONS: usize> ::core::fmt::Display for Dbg<DIMENSIONS> {
     }
 }
 
+impl<const DIMENSIONS: usize> crate::Node for Dbg<DIMENSIONS> {
+    fn name(&self) -> &str {
+        "dbg"
+    }
+
+    fn kind(&self) -> crate::NodeKind {
+        crate::NodeKind::Operator
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        Vec::new()
+    }
+}
+
 impl<const DIMENSIONS: usize> crate::Operator<DIMENSIONS> for Dbg<DIMENSIONS> {
     type Output = crate::Object<DIMENSIONS>;
 
@@ -247,6 +275,20 @@ impl<const DIMENSIONS: usize> ::core::fmt::Display for Disable<DIMENSIONS> {
     }
 }
 
+impl<const DIMENSIONS: usize> crate::Node for Disable<DIMENSIONS> {
+    fn name(&self) -> &str {
+        "disable"
+    }
+
+    fn kind(&self) -> crate::NodeKind {
+        crate::NodeKind::Operator
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        Vec::new()
+    }
+}
+
 impl<const DIMENSIONS: usize> crate::Operator<DIMENSIONS> for Disable<DIMENSIONS> {
     type Output = crate::Object<DIMENSIONS>;
 
@@ -352,6 +394,20 @@ impl<const DIMENSIONS: usize> ::core::fmt::Display for Root<DIMENSIONS> {
     }
 }
 
+impl<const DIMENSIONS: usize> crate::Node for Root<DIMENSIONS> {
+    fn name(&self) -> &str {
+        "root"
+    }
+
+    fn kind(&self) -> crate::NodeKind {
+        crate::NodeKind::Operator
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        Vec::new()
+    }
+}
+
 impl<const DIMENSIONS: usize> crate::Operator<DIMENSIONS> for Root<DIMENSIONS> {
     type Output = crate::Object<DIMENSIONS>;
 
@@ -33,6 +33,20 @@ impl<const DIMENSIONS: usize> ::core::fmt::Display for InPreview<DIMENSIONS> {
     }
 }
 
+impl<const DIMENSIONS: usize> crate::Node for InPreview<DIMENSIONS> {
+    fn name(&self) -> &str {
+        "in_preview"
+    }
+
+    fn kind(&self) -> crate::NodeKind {
+        crate::NodeKind::Operator
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        Vec::new()
+    }
+}
+
 impl<const DIMENSIONS: usize> crate::Operator<DIMENSIONS> for InPreview<DIMENSIONS> {
     type Output = crate::Object<DIMENSIONS>;
 
@@ -1,8 +1,8 @@
 /// Rotates children around the X axis by the given number of degrees.
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Default)]
 #[must_use = "Objects must be returned in order to be rendered"]
 pub struct RotateX<const DIMENSIONS: usize> {
-    angle: Option<crate::types::Angle>,
+    pub(crate) angle: Option<crate::types::Angle>,
 }
 
 /// Rotates children around the X axis by the given number of degrees.
@@ -51,6 +51,24 @@ impl<const DIMENSIONS: usize> ::core::fmt::Display for RotateX<DIMENSIONS> {
     }
 }
 
+impl<const DIMENSIONS: usize> crate::Node for RotateX<DIMENSIONS> {
+    fn name(&self) -> &str {
+        "rotate_x"
+    }
+
+    fn kind(&self) -> crate::NodeKind {
+        crate::NodeKind::Operator
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        let mut out: Vec<(&'static str, &dyn ::core::any::Any)> = Vec::new();
+        if let Some(value) = self.angle.as_ref() {
+            out.push(("angle", value));
+        }
+        out
+    }
+}
+
 impl<const DIMENSIONS: usize> crate::Operator<DIMENSIONS> for RotateX<DIMENSIONS> {
     type Output = crate::Object<DIMENSIONS>;
 
@@ -122,11 +140,11 @@ impl<const DIMENSIONS: usize> crate::IntoObject<DIMENSIONS> for RotateX<DIMENSIO
 }
 
 /// Rotates children around the X axis by the given number of degrees at a centerpoint.
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Default)]
 #[must_use = "Objects must be returned in order to be rendered"]
 pub struct RotateXAround<const DIMENSIONS: usize> {
-    angle: Option<crate::types::Angle>,
-    centerpoint: Option<crate::types::Length3>,
+    pub(crate) angle: Option<crate::types::Angle>,
+    pub(crate) centerpoint: Option<crate::types::Length3>,
 }
 
 #[inline]
@@ -191,6 +209,27 @@ impl<const DIMENSIONS: usize> ::core::fmt::Display for RotateXAround<DIMENSIONS>
     }
 }
 
+impl<const DIMENSIONS: usize> crate::Node for RotateXAround<DIMENSIONS> {
+    fn name(&self) -> &str {
+        "rotate_x_around"
+    }
+
+    fn kind(&self) -> crate::NodeKind {
+        crate::NodeKind::Operator
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        let mut out: Vec<(&'static str, &dyn ::core::any::Any)> = Vec::new();
+        if let Some(value) = self.angle.as_ref() {
+            out.push(("angle", value));
+        }
+        if let Some(value) = self.centerpoint.as_ref() {
+            out.push(("centerpoint", value));
+        }
+        out
+    }
+}
+
 impl<const DIMENSIONS: usize> crate::Operator<DIMENSIONS> for RotateXAround<DIMENSIONS> {
     type Output = crate::Object<DIMENSIONS>;
 
@@ -262,10 +301,10 @@ impl<const DIMENSIONS: usize> crate::IntoObject<DIMENSIONS> for RotateXAround<DI
 }
 
 /// Rotates children around the Y axis by the given number of degrees.
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Default)]
 #[must_use = "Objects must be returned in order to be rendered"]
 pub struct RotateY<const DIMENSIONS: usize> {
-    angle: Option<crate::types::Angle>,
+    pub(crate) angle: Option<crate::types::Angle>,
 }
 
 /// Rotates children around the Y axis by the given number of degrees.
@@ -314,6 +353,24 @@ impl<const DIMENSIONS: usize> ::core::fmt::Display for RotateY<DIMENSIONS> {
     }
 }
 
+impl<const DIMENSIONS: usize> crate::Node for RotateY<DIMENSIONS> {
+    fn name(&self) -> &str {
+        "rotate_y"
+    }
+
+    fn kind(&self) -> crate::NodeKind {
+        crate::NodeKind::Operator
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        let mut out: Vec<(&'static str, &dyn ::core::any::Any)> = Vec::new();
+        if let Some(value) = self.angle.as_ref() {
+            out.push(("angle", value));
+        }
+        out
+    }
+}
+
 impl<const DIMENSIONS: usize> crate::Operator<DIMENSIONS> for RotateY<DIMENSIONS> {
     type Output = crate::Object<DIMENSIONS>;
 
@@ -385,11 +442,11 @@ impl<const DIMENSIONS: usize> crate::IntoObject<DIMENSIONS> for RotateY<DIMENSIO
 }
 
 /// Rotates children around the Y axis by the given number of degrees at a centerpoint.
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Default)]
 #[must_use = "Objects must be returned in order to be rendered"]
 pub struct RotateYAround<const DIMENSIONS: usize> {
-    angle: Option<crate::types::Angle>,
-    centerpoint: Option<crate::types::Length3>,
+    pub(crate) angle: Option<crate::types::Angle>,
+    pub(crate) centerpoint: Option<crate::types::Length3>,
 }
 
 #[inline]
@@ -454,6 +511,27 @@ impl<const DIMENSIONS: usize> ::core::fmt::Display for RotateYAround<DIMENSIONS>
     }
 }
 
+impl<const DIMENSIONS: usize> crate::Node for RotateYAround<DIMENSIONS> {
+    fn name(&self) -> &str {
+        "rotate_y_around"
+    }
+
+    fn kind(&self) -> crate::NodeKind {
+        crate::NodeKind::Operator
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        let mut out: Vec<(&'static str, &dyn ::core::any::Any)> = Vec::new();
+        if let Some(value) = self.angle.as_ref() {
+            out.push(("angle", value));
+        }
+        if let Some(value) = self.centerpoint.as_ref() {
+            out.push(("centerpoint", value));
+        }
+        out
+    }
+}
+
 impl<const DIMENSIONS: usize> crate::Operator<DIMENSIONS> for RotateYAround<DIMENSIONS> {
     type Output = crate::Object<DIMENSIONS>;
 
@@ -525,10 +603,10 @@ impl<const DIMENSIONS: usize> crate::IntoObject<DIMENSIONS> for RotateYAround<DI
 }
 
 /// Rotates children around the Z axis by the given number of degrees.
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Default)]
 #[must_use = "Objects must be returned in order to be rendered"]
 pub struct RotateZ<const DIMENSIONS: usize> {
-    angle: Option<crate::types::Angle>,
+    pub(crate) angle: Option<crate::types::Angle>,
 }
 
 /// Rotates children around the Z axis by the given number of degrees.
@@ -577,6 +655,24 @@ impl<const DIMENSIONS: usize> ::core::fmt::Display for RotateZ<DIMENSIONS> {
     }
 }
 
+impl<const DIMENSIONS: usize> crate::Node for RotateZ<DIMENSIONS> {
+    fn name(&self) -> &str {
+        "rotate_z"
+    }
+
+    fn kind(&self) -> crate::NodeKind {
+        crate::NodeKind::Operator
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        let mut out: Vec<(&'static str, &dyn ::core::any::Any)> = Vec::new();
+        if let Some(value) = self.angle.as_ref() {
+            out.push(("angle", value));
+        }
+        out
+    }
+}
+
 impl<const DIMENSIONS: usize> crate::Operator<DIMENSIONS> for RotateZ<DIMENSIONS> {
     type Output = crate::Object<DIMENSIONS>;
 
@@ -648,11 +744,11 @@ impl<const DIMENSIONS: usize> crate::IntoObject<DIMENSIONS> for RotateZ<DIMENSIO
 }
 
 /// Rotates children around the Z axis by the given number of degrees at a centerpoint.
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Default)]
 #[must_use = "Objects must be returned in order to be rendered"]
 pub struct RotateZAround<const DIMENSIONS: usize> {
-    angle: Option<crate::types::Angle>,
-    centerpoint: Option<crate::types::Length3>,
+    pub(crate) angle: Option<crate::types::Angle>,
+    pub(crate) centerpoint: Option<crate::types::Length3>,
 }
 
 #[inline]
@@ -717,6 +813,27 @@ impl<const DIMENSIONS: usize> ::core::fmt::Display for RotateZAround<DIMENSIONS>
     }
 }
 
+impl<const DIMENSIONS: usize> crate::Node for RotateZAround<DIMENSIONS> {
+    fn name(&self) -> &str {
+        "rotate_z_around"
+    }
+
+    fn kind(&self) -> crate::NodeKind {
+        crate::NodeKind::Operator
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        let mut out: Vec<(&'static str, &dyn ::core::any::Any)> = Vec::new();
+        if let Some(value) = self.angle.as_ref() {
+            out.push(("angle", value));
+        }
+        if let Some(value) = self.centerpoint.as_ref() {
+            out.push(("centerpoint", value));
+        }
+        out
+    }
+}
+
 impl<const DIMENSIONS: usize> crate::Operator<DIMENSIONS> for RotateZAround<DIMENSIONS> {
     type Output = crate::Object<DIMENSIONS>;
 
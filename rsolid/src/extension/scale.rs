@@ -1,8 +1,8 @@
 /// Scales children by the given factor on the X axis.
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Default)]
 #[must_use = "Objects must be returned in order to be rendered"]
 pub struct ScaleX<const DIMENSIONS: usize> {
-    x: Option<crate::types::Scalar>,
+    pub(crate) x: Option<crate::types::Scalar>,
 }
 
 /// Scales children by the given factor on the X axis.
@@ -49,6 +49,24 @@ impl<const DIMENSIONS: usize> ::core::fmt::Display for ScaleX<DIMENSIONS> {
     }
 }
 
+impl<const DIMENSIONS: usize> crate::Node for ScaleX<DIMENSIONS> {
+    fn name(&self) -> &str {
+        "scale_x"
+    }
+
+    fn kind(&self) -> crate::NodeKind {
+        crate::NodeKind::Operator
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        let mut out: Vec<(&'static str, &dyn ::core::any::Any)> = Vec::new();
+        if let Some(value) = self.x.as_ref() {
+            out.push(("x", value));
+        }
+        out
+    }
+}
+
 impl<const DIMENSIONS: usize> crate::Operator<DIMENSIONS> for ScaleX<DIMENSIONS> {
     type Output = crate::Object<DIMENSIONS>;
 
@@ -120,10 +138,10 @@ impl<const DIMENSIONS: usize> crate::IntoObject<DIMENSIONS> for ScaleX<DIMENSION
 }
 
 /// Scales children by the given factor on the Y axis.
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Default)]
 #[must_use = "Objects must be returned in order to be rendered"]
 pub struct ScaleY<const DIMENSIONS: usize> {
-    y: Option<crate::types::Scalar>,
+    pub(crate) y: Option<crate::types::Scalar>,
 }
 
 /// Scales children by the given factor on the Y axis.
@@ -170,6 +188,24 @@ impl<const DIMENSIONS: usize> ::core::fmt::Display for ScaleY<DIMENSIONS> {
     }
 }
 
+impl<const DIMENSIONS: usize> crate::Node for ScaleY<DIMENSIONS> {
+    fn name(&self) -> &str {
+        "scale_y"
+    }
+
+    fn kind(&self) -> crate::NodeKind {
+        crate::NodeKind::Operator
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        let mut out: Vec<(&'static str, &dyn ::core::any::Any)> = Vec::new();
+        if let Some(value) = self.y.as_ref() {
+            out.push(("y", value));
+        }
+        out
+    }
+}
+
 impl<const DIMENSIONS: usize> crate::Operator<DIMENSIONS> for ScaleY<DIMENSIONS> {
     type Output = crate::Object<DIMENSIONS>;
 
@@ -241,10 +277,10 @@ impl<const DIMENSIONS: usize> crate::IntoObject<DIMENSIONS> for ScaleY<DIMENSION
 }
 
 /// Scales children by the given factor on the Z axis.
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Default)]
 #[must_use = "Objects must be returned in order to be rendered"]
 pub struct ScaleZ<const DIMENSIONS: usize> {
-    z: Option<crate::types::Scalar>,
+    pub(crate) z: Option<crate::types::Scalar>,
 }
 
 /// Scales children by the given factor on the Z axis.
@@ -291,6 +327,24 @@ impl<const DIMENSIONS: usize> ::core::fmt::Display for ScaleZ<DIMENSIONS> {
     }
 }
 
+impl<const DIMENSIONS: usize> crate::Node for ScaleZ<DIMENSIONS> {
+    fn name(&self) -> &str {
+        "scale_z"
+    }
+
+    fn kind(&self) -> crate::NodeKind {
+        crate::NodeKind::Operator
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        let mut out: Vec<(&'static str, &dyn ::core::any::Any)> = Vec::new();
+        if let Some(value) = self.z.as_ref() {
+            out.push(("z", value));
+        }
+        out
+    }
+}
+
 impl<const DIMENSIONS: usize> crate::Operator<DIMENSIONS> for ScaleZ<DIMENSIONS> {
     type Output = crate::Object<DIMENSIONS>;
 
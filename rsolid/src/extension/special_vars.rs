@@ -1,11 +1,11 @@
 /// The minimum angle for a fragment.
 ///
 /// Even a huge circle does not have more fragments than 360 divided by this number. The default value is 12 (i.e. 30 fragments for a full circle). The minimum allowed value is 0.01. Attempting to set a lower value causes a warning.
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Default)]
 #[must_use = "Objects must be returned in order to be rendered"]
 pub struct FragmentAngle<const DIMENSIONS: usize> {
-    angle: Option<crate::types::Angle>,
-    preview: Option<crate::types::Angle>,
+    pub(crate) angle: Option<crate::types::Angle>,
+    pub(crate) preview: Option<crate::types::Angle>,
 }
 
 #[inline]
@@ -71,6 +71,27 @@ impl<const DIMENSIONS: usize> ::core::fmt::Display for FragmentAngle<DIMENSIONS>
     }
 }
 
+impl<const DIMENSIONS: usize> crate::Node for FragmentAngle<DIMENSIONS> {
+    fn name(&self) -> &str {
+        "fragment_angle"
+    }
+
+    fn kind(&self) -> crate::NodeKind {
+        crate::NodeKind::Operator
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        let mut out: Vec<(&'static str, &dyn ::core::any::Any)> = Vec::new();
+        if let Some(value) = self.angle.as_ref() {
+            out.push(("angle", value));
+        }
+        if let Some(value) = self.preview.as_ref() {
+            out.push(("preview", value));
+        }
+        out
+    }
+}
+
 impl<const DIMENSIONS: usize> crate::Operator<DIMENSIONS> for FragmentAngle<DIMENSIONS> {
     type Output = crate::Object<DIMENSIONS>;
 
@@ -144,11 +165,11 @@ impl<const DIMENSIONS: usize> crate::IntoObject<DIMENSIONS> for FragmentAngle<DI
 /// The number of fragments and usually has the default value of 0.
 ///
 /// When this variable has a value greater than zero, the `fragment_angle` and `fragment_size` are ignored, and a full circle is rendered using this number of fragments.
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Default)]
 #[must_use = "Objects must be returned in order to be rendered"]
 pub struct FragmentCount<const DIMENSIONS: usize> {
-    count: Option<crate::types::Scalar>,
-    preview: Option<crate::types::Length>,
+    pub(crate) count: Option<crate::types::Scalar>,
+    pub(crate) preview: Option<crate::types::Length>,
 }
 
 #[inline]
@@ -214,6 +235,27 @@ impl<const DIMENSIONS: usize> ::core::fmt::Display for FragmentCount<DIMENSIONS>
     }
 }
 
+impl<const DIMENSIONS: usize> crate::Node for FragmentCount<DIMENSIONS> {
+    fn name(&self) -> &str {
+        "fragment_count"
+    }
+
+    fn kind(&self) -> crate::NodeKind {
+        crate::NodeKind::Operator
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        let mut out: Vec<(&'static str, &dyn ::core::any::Any)> = Vec::new();
+        if let Some(value) = self.count.as_ref() {
+            out.push(("count", value));
+        }
+        if let Some(value) = self.preview.as_ref() {
+            out.push(("preview", value));
+        }
+        out
+    }
+}
+
 impl<const DIMENSIONS: usize> crate::Operator<DIMENSIONS> for FragmentCount<DIMENSIONS> {
     type Output = crate::Object<DIMENSIONS>;
 
@@ -287,11 +329,11 @@ impl<const DIMENSIONS: usize> crate::IntoObject<DIMENSIONS> for FragmentCount<DI
 /// The minimum size of a fragment.
 ///
 /// The default value is 2 so very small circles have a smaller number of fragments than specified using `fragment_angle`. The minimum allowed value is 0.01. Attempting to set a lower value causes a warning.
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Default)]
 #[must_use = "Objects must be returned in order to be rendered"]
 pub struct FragmentSize<const DIMENSIONS: usize> {
-    preview: Option<crate::types::Length>,
-    size: Option<crate::types::Length>,
+    pub(crate) preview: Option<crate::types::Length>,
+    pub(crate) size: Option<crate::types::Length>,
 }
 
 #[inline]
@@ -357,6 +399,27 @@ impl<const DIMENSIONS: usize> ::core::fmt::Display for FragmentSize<DIMENSIONS>
     }
 }
 
+impl<const DIMENSIONS: usize> crate::Node for FragmentSize<DIMENSIONS> {
+    fn name(&self) -> &str {
+        "fragment_size"
+    }
+
+    fn kind(&self) -> crate::NodeKind {
+        crate::NodeKind::Operator
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        let mut out: Vec<(&'static str, &dyn ::core::any::Any)> = Vec::new();
+        if let Some(value) = self.preview.as_ref() {
+            out.push(("preview", value));
+        }
+        if let Some(value) = self.size.as_ref() {
+            out.push(("size", value));
+        }
+        out
+    }
+}
+
 impl<const DIMENSIONS: usize> crate::Operator<DIMENSIONS> for FragmentSize<DIMENSIONS> {
     type Output = crate::Object<DIMENSIONS>;
 
@@ -1,8 +1,8 @@
 /// Moves children back (in the Y- direction) by the given amount.
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Default)]
 #[must_use = "Objects must be returned in order to be rendered"]
 pub struct Back<const DIMENSIONS: usize> {
-    y: Option<crate::types::Length>,
+    pub(crate) y: Option<crate::types::Length>,
 }
 
 /// Moves children back (in the Y- direction) by the given amount.
@@ -49,6 +49,24 @@ impl<const DIMENSIONS: usize> ::core::fmt::Display for Back<DIMENSIONS> {
     }
 }
 
+impl<const DIMENSIONS: usize> crate::Node for Back<DIMENSIONS> {
+    fn name(&self) -> &str {
+        "back"
+    }
+
+    fn kind(&self) -> crate::NodeKind {
+        crate::NodeKind::Operator
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        let mut out: Vec<(&'static str, &dyn ::core::any::Any)> = Vec::new();
+        if let Some(value) = self.y.as_ref() {
+            out.push(("y", value));
+        }
+        out
+    }
+}
+
 impl<const DIMENSIONS: usize> crate::Operator<DIMENSIONS> for Back<DIMENSIONS> {
     type Output = crate::Object<DIMENSIONS>;
 
@@ -120,10 +138,10 @@ impl<const DIMENSIONS: usize> crate::IntoObject<DIMENSIONS> for Back<DIMENSIONS>
 }
 
 /// Moves children down (in the Z- direction) by the given amount.
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Default)]
 #[must_use = "Objects must be returned in order to be rendered"]
 pub struct Down<const DIMENSIONS: usize> {
-    z: Option<crate::types::Length>,
+    pub(crate) z: Option<crate::types::Length>,
 }
 
 /// Moves children down (in the Z- direction) by the given amount.
@@ -170,6 +188,24 @@ impl<const DIMENSIONS: usize> ::core::fmt::Display for Down<DIMENSIONS> {
     }
 }
 
+impl<const DIMENSIONS: usize> crate::Node for Down<DIMENSIONS> {
+    fn name(&self) -> &str {
+        "down"
+    }
+
+    fn kind(&self) -> crate::NodeKind {
+        crate::NodeKind::Operator
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        let mut out: Vec<(&'static str, &dyn ::core::any::Any)> = Vec::new();
+        if let Some(value) = self.z.as_ref() {
+            out.push(("z", value));
+        }
+        out
+    }
+}
+
 impl<const DIMENSIONS: usize> crate::Operator<DIMENSIONS> for Down<DIMENSIONS> {
     type Output = crate::Object<DIMENSIONS>;
 
@@ -241,10 +277,10 @@ impl<const DIMENSIONS: usize> crate::IntoObject<DIMENSIONS> for Down<DIMENSIONS>
 }
 
 /// Moves children forward (in the Y+ direction) by the given amount.
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Default)]
 #[must_use = "Objects must be returned in order to be rendered"]
 pub struct Fwd<const DIMENSIONS: usize> {
-    y: Option<crate::types::Length>,
+    pub(crate) y: Option<crate::types::Length>,
 }
 
 /// Moves children forward (in the Y+ direction) by the given amount.
@@ -291,6 +327,24 @@ impl<const DIMENSIONS: usize> ::core::fmt::Display for Fwd<DIMENSIONS> {
     }
 }
 
+impl<const DIMENSIONS: usize> crate::Node for Fwd<DIMENSIONS> {
+    fn name(&self) -> &str {
+        "fwd"
+    }
+
+    fn kind(&self) -> crate::NodeKind {
+        crate::NodeKind::Operator
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        let mut out: Vec<(&'static str, &dyn ::core::any::Any)> = Vec::new();
+        if let Some(value) = self.y.as_ref() {
+            out.push(("y", value));
+        }
+        out
+    }
+}
+
 impl<const DIMENSIONS: usize> crate::Operator<DIMENSIONS> for Fwd<DIMENSIONS> {
     type Output = crate::Object<DIMENSIONS>;
 
@@ -362,10 +416,10 @@ impl<const DIMENSIONS: usize> crate::IntoObject<DIMENSIONS> for Fwd<DIMENSIONS>
 }
 
 /// Moves children left (in the X- direction) by the given amount.
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Default)]
 #[must_use = "Objects must be returned in order to be rendered"]
 pub struct Left<const DIMENSIONS: usize> {
-    x: Option<crate::types::Length>,
+    pub(crate) x: Option<crate::types::Length>,
 }
 
 /// Moves children left (in the X- direction) by the given amount.
@@ -412,6 +466,24 @@ impl<const DIMENSIONS: usize> ::core::fmt::Display for Left<DIMENSIONS> {
     }
 }
 
+impl<const DIMENSIONS: usize> crate::Node for Left<DIMENSIONS> {
+    fn name(&self) -> &str {
+        "left"
+    }
+
+    fn kind(&self) -> crate::NodeKind {
+        crate::NodeKind::Operator
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        let mut out: Vec<(&'static str, &dyn ::core::any::Any)> = Vec::new();
+        if let Some(value) = self.x.as_ref() {
+            out.push(("x", value));
+        }
+        out
+    }
+}
+
 impl<const DIMENSIONS: usize> crate::Operator<DIMENSIONS> for Left<DIMENSIONS> {
     type Output = crate::Object<DIMENSIONS>;
 
@@ -483,10 +555,10 @@ impl<const DIMENSIONS: usize> crate::IntoObject<DIMENSIONS> for Left<DIMENSIONS>
 }
 
 /// Moves children right (in the X+ direction) by the given amount.
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Default)]
 #[must_use = "Objects must be returned in order to be rendered"]
 pub struct Right<const DIMENSIONS: usize> {
-    x: Option<crate::types::Length>,
+    pub(crate) x: Option<crate::types::Length>,
 }
 
 /// Moves children right (in the X+ direction) by the given amount.
@@ -533,6 +605,24 @@ impl<const DIMENSIONS: usize> ::core::fmt::Display for Right<DIMENSIONS> {
     }
 }
 
+impl<const DIMENSIONS: usize> crate::Node for Right<DIMENSIONS> {
+    fn name(&self) -> &str {
+        "right"
+    }
+
+    fn kind(&self) -> crate::NodeKind {
+        crate::NodeKind::Operator
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        let mut out: Vec<(&'static str, &dyn ::core::any::Any)> = Vec::new();
+        if let Some(value) = self.x.as_ref() {
+            out.push(("x", value));
+        }
+        out
+    }
+}
+
 impl<const DIMENSIONS: usize> crate::Operator<DIMENSIONS> for Right<DIMENSIONS> {
     type Output = crate::Object<DIMENSIONS>;
 
@@ -604,10 +694,10 @@ impl<const DIMENSIONS: usize> crate::IntoObject<DIMENSIONS> for Right<DIMENSIONS
 }
 
 /// Moves children up (in the Z+ direction) by the given amount.
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Default)]
 #[must_use = "Objects must be returned in order to be rendered"]
 pub struct Up<const DIMENSIONS: usize> {
-    z: Option<crate::types::Length>,
+    pub(crate) z: Option<crate::types::Length>,
 }
 
 /// Moves children up (in the Z+ direction) by the given amount.
@@ -654,6 +744,24 @@ impl<const DIMENSIONS: usize> ::core::fmt::Display for Up<DIMENSIONS> {
     }
 }
 
+impl<const DIMENSIONS: usize> crate::Node for Up<DIMENSIONS> {
+    fn name(&self) -> &str {
+        "up"
+    }
+
+    fn kind(&self) -> crate::NodeKind {
+        crate::NodeKind::Operator
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        let mut out: Vec<(&'static str, &dyn ::core::any::Any)> = Vec::new();
+        if let Some(value) = self.z.as_ref() {
+            out.push(("z", value));
+        }
+        out
+    }
+}
+
 impl<const DIMENSIONS: usize> crate::Operator<DIMENSIONS> for Up<DIMENSIONS> {
     type Output = crate::Object<DIMENSIONS>;
 
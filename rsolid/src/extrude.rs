@@ -0,0 +1,179 @@
+//! Checks for [`LinearExtrude`] parameters that OpenSCAD would reject or quietly ignore
+
+use crate::{
+    math::{num, Symbolic},
+    scad, Expr, LinearExtrude, LinearExtrudeScale,
+};
+use core::fmt;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExtrudeError {
+    NegativeHeight(f64),
+    NegativeScale(f64),
+    /// `slices` must be a whole number of at least 1
+    InvalidSlices(f64),
+    /// `segments` must be a whole number of at least 0
+    InvalidSegments(f64),
+}
+
+impl fmt::Display for ExtrudeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NegativeHeight(value) => write!(f, "extrusion height {value} is negative"),
+            Self::NegativeScale(value) => write!(f, "extrusion scale {value} is negative"),
+            Self::InvalidSlices(value) => {
+                write!(
+                    f,
+                    "extrusion slices {value} isn't a whole number of at least 1"
+                )
+            }
+            Self::InvalidSegments(value) => {
+                write!(
+                    f,
+                    "extrusion segments {value} isn't a whole number of at least 0"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ExtrudeError {}
+
+/// Parameters that OpenSCAD accepts, but which probably don't do what was meant
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExtrudeWarning {
+    /// The extrusion is empty
+    ZeroHeight,
+    /// A twist with a single slice only turns the top face, leaving the sides flat
+    TwistWithOneSlice(f64),
+}
+
+impl fmt::Display for ExtrudeWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::ZeroHeight => f.write_str("extrusion height is 0, so nothing is extruded"),
+            Self::TwistWithOneSlice(twist) => write!(
+                f,
+                "extrusion twists by {twist} degrees in a single slice, so the sides won't curve"
+            ),
+        }
+    }
+}
+
+impl LinearExtrude {
+    /// Checks the parameters, returning any warnings
+    ///
+    /// Only constant parameters are checked, since expressions like customizer variables are
+    /// only known to OpenSCAD. This is also run when the extrusion is exported, where the
+    /// problems become diagnostics, and by [`to_mesh`](crate::Object::to_mesh), which fails with
+    /// the error.
+    ///
+    /// ```
+    /// use rsolid::*;
+    ///
+    /// assert!(linear_extrude(-1).validate().is_err());
+    /// assert_eq!(linear_extrude(10).twist(90).slices(1).validate().unwrap().len(), 1);
+    /// ```
+    pub fn validate(&self) -> Result<Vec<ExtrudeWarning>, ExtrudeError> {
+        let mut warnings = vec![];
+
+        if let Some(height) = constant(self.height.as_ref().map(|v| &v.0)) {
+            if height < 0.0 {
+                return Err(ExtrudeError::NegativeHeight(height));
+            }
+            if height == 0.0 {
+                warnings.push(ExtrudeWarning::ZeroHeight);
+            }
+        }
+
+        if let Ok(scale) = scale(self) {
+            if let Some(value) = scale.into_iter().find(|value| *value < 0.0) {
+                return Err(ExtrudeError::NegativeScale(value));
+            }
+        }
+
+        let slices = constant(self.slices.as_ref().map(|v| &v.0));
+        if let Some(slices) = slices.filter(|slices| *slices < 1.0 || slices.fract() != 0.0) {
+            return Err(ExtrudeError::InvalidSlices(slices));
+        }
+        if let Some(segments) = constant(self.segments.as_ref().map(|v| &v.0))
+            .filter(|segments| *segments < 0.0 || segments.fract() != 0.0)
+        {
+            return Err(ExtrudeError::InvalidSegments(segments));
+        }
+
+        if let Some(twist) = constant(self.twist.as_ref().map(|v| &v.0)) {
+            if twist != 0.0 && slices == Some(1.0) {
+                warnings.push(ExtrudeWarning::TwistWithOneSlice(twist));
+            }
+        }
+
+        Ok(warnings)
+    }
+}
+
+/// Records the problems [`LinearExtrude::validate`] finds with the emitted code
+pub(crate) fn report(extrude: &LinearExtrude, f: &mut scad::Formatter) {
+    match extrude.validate() {
+        Ok(warnings) => {
+            for warning in warnings {
+                f.warn(warning);
+            }
+        }
+        Err(err) => f.warn(err),
+    }
+}
+
+fn constant(expr: Option<&Expr>) -> Option<f64> {
+    num(expr?).ok()
+}
+
+/// The scale of the top of the extrusion along X and Y
+pub(crate) fn scale(extrude: &LinearExtrude) -> Result<[f64; 2], Symbolic> {
+    match &extrude.scale {
+        Some(LinearExtrudeScale::Scalar2(v)) => Ok([num(&v.0[0].0)?, num(&v.0[1].0)?]),
+        Some(LinearExtrudeScale::Scalar(v)) => Ok([num(&v.0)?; 2]),
+        None => Ok([1.0; 2]),
+    }
+}
+
+#[test]
+fn validate_test() {
+    use crate::*;
+
+    assert_eq!(linear_extrude(10).validate(), Ok(vec![]));
+    assert_eq!(
+        linear_extrude(-2).validate(),
+        Err(ExtrudeError::NegativeHeight(-2.0))
+    );
+    assert_eq!(
+        linear_extrude(0).validate(),
+        Ok(vec![ExtrudeWarning::ZeroHeight])
+    );
+    assert_eq!(
+        linear_extrude(10).scale([1.0, -0.5]).validate(),
+        Err(ExtrudeError::NegativeScale(-0.5))
+    );
+    assert_eq!(
+        linear_extrude(10).slices(0).validate(),
+        Err(ExtrudeError::InvalidSlices(0.0))
+    );
+    assert_eq!(
+        linear_extrude(10).segments(2.5).validate(),
+        Err(ExtrudeError::InvalidSegments(2.5))
+    );
+    assert_eq!(
+        linear_extrude(10).twist(90).slices(1).validate(),
+        Ok(vec![ExtrudeWarning::TwistWithOneSlice(90.0)])
+    );
+
+    // customizer variables are left to OpenSCAD
+    let height = param::<Length>("height", 10).var();
+    assert_eq!(linear_extrude(&height).validate(), Ok(vec![]));
+
+    // exporting reports the problems, and bounds can't be worked out for invalid extrusions
+    use crate::scad::Scad;
+    let (_, warnings) = (square(1) >> linear_extrude(-1)).to_scad_with_warnings();
+    assert_eq!(warnings, ["extrusion height -1 is negative"]);
+    assert_eq!((square(1) >> linear_extrude(-1)).bounds(), None);
+}
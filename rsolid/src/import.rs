@@ -12,6 +12,18 @@ impl<const D: usize> scad::Scad for Import<D> {
     }
 }
 
+impl<const D: usize> Node for Import<D> {
+    #[inline]
+    fn name(&self) -> &str {
+        "import"
+    }
+
+    #[inline]
+    fn parameters(&self) -> Vec<(&'static str, &dyn core::any::Any)> {
+        vec![("file", &self.0)]
+    }
+}
+
 impl<const D: usize> fmt::Display for Import<D> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.write_str(&self.to_scad())
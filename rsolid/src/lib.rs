@@ -2,69 +2,84 @@
 #[macro_use]
 mod testing;
 
+pub mod anchor;
+pub mod animation;
 mod block;
 pub mod bosl;
+mod bounds;
+pub mod export;
+mod expr;
 mod ext;
 mod extension;
+mod extrude;
 mod helpers;
 pub mod import;
 pub mod mask;
+mod math;
+#[cfg(feature = "mesh")]
+pub mod mesh;
 mod object;
 mod operator;
 mod parameter;
+mod polyhedron;
 mod primitive;
+#[cfg(test)]
+mod projection;
+mod raw;
 pub mod scad;
 mod shape;
+mod tree;
 mod types;
-// TODO
-//mod var;
+mod var;
 
+pub use bounds::Aabb;
+pub use expr::{ternary, BinaryOp, Condition, Expr, UnaryOp, Value, Variable};
 pub use ext::*;
 pub use extension::*;
+pub use extrude::{ExtrudeError, ExtrudeWarning};
 pub use helpers::*;
 pub use import::*;
 pub use object::{IntoObject, Object};
 pub use operator::Operator;
+pub use polyhedron::PolyhedronError;
 pub use primitive::*;
+pub use raw::{raw, raw_operator, Raw, RawOperator};
 pub use shape::*;
+pub use tree::{AnyObject, Node, NodeKind};
 pub use types::*;
+pub use var::*;
 
-pub fn export<V: scad::Scad>(v: &V, path: &std::path::Path, renders: &[&str]) {
-    let out = v.to_scad();
+/// Writes `target/rsolid/<path>.scad` and renders it to each of the given formats
+///
+/// The formats have to fit the object's dimensions, like [`export::Mesh::ThreeMf`] for 3D
+/// objects, and PNGs with [`export::Png::snapshot`] settings give images that don't change:
+///
+/// ```no_run
+/// use rsolid::{export::*, *};
+///
+/// let part = cube(10) - sphere(6);
+/// export!(part, "part", &[&Mesh::StlAscii, &Png::snapshot()]);
+/// ```
+///
+/// # Panics
+///
+/// Panics if the files can't be written or if OpenSCAD fails. Use [`export::Exporter`] to
+/// handle errors instead.
+pub fn export<const DIMENSIONS: usize, V: IntoObject<DIMENSIONS> + Clone>(
+    v: &V,
+    path: &std::path::Path,
+    renders: &[&dyn export::Format<DIMENSIONS>],
+) {
+    eprintln!("rendering {}...", path.display());
 
-    let stem = std::path::Path::new("target/rsolid").join(path);
-    std::fs::create_dir_all(stem.parent().unwrap()).unwrap();
+    let out = export::Exporter::new()
+        .export(v, path, renders)
+        .unwrap_or_else(|err| panic!("{err}"));
+    for diagnostic in &out.diagnostics {
+        eprintln!("{diagnostic}");
+    }
 
-    let scad = stem.with_extension("scad");
-    eprintln!("rendering {}...", scad.display());
-    std::fs::write(&scad, out).unwrap();
     eprintln!("  done");
-
-    for ext in renders {
-        let out = stem.with_extension(ext);
-        eprintln!("rendering {}...", out.display());
-
-        let cmd = std::process::Command::new("openscad")
-            .arg("-o")
-            .arg("-")
-            .arg("--export-format")
-            .arg(ext)
-            .arg("--render")
-            .arg("true")
-            .arg(&scad)
-            .stderr(std::process::Stdio::inherit())
-            .stdout(std::process::Stdio::piped())
-            .spawn()
-            .unwrap()
-            .wait_with_output()
-            .unwrap();
-
-        assert!(cmd.status.success());
-
-        std::fs::write(out, cmd.stdout).unwrap();
-
-        println!("  done");
-    }
 }
 
 #[macro_export]
@@ -1,22 +1,20 @@
-use crate::*;
+use crate::{geometry::Path2, *};
 
 pub mod face;
 
-fn preview_padding(width: f64, height: f64) -> Object<2> {
-    let padding = 0.01;
-    let u = square([width, padding]).center(true) >> fwd(height / 2.0);
-    let o = square([padding, height]).center(true) >> right(width / 2.0);
-
-    // only show the padding in preview mode - the regular render doesn't have artifacts
-    (u + o) >> in_preview()
-}
+/// How far masks reach past the faces they cut into, so the faces don't line up in preview
+const OVERLAP: f64 = 0.01;
 
+/// What to take off a corner at the origin, with the object towards -X and -Y, to round it with
+/// the given radius
 pub fn fillet(rounding: f64) -> Object<2> {
-    let c = circle(rounding) >> back(rounding / 2.0) >> left(rounding / 2.0);
-    let s = square(rounding).center(true);
-    let padding = preview_padding(rounding, rounding);
-
-    (s - c + padding) >> back(rounding / 2.0) >> left(rounding / 2.0)
+    Path2::new([OVERLAP, OVERLAP])
+        .line_to([-rounding, OVERLAP])
+        .line_to([-rounding, 0.0])
+        .arc([-rounding, -rounding], -90.0)
+        .line_to([OVERLAP, -rounding])
+        .close()
+        .into_object()
 }
 
 #[test]
@@ -24,11 +22,16 @@ fn fillet_test() {
     assert_2d_snapshot!(fillet(100.0));
 }
 
+/// What to take off a corner at the origin, with the object towards -X and -Y, to bevel it
+/// `width` along X and `height` along Y
 pub fn chamfer(width: f64, height: f64) -> Object<2> {
-    let tri = triangle::right(width, height) >> rotate_z(180);
-    let padding = preview_padding(width, height);
-
-    (tri + padding) >> back(height / 2.0) >> left(width / 2.0)
+    Path2::new([OVERLAP, OVERLAP])
+        .line_to([-width, OVERLAP])
+        .line_to([-width, 0.0])
+        .line_to([0.0, -height])
+        .line_to([OVERLAP, -height])
+        .close()
+        .into_object()
 }
 
 #[test]
@@ -0,0 +1,466 @@
+//! Vector math and affine transforms for evaluating objects in Rust
+//!
+//! This is shared by [`bounds`](crate::bounds), the mesh evaluator and the shapes that are
+//! worked out in Rust. The helpers at the end read constant parameter values out of builders
+//! and turn transform operators into an [`Affine`], failing with [`Symbolic`] for values that
+//! are only known once OpenSCAD runs.
+
+use crate::{
+    expr::Expr,
+    extension::*,
+    primitive::*,
+    types::{Length, Length2, Length3},
+    Object,
+};
+use core::ops;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) struct Vec3 {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Vec3 {
+    #[inline]
+    pub const fn new(x: f64, y: f64, z: f64) -> Self {
+        Self { x, y, z }
+    }
+
+    #[inline]
+    pub fn dot(self, other: Self) -> f64 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    #[inline]
+    pub fn cross(self, other: Self) -> Self {
+        Self::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    #[inline]
+    pub fn length(self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    #[inline]
+    pub fn unit(self) -> Self {
+        let length = self.length();
+        if length == 0.0 {
+            self
+        } else {
+            self / length
+        }
+    }
+
+    #[cfg(feature = "mesh")]
+    #[inline]
+    pub fn lerp(self, other: Self, t: f64) -> Self {
+        self + (other - self) * t
+    }
+}
+
+impl From<[f64; 3]> for Vec3 {
+    #[inline]
+    fn from([x, y, z]: [f64; 3]) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+impl ops::Add for Vec3 {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl ops::Sub for Vec3 {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl ops::Mul<f64> for Vec3 {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, other: f64) -> Self {
+        Self::new(self.x * other, self.y * other, self.z * other)
+    }
+}
+
+impl ops::Div<f64> for Vec3 {
+    type Output = Self;
+
+    #[inline]
+    fn div(self, other: f64) -> Self {
+        Self::new(self.x / other, self.y / other, self.z / other)
+    }
+}
+
+impl ops::Neg for Vec3 {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y, -self.z)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) struct Vec2 {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Vec2 {
+    #[inline]
+    pub const fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+
+    #[inline]
+    pub fn dot(self, other: Self) -> f64 {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// The Z component of the 3D cross product, which is positive when `other` is
+    /// counter-clockwise from `self`
+    #[inline]
+    pub fn cross(self, other: Self) -> f64 {
+        self.x * other.y - self.y * other.x
+    }
+
+    #[inline]
+    pub fn length(self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    /// Turns the vector a quarter turn counter-clockwise
+    #[inline]
+    pub fn perp(self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    #[inline]
+    pub fn unit(self) -> Self {
+        let length = self.length();
+        if length == 0.0 {
+            self
+        } else {
+            self / length
+        }
+    }
+}
+
+impl From<[f64; 2]> for Vec2 {
+    #[inline]
+    fn from([x, y]: [f64; 2]) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl From<Vec2> for [f64; 2] {
+    #[inline]
+    fn from(v: Vec2) -> Self {
+        [v.x, v.y]
+    }
+}
+
+impl ops::Add for Vec2 {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl ops::Sub for Vec2 {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl ops::Mul<f64> for Vec2 {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, other: f64) -> Self {
+        Self::new(self.x * other, self.y * other)
+    }
+}
+
+impl ops::Div<f64> for Vec2 {
+    type Output = Self;
+
+    #[inline]
+    fn div(self, other: f64) -> Self {
+        Self::new(self.x / other, self.y / other)
+    }
+}
+
+impl ops::Neg for Vec2 {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+/// An affine transform, stored as the top three rows of a 4x4 matrix
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Affine(pub [[f64; 4]; 3]);
+
+impl Affine {
+    pub const IDENTITY: Self = Self([
+        [1.0, 0.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+    ]);
+
+    pub fn translate(v: Vec3) -> Self {
+        let mut m = Self::IDENTITY;
+        m.0[0][3] = v.x;
+        m.0[1][3] = v.y;
+        m.0[2][3] = v.z;
+        m
+    }
+
+    pub fn scale(v: Vec3) -> Self {
+        let mut m = Self::IDENTITY;
+        m.0[0][0] = v.x;
+        m.0[1][1] = v.y;
+        m.0[2][2] = v.z;
+        m
+    }
+
+    /// Rotates by `degrees` around `axis`, using Rodrigues' rotation formula
+    pub fn rotate_axis(axis: Vec3, degrees: f64) -> Self {
+        let len = axis.length();
+        if len == 0.0 {
+            return Self::IDENTITY;
+        }
+        let Vec3 { x, y, z } = axis / len;
+        let (s, c) = sin_cos_degrees(degrees);
+        let t = 1.0 - c;
+        Self([
+            [t * x * x + c, t * x * y - s * z, t * x * z + s * y, 0.0],
+            [t * x * y + s * z, t * y * y + c, t * y * z - s * x, 0.0],
+            [t * x * z - s * y, t * y * z + s * x, t * z * z + c, 0.0],
+        ])
+    }
+
+    /// Rotates around X, then Y, then Z, like `rotate([x, y, z])`
+    pub fn rotate_xyz(v: Vec3) -> Self {
+        Self::rotate_axis(Vec3::new(0.0, 0.0, 1.0), v.z)
+            * Self::rotate_axis(Vec3::new(0.0, 1.0, 0.0), v.y)
+            * Self::rotate_axis(Vec3::new(1.0, 0.0, 0.0), v.x)
+    }
+
+    pub fn mirror(normal: Vec3) -> Self {
+        let len = normal.length();
+        if len == 0.0 {
+            return Self::IDENTITY;
+        }
+        let n = normal / len;
+        let n = [n.x, n.y, n.z];
+        let mut m = Self::IDENTITY;
+        for (row, out) in m.0.iter_mut().enumerate() {
+            for col in 0..3 {
+                out[col] -= 2.0 * n[row] * n[col];
+            }
+        }
+        m
+    }
+
+    pub fn apply(&self, v: Vec3) -> Vec3 {
+        let [x, y, z] = self
+            .0
+            .map(|row| row[0] * v.x + row[1] * v.y + row[2] * v.z + row[3]);
+        Vec3::new(x, y, z)
+    }
+}
+
+impl ops::Mul for Affine {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        let (a, b) = (&self.0, &other.0);
+        Self(core::array::from_fn(|row| {
+            core::array::from_fn(|col| {
+                let sum = (0..3).map(|k| a[row][k] * b[k][col]).sum::<f64>();
+                if col == 3 {
+                    sum + a[row][3]
+                } else {
+                    sum
+                }
+            })
+        }))
+    }
+}
+
+/// Like `f64::sin_cos` but in degrees, and exact for multiples of 90 degrees like OpenSCAD
+pub(crate) fn sin_cos_degrees(degrees: f64) -> (f64, f64) {
+    let degrees = degrees.rem_euclid(360.0);
+    match degrees {
+        0.0 => (0.0, 1.0),
+        90.0 => (1.0, 0.0),
+        180.0 => (0.0, -1.0),
+        270.0 => (-1.0, 0.0),
+        _ => degrees.to_radians().sin_cos(),
+    }
+}
+
+/// Rounds away floating point noise from worked out values, so the emitted numbers stay readable
+pub(crate) fn round(v: f64) -> f64 {
+    let v = (v * 1e9).round() / 1e9;
+    v + 0.0
+}
+
+/// A parameter that is a symbolic expression, like a customizer parameter, instead of a constant
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Symbolic(pub String);
+
+pub(crate) fn num(expr: &Expr) -> Result<f64, Symbolic> {
+    expr.as_const().ok_or_else(|| Symbolic(expr.to_string()))
+}
+
+pub(crate) fn vec2(v: &Length2) -> Result<[f64; 2], Symbolic> {
+    Ok([num(&v.0[0].0)?, num(&v.0[1].0)?])
+}
+
+pub(crate) fn vec3(v: &Length3) -> Result<Vec3, Symbolic> {
+    Ok(Vec3::new(num(&v.0[0].0)?, num(&v.0[1].0)?, num(&v.0[2].0)?))
+}
+
+pub(crate) fn length(v: &Option<Length>, default: f64) -> Result<f64, Symbolic> {
+    v.as_ref().map_or(Ok(default), |v| num(&v.0))
+}
+
+/// Returns the transform applied by an operator, or `None` if it isn't an affine transform
+///
+/// `resize` isn't included, since it depends on the size of the children.
+pub(crate) fn transform<const DIMENSIONS: usize>(
+    parent: &Object<DIMENSIONS>,
+) -> Result<Option<Affine>, Symbolic> {
+    macro_rules! translate_by {
+        ($ty:ident, $field:ident, $v:expr) => {
+            if let Some(op) = parent.downcast_ref::<$ty<DIMENSIONS>>() {
+                let $field = length(&op.$field, 0.0)?;
+                return Ok(Some(Affine::translate($v)));
+            }
+        };
+    }
+
+    macro_rules! rotate_by {
+        ($ty:ident, $around:ident, $axis:expr) => {
+            if let Some(op) = parent.downcast_ref::<$ty<DIMENSIONS>>() {
+                let a = op.angle.as_ref().map_or(Ok(0.0), |a| num(&a.0))?;
+                return Ok(Some(Affine::rotate_axis($axis, a)));
+            }
+
+            if let Some(op) = parent.downcast_ref::<$around<DIMENSIONS>>() {
+                let a = op.angle.as_ref().map_or(Ok(0.0), |a| num(&a.0))?;
+                let rotate = Affine::rotate_axis($axis, a);
+                return Ok(Some(match &op.centerpoint {
+                    Some(cp) => {
+                        let cp = vec3(cp)?;
+                        Affine::translate(cp) * rotate * Affine::translate(-cp)
+                    }
+                    None => rotate,
+                }));
+            }
+        };
+    }
+
+    macro_rules! scale_by {
+        ($ty:ident, $field:ident, $v:expr) => {
+            if let Some(op) = parent.downcast_ref::<$ty<DIMENSIONS>>() {
+                let $field = op.$field.as_ref().map_or(Ok(1.0), |v| num(&v.0))?;
+                return Ok(Some(Affine::scale($v)));
+            }
+        };
+    }
+
+    macro_rules! mirror_by {
+        ($ty:ident, $normal:expr) => {
+            if parent.downcast_ref::<$ty<DIMENSIONS>>().is_some() {
+                return Ok(Some(Affine::mirror($normal)));
+            }
+        };
+    }
+
+    if let Some(op) = parent.downcast_ref::<Translate<DIMENSIONS>>() {
+        let v = op.value.as_ref().map_or(Ok(Vec3::default()), vec3)?;
+        return Ok(Some(Affine::translate(v)));
+    }
+
+    if let Some(op) = parent.downcast_ref::<Rotate<DIMENSIONS>>() {
+        return Ok(Some(match &op.angle {
+            Some(RotateAngle::Angle3(a)) => {
+                Affine::rotate_xyz(Vec3::new(num(&a.0[0].0)?, num(&a.0[1].0)?, num(&a.0[2].0)?))
+            }
+            Some(RotateAngle::Angle(a)) => {
+                let axis = match &op.value {
+                    Some(v) => vec3(v)?,
+                    None => Vec3::new(0.0, 0.0, 1.0),
+                };
+                Affine::rotate_axis(axis, num(&a.0)?)
+            }
+            None => Affine::IDENTITY,
+        }));
+    }
+
+    if let Some(op) = parent.downcast_ref::<Scale<DIMENSIONS>>() {
+        let v = match &op.value {
+            Some(ScaleValue::Scalar3(v)) => {
+                Vec3::new(num(&v.0[0].0)?, num(&v.0[1].0)?, num(&v.0[2].0)?)
+            }
+            Some(ScaleValue::Scalar(v)) => {
+                let v = num(&v.0)?;
+                Vec3::new(v, v, v)
+            }
+            None => Vec3::new(1.0, 1.0, 1.0),
+        };
+        return Ok(Some(Affine::scale(v)));
+    }
+
+    if let Some(op) = parent.downcast_ref::<Mirror<DIMENSIONS>>() {
+        let v = op.vector.as_ref().map_or(Ok(Vec3::default()), vec3)?;
+        return Ok(Some(Affine::mirror(v)));
+    }
+
+    translate_by!(Left, x, Vec3::new(-x, 0.0, 0.0));
+    translate_by!(Right, x, Vec3::new(x, 0.0, 0.0));
+    translate_by!(Fwd, y, Vec3::new(0.0, -y, 0.0));
+    translate_by!(Back, y, Vec3::new(0.0, y, 0.0));
+    translate_by!(Down, z, Vec3::new(0.0, 0.0, -z));
+    translate_by!(Up, z, Vec3::new(0.0, 0.0, z));
+
+    rotate_by!(RotateX, RotateXAround, Vec3::new(1.0, 0.0, 0.0));
+    rotate_by!(RotateY, RotateYAround, Vec3::new(0.0, 1.0, 0.0));
+    rotate_by!(RotateZ, RotateZAround, Vec3::new(0.0, 0.0, 1.0));
+
+    scale_by!(ScaleX, x, Vec3::new(x, 1.0, 1.0));
+    scale_by!(ScaleY, y, Vec3::new(1.0, y, 1.0));
+    scale_by!(ScaleZ, z, Vec3::new(1.0, 1.0, z));
+
+    mirror_by!(MirrorX, Vec3::new(1.0, 0.0, 0.0));
+    mirror_by!(MirrorY, Vec3::new(0.0, 1.0, 0.0));
+    mirror_by!(MirrorZ, Vec3::new(0.0, 0.0, 1.0));
+
+    Ok(None)
+}
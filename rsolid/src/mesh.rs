@@ -0,0 +1,281 @@
+//! Native evaluation of objects into triangle meshes, without needing OpenSCAD
+//!
+//! This covers the core primitives, extrusions, affine transforms and booleans. Objects
+//! that can't be evaluated, like imports or BOSL modules, produce a [`MeshError`] so the
+//! model can still be rendered with OpenSCAD instead.
+//!
+//! ```
+//! use rsolid::*;
+//!
+//! let part = cube(10) - cylinder(12, 3).right(5).back(5).down(1);
+//! let mesh = part.to_mesh().unwrap();
+//! assert!(mesh.volume() < 1000.0);
+//! ```
+
+use crate::{math::Symbolic, Object};
+use core::fmt;
+use std::{collections::HashMap, path::Path};
+
+mod csg;
+mod eval;
+
+pub(crate) use crate::math::Vec3;
+
+/// An indexed triangle mesh with counter-clockwise winding when viewed from outside
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Mesh {
+    pub vertices: Vec<[f64; 3]>,
+    pub triangles: Vec<[usize; 3]>,
+}
+
+impl Mesh {
+    fn from_polygons(polygons: &[csg::Polygon]) -> Self {
+        let mut mesh = Self::default();
+        let mut indices = HashMap::new();
+
+        let mut index = |v: Vec3, mesh: &mut Self| {
+            *indices.entry(key(v)).or_insert_with(|| {
+                mesh.vertices.push([v.x, v.y, v.z]);
+                mesh.vertices.len() - 1
+            })
+        };
+
+        for polygon in polygons {
+            // the polygons are convex, so a fan covers them
+            let first = index(polygon.vertices[0], &mut mesh);
+            for pair in polygon.vertices[1..].windows(2) {
+                let b = index(pair[0], &mut mesh);
+                let c = index(pair[1], &mut mesh);
+                if first != b && b != c && c != first {
+                    mesh.triangles.push([first, b, c]);
+                }
+            }
+        }
+
+        mesh
+    }
+
+    /// Returns the enclosed volume, which is negative if the mesh is inside out
+    pub fn volume(&self) -> f64 {
+        self.triangles
+            .iter()
+            .map(|[a, b, c]| {
+                let [a, b, c] = [a, b, c].map(|idx| Vec3::from(self.vertices[*idx]));
+                a.dot(b.cross(c)) / 6.0
+            })
+            .sum()
+    }
+
+    /// Returns the smallest and largest corners of the mesh, if it isn't empty
+    pub fn bounds(&self) -> Option<([f64; 3], [f64; 3])> {
+        let mut points = self.vertices.iter();
+        let first = *points.next()?;
+        Some(points.fold((first, first), |(mut min, mut max), p| {
+            for axis in 0..3 {
+                min[axis] = min[axis].min(p[axis]);
+                max[axis] = max[axis].max(p[axis]);
+            }
+            (min, max)
+        }))
+    }
+
+    /// Formats the mesh as an ASCII STL file
+    pub fn to_stl(&self, name: &str) -> String {
+        use fmt::Write;
+
+        let mut out = String::new();
+        let _ = writeln!(out, "solid {name}");
+        for tri in &self.triangles {
+            let [a, b, c] = tri.map(|idx| Vec3::from(self.vertices[idx]));
+            let n = (b - a).cross(c - a);
+            let n = n / n.length().max(f64::MIN_POSITIVE);
+            let _ = writeln!(out, "  facet normal {} {} {}", n.x, n.y, n.z);
+            let _ = writeln!(out, "    outer loop");
+            for v in [a, b, c] {
+                let _ = writeln!(out, "      vertex {} {} {}", v.x, v.y, v.z);
+            }
+            let _ = writeln!(out, "    endloop");
+            let _ = writeln!(out, "  endfacet");
+        }
+        let _ = writeln!(out, "endsolid {name}");
+        out
+    }
+
+    /// Writes the mesh as an ASCII STL file
+    pub fn write_stl(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let path = path.as_ref();
+        let name = path
+            .file_stem()
+            .map_or("rsolid".into(), |stem| stem.to_string_lossy());
+        std::fs::write(path, self.to_stl(&name))
+    }
+}
+
+impl Object<3> {
+    /// Evaluates the object into a triangle mesh
+    #[inline]
+    pub fn to_mesh(&self) -> Result<Mesh, MeshError> {
+        let polygons = eval::solid(self, &eval::Context::default())?;
+        Ok(Mesh::from_polygons(&polygons))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MeshError {
+    /// The object has no native implementation
+    Unsupported(String),
+    /// A parameter is a symbolic expression, like a customizer parameter, instead of a constant
+    Symbolic(String),
+    /// The parameters don't describe a valid shape
+    Invalid(String),
+}
+
+impl fmt::Display for MeshError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Unsupported(object) => write!(f, "{object} can't be evaluated natively"),
+            Self::Symbolic(expr) => write!(f, "{expr} is not a constant"),
+            Self::Invalid(reason) => f.write_str(reason),
+        }
+    }
+}
+
+impl std::error::Error for MeshError {}
+
+impl From<Symbolic> for MeshError {
+    #[inline]
+    fn from(Symbolic(expr): Symbolic) -> Self {
+        Self::Symbolic(expr)
+    }
+}
+
+/// A hashable key for welding vertices, which treats `0.0` and `-0.0` as the same
+#[inline]
+fn key(v: Vec3) -> [u64; 3] {
+    [v.x, v.y, v.z].map(|v| (v + 0.0).to_bits())
+}
+
+#[cfg(test)]
+fn assert_volume(object: impl crate::IntoObject<3>, expected: f64) {
+    let mesh = object.into_object().to_mesh().unwrap();
+    let volume = mesh.volume();
+    assert!(
+        (volume - expected).abs() < expected * 1e-6,
+        "expected a volume of {expected}, got {volume}"
+    );
+}
+
+#[test]
+fn primitive_test() {
+    use crate::*;
+
+    assert_volume(cube([10, 20, 30]), 6000.0);
+    assert_volume(cube(10).center(true) >> mirror([1, 1, 0]), 1000.0);
+    assert_volume(cylinder(10, 1) >> fragment_count(4), 20.0);
+    assert_volume(
+        polyhedron(
+            [[0, 0, 0], [1, 0, 0], [0, 1, 0], [0, 0, 1]],
+            [[0, 1, 2], [0, 3, 1], [0, 2, 3], [1, 3, 2]],
+        ),
+        1.0 / 6.0,
+    );
+
+    // an L shaped prism, whose concave ends can't be split into a fan from their first point
+    let l = [[0, 0], [2, 0], [2, 1], [1, 1], [1, 2], [0, 2]];
+    let points: Vec<_> = [0, 1]
+        .into_iter()
+        .flat_map(|z| l.map(|[x, y]| [x, y, z]))
+        .collect();
+    let mut faces = vec![vec![0, 1, 2, 3, 4, 5], vec![11, 10, 9, 8, 7, 6]];
+    faces.extend((0..6).map(|i| vec![i, i + 6, (i + 1) % 6 + 6, (i + 1) % 6]));
+    let prism = polyhedron(points, faces).into_object();
+    let mesh = prism.clone().to_mesh().unwrap();
+    for triangle in &mesh.triangles {
+        let [a, b, c] = triangle.map(|idx| Vec3::from(mesh.vertices[idx]));
+        if a.z == b.z && b.z == c.z {
+            // the ends face down and up, so triangles folded over the notch face the wrong way
+            let normal = (b - a).cross(c - a);
+            assert_eq!(
+                normal.z > 0.0,
+                a.z > 0.0,
+                "{triangle:?} faces the wrong way"
+            );
+        }
+    }
+    assert_volume(prism, 3.0);
+
+    let sphere = sphere(10).into_object().to_mesh().unwrap().volume();
+    assert!(sphere > 3000.0 && sphere < 4189.0, "{sphere}");
+
+    insta::assert_snapshot!(cube(1).into_object().to_mesh().unwrap().to_stl("cube"));
+
+    // `$fa` and `$fs` are raised to 0.01, so a unit circle gets 2π / 0.01 fragments
+    let fine = circle(1).fragment_angle(0).fragment_size(0) >> linear_extrude(1);
+    assert_eq!(fine.to_mesh().unwrap().vertices.len(), 2 * 629);
+}
+
+#[test]
+fn csg_test() {
+    use crate::*;
+
+    assert_volume(cube(10) + cube(10).right(5), 1500.0);
+    assert_volume(cube(10) + cube(10).right(20), 2000.0);
+    assert_volume(cube(10) - cube(10).right(5), 500.0);
+    assert_volume(cube(10).intersection(cube(10).right(5).back(5)), 250.0);
+    assert_volume(cube(10).intersection(cube(10).right(20)) + cube(1), 1.0);
+    assert_volume(
+        cube(10) - cube([2, 2, 20]).right(4).back(4).down(5),
+        1000.0 - 40.0,
+    );
+}
+
+#[test]
+fn extrude_test() {
+    use crate::*;
+
+    assert_volume(square(10) >> linear_extrude(5), 500.0);
+
+    let twisted =
+        square([10, 4]).center(true) >> linear_extrude(5).twist(90) >> fragment_count(400);
+    let twisted = twisted.to_mesh().unwrap().volume();
+    assert!((twisted - 200.0).abs() < 5.0, "{twisted}");
+    assert_volume((square(10) - square(4)) >> linear_extrude(1), 84.0);
+
+    // a square frustum and pyramid
+    assert_volume(square(2).center(true) >> linear_extrude(3).scale(0.5), 7.0);
+    assert_volume(
+        square(2).center(true) >> linear_extrude(3).scale(0).segments(12),
+        4.0,
+    );
+    // a wedge, with the sides split into triangles
+    assert_volume(square(2) >> linear_extrude(3).scale([1, 0]), 6.0);
+    let twisted = square([10, 4]).center(true) >> linear_extrude(5).twist(90).slices(100);
+    let twisted = twisted.to_mesh().unwrap().volume();
+    assert!((twisted - 200.0).abs() < 5.0, "{twisted}");
+
+    // a square ring around the Z axis, with 4 fragments
+    let ring = square(2).right(1) >> rotate_extrude().fragment_resolution(4);
+    assert_volume(ring, (18.0 - 2.0) * 2.0);
+
+    let half = square(2).right(1) >> rotate_extrude().angle(-180).fragment_resolution(4);
+    assert_volume(half, 16.0);
+}
+
+#[test]
+fn error_test() {
+    use crate::*;
+
+    let size = param::<Length>("size", 10).var();
+    assert_eq!(
+        cube(size).into_object().to_mesh(),
+        Err(MeshError::Symbolic("size".into()))
+    );
+    assert_eq!(
+        (cube(10) >> hull()).to_mesh(),
+        Err(MeshError::Unsupported("hull".into()))
+    );
+    assert_eq!(
+        (square(1) >> linear_extrude(-1)).to_mesh(),
+        Err(MeshError::Invalid("extrusion height -1 is negative".into()))
+    );
+}
@@ -0,0 +1,289 @@
+//! Boolean operations on polygon soups using BSP trees, after the approach from csg.js
+
+use super::Vec3;
+
+/// Tolerance used to decide whether a point lies on a plane
+const EPSILON: f64 = 1e-7;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Plane {
+    normal: Vec3,
+    w: f64,
+}
+
+impl Plane {
+    fn from_points(a: Vec3, b: Vec3, c: Vec3) -> Option<Self> {
+        let normal = (b - a).cross(c - a);
+        let len = normal.length();
+        if len < EPSILON * EPSILON {
+            return None;
+        }
+        let normal = normal / len;
+        Some(Self {
+            normal,
+            w: normal.dot(a),
+        })
+    }
+
+    fn flip(&mut self) {
+        self.normal = -self.normal;
+        self.w = -self.w;
+    }
+
+    /// Splits the polygon by this plane, sorting the pieces into the given lists
+    fn split(
+        &self,
+        polygon: Polygon,
+        coplanar_front: &mut Vec<Polygon>,
+        coplanar_back: &mut Vec<Polygon>,
+        front: &mut Vec<Polygon>,
+        back: &mut Vec<Polygon>,
+    ) {
+        const COPLANAR: u8 = 0;
+        const FRONT: u8 = 1;
+        const BACK: u8 = 2;
+        const SPANNING: u8 = 3;
+
+        let mut kind = COPLANAR;
+        let types: Vec<u8> = polygon
+            .vertices
+            .iter()
+            .map(|v| {
+                let t = self.normal.dot(*v) - self.w;
+                let ty = if t < -EPSILON {
+                    BACK
+                } else if t > EPSILON {
+                    FRONT
+                } else {
+                    COPLANAR
+                };
+                kind |= ty;
+                ty
+            })
+            .collect();
+
+        match kind {
+            COPLANAR => {
+                if self.normal.dot(polygon.plane.normal) > 0.0 {
+                    coplanar_front.push(polygon);
+                } else {
+                    coplanar_back.push(polygon);
+                }
+            }
+            FRONT => front.push(polygon),
+            BACK => back.push(polygon),
+            _ => {
+                let mut f = vec![];
+                let mut b = vec![];
+                let count = polygon.vertices.len();
+                for i in 0..count {
+                    let j = (i + 1) % count;
+                    let (ti, tj) = (types[i], types[j]);
+                    let (vi, vj) = (polygon.vertices[i], polygon.vertices[j]);
+                    if ti != BACK {
+                        f.push(vi);
+                    }
+                    if ti != FRONT {
+                        b.push(vi);
+                    }
+                    if (ti | tj) == SPANNING {
+                        let t = (self.w - self.normal.dot(vi)) / self.normal.dot(vj - vi);
+                        let v = vi.lerp(vj, t);
+                        f.push(v);
+                        b.push(v);
+                    }
+                }
+                if f.len() >= 3 {
+                    front.push(Polygon {
+                        vertices: f,
+                        plane: polygon.plane,
+                    });
+                }
+                if b.len() >= 3 {
+                    back.push(Polygon {
+                        vertices: b,
+                        plane: polygon.plane,
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// A convex, planar polygon with counter-clockwise winding when viewed from outside
+#[derive(Clone, Debug)]
+pub(crate) struct Polygon {
+    pub vertices: Vec<Vec3>,
+    plane: Plane,
+}
+
+impl Polygon {
+    /// Returns `None` for degenerate polygons
+    pub fn new(mut vertices: Vec<Vec3>) -> Option<Self> {
+        vertices.dedup();
+        while vertices.len() > 1 && vertices.first() == vertices.last() {
+            vertices.pop();
+        }
+        if vertices.len() < 3 {
+            return None;
+        }
+
+        // find the first three points that aren't collinear
+        let a = vertices[0];
+        let plane = vertices
+            .windows(2)
+            .skip(1)
+            .find_map(|w| Plane::from_points(a, w[0], w[1]))?;
+
+        Some(Self { vertices, plane })
+    }
+
+    fn flip(&mut self) {
+        self.vertices.reverse();
+        self.plane.flip();
+    }
+}
+
+#[derive(Debug, Default)]
+struct Node {
+    plane: Option<Plane>,
+    front: Option<Box<Node>>,
+    back: Option<Box<Node>>,
+    polygons: Vec<Polygon>,
+}
+
+impl Node {
+    fn new(polygons: Vec<Polygon>) -> Self {
+        let mut node = Self::default();
+        node.build(polygons);
+        node
+    }
+
+    /// Converts solid space to empty space and empty space to solid space
+    fn invert(&mut self) {
+        for polygon in &mut self.polygons {
+            polygon.flip();
+        }
+        if let Some(plane) = &mut self.plane {
+            plane.flip();
+        }
+        if let Some(front) = &mut self.front {
+            front.invert();
+        }
+        if let Some(back) = &mut self.back {
+            back.invert();
+        }
+        core::mem::swap(&mut self.front, &mut self.back);
+    }
+
+    /// Removes all of the parts of the polygons that are inside of this tree
+    fn clip_polygons(&self, polygons: Vec<Polygon>) -> Vec<Polygon> {
+        let Some(plane) = &self.plane else {
+            return polygons;
+        };
+
+        let mut front = vec![];
+        let mut back = vec![];
+        for polygon in polygons {
+            let (mut cf, mut cb) = (vec![], vec![]);
+            plane.split(polygon, &mut cf, &mut cb, &mut front, &mut back);
+            front.append(&mut cf);
+            back.append(&mut cb);
+        }
+
+        let mut front = match &self.front {
+            Some(node) => node.clip_polygons(front),
+            None => front,
+        };
+        let back = match &self.back {
+            Some(node) => node.clip_polygons(back),
+            None => vec![],
+        };
+        front.extend(back);
+        front
+    }
+
+    /// Removes all of the parts of this tree's polygons that are inside of the other tree
+    fn clip_to(&mut self, other: &Node) {
+        self.polygons = other.clip_polygons(core::mem::take(&mut self.polygons));
+        if let Some(front) = &mut self.front {
+            front.clip_to(other);
+        }
+        if let Some(back) = &mut self.back {
+            back.clip_to(other);
+        }
+    }
+
+    fn all_polygons(&self) -> Vec<Polygon> {
+        let mut out = self.polygons.clone();
+        if let Some(front) = &self.front {
+            out.extend(front.all_polygons());
+        }
+        if let Some(back) = &self.back {
+            out.extend(back.all_polygons());
+        }
+        out
+    }
+
+    fn build(&mut self, polygons: Vec<Polygon>) {
+        if polygons.is_empty() {
+            return;
+        }
+
+        let plane = *self.plane.get_or_insert(polygons[0].plane);
+        let mut front = vec![];
+        let mut back = vec![];
+        for polygon in polygons {
+            let (mut cf, mut cb) = (vec![], vec![]);
+            plane.split(polygon, &mut cf, &mut cb, &mut front, &mut back);
+            self.polygons.append(&mut cf);
+            self.polygons.append(&mut cb);
+        }
+
+        if !front.is_empty() {
+            self.front.get_or_insert_with(Default::default).build(front);
+        }
+        if !back.is_empty() {
+            self.back.get_or_insert_with(Default::default).build(back);
+        }
+    }
+}
+
+pub(crate) fn union(a: Vec<Polygon>, b: Vec<Polygon>) -> Vec<Polygon> {
+    let mut a = Node::new(a);
+    let mut b = Node::new(b);
+    a.clip_to(&b);
+    b.clip_to(&a);
+    b.invert();
+    b.clip_to(&a);
+    b.invert();
+    a.build(b.all_polygons());
+    a.all_polygons()
+}
+
+pub(crate) fn difference(a: Vec<Polygon>, b: Vec<Polygon>) -> Vec<Polygon> {
+    let mut a = Node::new(a);
+    let mut b = Node::new(b);
+    a.invert();
+    a.clip_to(&b);
+    b.clip_to(&a);
+    b.invert();
+    b.clip_to(&a);
+    b.invert();
+    a.build(b.all_polygons());
+    a.invert();
+    a.all_polygons()
+}
+
+pub(crate) fn intersection(a: Vec<Polygon>, b: Vec<Polygon>) -> Vec<Polygon> {
+    let mut a = Node::new(a);
+    let mut b = Node::new(b);
+    a.invert();
+    b.clip_to(&a);
+    b.invert();
+    a.clip_to(&b);
+    b.clip_to(&a);
+    a.build(b.all_polygons());
+    a.invert();
+    a.all_polygons()
+}
@@ -0,0 +1,842 @@
+//! Walks the object tree and evaluates it into polygons
+//!
+//! Missing parameters fall back to OpenSCAD's builtin defaults, since those are what the
+//! emitted code would use.
+
+use super::{
+    csg::{self, Polygon},
+    MeshError, Vec3,
+};
+use crate::{
+    block::{Block, Boolean, BooleanKind},
+    extension::*,
+    math::{self, length, num, vec2, vec3, Affine},
+    operator::Wrapped,
+    primitive::*,
+    types::{FragmentResolution, Length},
+    Object,
+};
+use core::f64::consts::TAU;
+
+type Result<T, E = MeshError> = core::result::Result<T, E>;
+
+type Solid = Vec<Polygon>;
+
+/// The special variables that control the resolution of curved shapes
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Context {
+    fragment_count: f64,
+    fragment_angle: f64,
+    fragment_size: f64,
+}
+
+impl Default for Context {
+    fn default() -> Self {
+        Self {
+            fragment_count: 0.0,
+            fragment_angle: 12.0,
+            fragment_size: 2.0,
+        }
+    }
+}
+
+impl Context {
+    /// Applies the per-object overrides, like `circle(r, $fn=6)`
+    fn with(
+        mut self,
+        count: &Option<FragmentResolution>,
+        angle: &Option<crate::types::Angle>,
+        size: &Option<Length>,
+    ) -> Result<Self> {
+        if let Some(count) = count {
+            self.fragment_count = num(&count.0)?;
+        }
+        if let Some(angle) = angle {
+            self.fragment_angle = num(&angle.0)?;
+        }
+        if let Some(size) = size {
+            self.fragment_size = num(&size.0)?;
+        }
+        Ok(self)
+    }
+
+    /// The number of fragments for a circle, using the same rules as OpenSCAD
+    fn fragments(&self, r: f64) -> usize {
+        if r < 1e-8 {
+            3
+        } else if self.fragment_count > 0.0 {
+            (self.fragment_count as usize).max(3)
+        } else {
+            // OpenSCAD raises `$fa` and `$fs` to 0.01, which keeps the count finite
+            let angle = self.fragment_angle.max(0.01);
+            let size = self.fragment_size.max(0.01);
+            let count = (360.0 / angle).min(r * TAU / size);
+            count.max(5.0).ceil() as usize
+        }
+    }
+}
+
+/// A 2D shape, which is kept as a tree so the booleans can be applied after extruding
+#[derive(Clone, Debug)]
+enum Shape {
+    Contour(Vec<[f64; 2]>),
+    Union(Vec<Shape>),
+    Difference(Vec<Shape>),
+    Intersection(Vec<Shape>),
+}
+
+impl Shape {
+    fn transform(&mut self, m: &Affine) {
+        match self {
+            Self::Contour(points) => {
+                for p in points {
+                    let v = m.apply(Vec3::new(p[0], p[1], 0.0));
+                    *p = [v.x, v.y];
+                }
+            }
+            Self::Union(shapes) | Self::Difference(shapes) | Self::Intersection(shapes) => {
+                for shape in shapes {
+                    shape.transform(m);
+                }
+            }
+        }
+    }
+
+    /// The furthest distance of any point from the Z axis
+    fn radius(&self) -> f64 {
+        match self {
+            Self::Contour(points) => points.iter().map(|p| p[0].hypot(p[1])).fold(0.0, f64::max),
+            Self::Union(shapes) | Self::Difference(shapes) | Self::Intersection(shapes) => {
+                shapes.iter().map(Self::radius).fold(0.0, f64::max)
+            }
+        }
+    }
+
+    /// The largest X value of any point, which is the radius when revolving
+    fn max_x(&self) -> f64 {
+        match self {
+            Self::Contour(points) => points.iter().map(|p| p[0]).fold(0.0, f64::max),
+            Self::Union(shapes) | Self::Difference(shapes) | Self::Intersection(shapes) => {
+                shapes.iter().map(Self::max_x).fold(0.0, f64::max)
+            }
+        }
+    }
+
+    /// Converts each contour into a solid and applies the booleans to the results
+    fn to_solid(&self, contour: &dyn Fn(&[[f64; 2]]) -> Result<Solid>) -> Result<Solid> {
+        let solids = |shapes: &[Shape]| -> Result<Vec<Solid>> {
+            shapes.iter().map(|shape| shape.to_solid(contour)).collect()
+        };
+
+        Ok(match self {
+            Self::Contour(points) => contour(&ccw(points))?,
+            Self::Union(shapes) => union(solids(shapes)?),
+            Self::Difference(shapes) => difference(solids(shapes)?),
+            Self::Intersection(shapes) => intersection(solids(shapes)?),
+        })
+    }
+}
+
+impl Affine {
+    fn determinant(&self) -> f64 {
+        let m = &self.0;
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+}
+
+/// How an operator affects its children
+enum Op {
+    Transform(Affine),
+    Context(Context),
+    Boolean(BooleanKind),
+    /// The children are rendered unchanged
+    Pass,
+    /// The children aren't rendered
+    Empty,
+}
+
+fn unsupported<T: core::fmt::Debug>(value: &T) -> MeshError {
+    let name = format!("{value:?}");
+    // only keep the name of the module, since the arguments can be huge
+    let name = name.split([' ', '(', '{']).next().unwrap_or_default();
+    MeshError::Unsupported(name.to_string())
+}
+
+/// Evaluates a 3D object
+pub(crate) fn solid(object: &Object<3>, ctx: &Context) -> Result<Solid> {
+    if let Some(cube) = object.downcast_ref::<Cube>() {
+        let size = match &cube.size {
+            Some(CubeSize::Length3(size)) => vec3(size)?,
+            Some(CubeSize::Length(size)) => {
+                let size = num(&size.0)?;
+                Vec3::new(size, size, size)
+            }
+            None => Vec3::new(1.0, 1.0, 1.0),
+        };
+        let min = if cube.center == Some(true) {
+            size * -0.5
+        } else {
+            Vec3::default()
+        };
+        return Ok(cuboid(min, min + size));
+    }
+
+    if let Some(sphere) = object.downcast_ref::<Sphere>() {
+        let ctx = ctx.with(
+            &sphere.fragment_resolution,
+            &sphere.fragment_angle,
+            &sphere.fragment_size,
+        )?;
+        return Ok(self::sphere(length(&sphere.radius, 1.0)?, &ctx));
+    }
+
+    if let Some(cylinder) = object.downcast_ref::<Cylinder>() {
+        let (r1, r2) = match &cylinder.radius {
+            Some(CylinderRadius::Length(r)) => (num(&r.0)?, num(&r.0)?),
+            Some(CylinderRadius::Length2(r)) => vec2(r)?.into(),
+            None => (1.0, 1.0),
+        };
+        let r1 = length(&cylinder.radius_bottom, r1)?;
+        let r2 = length(&cylinder.radius_top, r2)?;
+        let h = length(&cylinder.height, 1.0)?;
+        let z = if cylinder.center == Some(true) {
+            -h / 2.0
+        } else {
+            0.0
+        };
+        return Ok(self::cylinder(r1, r2, z, z + h, ctx));
+    }
+
+    if let Some(polyhedron) = object.downcast_ref::<Polyhedron>() {
+        return self::polyhedron(polyhedron);
+    }
+
+    if let Some(block) = object.downcast_ref::<Block<3>>() {
+        return solids(&block.0, ctx).map(union);
+    }
+
+    if let Some(boolean) = object.downcast_ref::<Boolean<3>>() {
+        return boolean_solid(boolean.kind, &boolean.children, ctx);
+    }
+
+    if let Some(wrapped) = object.downcast_ref::<Wrapped<3, 3>>() {
+        return match operator(&wrapped.parent, ctx)? {
+            Op::Transform(m) => Ok(transform(solid(&wrapped.child, ctx)?, &m)),
+            Op::Context(ctx) => solid(&wrapped.child, &ctx),
+            Op::Boolean(kind) => boolean_solid(kind, &children(&wrapped.child), ctx),
+            Op::Pass => solid(&wrapped.child, ctx),
+            Op::Empty => Ok(vec![]),
+        };
+    }
+
+    if let Some(wrapped) = object.downcast_ref::<Wrapped<2, 3>>() {
+        let child = shape(&wrapped.child, ctx)?;
+
+        if let Some(extrude) = wrapped.parent.downcast_ref::<LinearExtrude>() {
+            return linear_extrude(extrude, &child, ctx);
+        }
+
+        if let Some(extrude) = wrapped.parent.downcast_ref::<RotateExtrude>() {
+            let ctx = ctx.with(
+                &extrude.fragment_resolution,
+                &extrude.fragment_angle,
+                &extrude.fragment_size,
+            )?;
+            let angle = extrude.angle.as_ref().map_or(Ok(360.0), |a| num(&a.0))?;
+            return rotate_extrude(&child, angle, &ctx);
+        }
+
+        return Err(unsupported(&wrapped.parent));
+    }
+
+    Err(unsupported(object))
+}
+
+/// Evaluates a 2D object
+fn shape(object: &Object<2>, ctx: &Context) -> Result<Shape> {
+    if let Some(square) = object.downcast_ref::<Square>() {
+        let [x, y] = match &square.size {
+            Some(SquareSize::Length2(size)) => vec2(size)?,
+            Some(SquareSize::Length(size)) => [num(&size.0)?; 2],
+            None => [1.0, 1.0],
+        };
+        let [x0, y0] = if square.center == Some(true) {
+            [-x / 2.0, -y / 2.0]
+        } else {
+            [0.0, 0.0]
+        };
+        let (x1, y1) = (x0 + x, y0 + y);
+        return Ok(Shape::Contour(vec![[x0, y0], [x1, y0], [x1, y1], [x0, y1]]));
+    }
+
+    if let Some(circle) = object.downcast_ref::<Circle>() {
+        let ctx = ctx.with(
+            &circle.fragment_resolution,
+            &circle.fragment_angle,
+            &circle.fragment_size,
+        )?;
+        let r = length(&circle.radius, 1.0)?;
+        return Ok(Shape::Contour(ring(r, ctx.fragments(r))));
+    }
+
+    if let Some(polygon) = object.downcast_ref::<crate::primitive::Polygon>() {
+        if polygon
+            .paths
+            .as_ref()
+            .is_some_and(|paths| !paths.0.is_empty())
+        {
+            return Err(MeshError::Unsupported("polygon with paths".into()));
+        }
+        let points = match &polygon.points {
+            Some(points) => points.0.iter().map(vec2).collect::<Result<_, _>>()?,
+            None => vec![],
+        };
+        return Ok(Shape::Contour(points));
+    }
+
+    if let Some(block) = object.downcast_ref::<Block<2>>() {
+        return shapes(&block.0, ctx).map(Shape::Union);
+    }
+
+    if let Some(boolean) = object.downcast_ref::<Boolean<2>>() {
+        return boolean_shape(boolean.kind, &boolean.children, ctx);
+    }
+
+    if let Some(wrapped) = object.downcast_ref::<Wrapped<2, 2>>() {
+        return match operator(&wrapped.parent, ctx)? {
+            Op::Transform(m) => {
+                let mut shape = shape(&wrapped.child, ctx)?;
+                shape.transform(&m);
+                Ok(shape)
+            }
+            Op::Context(ctx) => shape(&wrapped.child, &ctx),
+            Op::Boolean(kind) => boolean_shape(kind, &children(&wrapped.child), ctx),
+            Op::Pass => shape(&wrapped.child, ctx),
+            Op::Empty => Ok(Shape::Union(vec![])),
+        };
+    }
+
+    Err(unsupported(object))
+}
+
+fn solids(objects: &[Object<3>], ctx: &Context) -> Result<Vec<Solid>> {
+    objects.iter().map(|object| solid(object, ctx)).collect()
+}
+
+fn shapes(objects: &[Object<2>], ctx: &Context) -> Result<Vec<Shape>> {
+    objects.iter().map(|object| shape(object, ctx)).collect()
+}
+
+/// The children of an operator, which are wrapped in a block if there's more than one
+fn children<const DIMENSIONS: usize>(child: &Object<DIMENSIONS>) -> Vec<Object<DIMENSIONS>> {
+    match child.downcast_ref::<Block<DIMENSIONS>>() {
+        Some(block) => block.0.clone(),
+        None => vec![child.clone()],
+    }
+}
+
+fn boolean_solid(kind: BooleanKind, children: &[Object<3>], ctx: &Context) -> Result<Solid> {
+    let children = solids(children, ctx)?;
+    match kind {
+        BooleanKind::Union => Ok(union(children)),
+        BooleanKind::Difference => Ok(difference(children)),
+        BooleanKind::Intersection => Ok(intersection(children)),
+        BooleanKind::Minkowski => Err(MeshError::Unsupported("minkowski".into())),
+    }
+}
+
+fn boolean_shape(kind: BooleanKind, children: &[Object<2>], ctx: &Context) -> Result<Shape> {
+    let children = shapes(children, ctx)?;
+    match kind {
+        BooleanKind::Union => Ok(Shape::Union(children)),
+        BooleanKind::Difference => Ok(Shape::Difference(children)),
+        BooleanKind::Intersection => Ok(Shape::Intersection(children)),
+        BooleanKind::Minkowski => Err(MeshError::Unsupported("minkowski".into())),
+    }
+}
+
+/// Works out what an operator does to its children
+fn operator<const DIMENSIONS: usize>(parent: &Object<DIMENSIONS>, ctx: &Context) -> Result<Op> {
+    macro_rules! op {
+        ($ty:ident, $op:expr) => {
+            if parent.downcast_ref::<$ty<DIMENSIONS>>().is_some() {
+                return Ok($op);
+            }
+        };
+    }
+
+    if let Some(m) = math::transform(parent)? {
+        return Ok(Op::Transform(m));
+    }
+
+    // the preview values are ignored, since the mesh is always a full render
+    if let Some(op) = parent.downcast_ref::<FragmentAngle<DIMENSIONS>>() {
+        let mut ctx = *ctx;
+        ctx.fragment_angle = op.angle.as_ref().map_or(Ok(12.0), |v| num(&v.0))?;
+        return Ok(Op::Context(ctx));
+    }
+
+    if let Some(op) = parent.downcast_ref::<FragmentSize<DIMENSIONS>>() {
+        let mut ctx = *ctx;
+        ctx.fragment_size = length(&op.size, 2.0)?;
+        return Ok(Op::Context(ctx));
+    }
+
+    if let Some(op) = parent.downcast_ref::<FragmentCount<DIMENSIONS>>() {
+        let mut ctx = *ctx;
+        ctx.fragment_count = op.count.as_ref().map_or(Ok(0.0), |v| num(&v.0))?;
+        return Ok(Op::Context(ctx));
+    }
+
+    op!(Union, Op::Boolean(BooleanKind::Union));
+    op!(Difference, Op::Boolean(BooleanKind::Difference));
+    op!(Intersection, Op::Boolean(BooleanKind::Intersection));
+    op!(ColorOperator, Op::Pass);
+    op!(Dbg, Op::Pass);
+    op!(Bg, Op::Empty);
+    op!(Disable, Op::Empty);
+    op!(InPreview, Op::Empty);
+
+    Err(unsupported(parent))
+}
+
+fn transform(solid: Solid, m: &Affine) -> Solid {
+    // mirroring turns the polygons inside out, so flip them back
+    let flip = m.determinant() < 0.0;
+    solid
+        .into_iter()
+        .filter_map(|polygon| {
+            let mut vertices: Vec<_> = polygon.vertices.iter().map(|v| m.apply(*v)).collect();
+            if flip {
+                vertices.reverse();
+            }
+            Polygon::new(vertices)
+        })
+        .collect()
+}
+
+fn bounds(solid: &Solid) -> Option<(Vec3, Vec3)> {
+    let mut points = solid.iter().flat_map(|p| p.vertices.iter());
+    let first = *points.next()?;
+    Some(points.fold((first, first), |(min, max), p| {
+        (
+            Vec3::new(min.x.min(p.x), min.y.min(p.y), min.z.min(p.z)),
+            Vec3::new(max.x.max(p.x), max.y.max(p.y), max.z.max(p.z)),
+        )
+    }))
+}
+
+fn overlaps(a: &Solid, b: &Solid) -> bool {
+    match (bounds(a), bounds(b)) {
+        (Some((a_min, a_max)), Some((b_min, b_max))) => {
+            a_min.x <= b_max.x
+                && b_min.x <= a_max.x
+                && a_min.y <= b_max.y
+                && b_min.y <= a_max.y
+                && a_min.z <= b_max.z
+                && b_min.z <= a_max.z
+        }
+        _ => false,
+    }
+}
+
+fn union(solids: Vec<Solid>) -> Solid {
+    solids.into_iter().fold(vec![], |mut acc, solid| {
+        if overlaps(&acc, &solid) {
+            csg::union(acc, solid)
+        } else {
+            // disjoint solids can just be merged
+            acc.extend(solid);
+            acc
+        }
+    })
+}
+
+fn difference(solids: Vec<Solid>) -> Solid {
+    let mut solids = solids.into_iter();
+    let first = solids.next().unwrap_or_default();
+    solids.fold(first, |acc, solid| {
+        if overlaps(&acc, &solid) {
+            csg::difference(acc, solid)
+        } else {
+            acc
+        }
+    })
+}
+
+fn intersection(solids: Vec<Solid>) -> Solid {
+    let mut solids = solids.into_iter();
+    let first = solids.next().unwrap_or_default();
+    solids.fold(first, |acc, solid| {
+        if overlaps(&acc, &solid) {
+            csg::intersection(acc, solid)
+        } else {
+            vec![]
+        }
+    })
+}
+
+fn cuboid(min: Vec3, max: Vec3) -> Solid {
+    let p = |x: bool, y: bool, z: bool| {
+        Vec3::new(
+            if x { max.x } else { min.x },
+            if y { max.y } else { min.y },
+            if z { max.z } else { min.z },
+        )
+    };
+    let (f, t) = (false, true);
+    [
+        [p(f, f, f), p(f, t, f), p(t, t, f), p(t, f, f)],
+        [p(f, f, t), p(t, f, t), p(t, t, t), p(f, t, t)],
+        [p(f, f, f), p(t, f, f), p(t, f, t), p(f, f, t)],
+        [p(f, t, f), p(f, t, t), p(t, t, t), p(t, t, f)],
+        [p(f, f, f), p(f, f, t), p(f, t, t), p(f, t, f)],
+        [p(t, f, f), p(t, t, f), p(t, t, t), p(t, f, t)],
+    ]
+    .into_iter()
+    .filter_map(|face| Polygon::new(face.to_vec()))
+    .collect()
+}
+
+/// The points on a circle, counter-clockwise from the X axis
+fn ring(r: f64, fragments: usize) -> Vec<[f64; 2]> {
+    (0..fragments)
+        .map(|idx| {
+            let (s, c) = (360.0 * idx as f64 / fragments as f64)
+                .to_radians()
+                .sin_cos();
+            [r * c, r * s]
+        })
+        .collect()
+}
+
+fn sphere(r: f64, ctx: &Context) -> Solid {
+    let fragments = ctx.fragments(r);
+    let rings = fragments.div_ceil(2);
+
+    let rings: Vec<Vec<Vec3>> = (0..rings)
+        .map(|idx| {
+            let phi = (180.0 * (idx as f64 + 0.5) / rings as f64).to_radians();
+            let (s, z) = (r * phi.sin(), r * phi.cos());
+            ring(s, fragments)
+                .into_iter()
+                .map(|[x, y]| Vec3::new(x, y, z))
+                .collect()
+        })
+        .collect();
+
+    let mut out = vec![];
+    out.extend(Polygon::new(rings[0].clone()));
+    for pair in rings.windows(2) {
+        let (upper, lower) = (&pair[0], &pair[1]);
+        for i in 0..fragments {
+            let j = (i + 1) % fragments;
+            out.extend(Polygon::new(vec![upper[i], lower[i], lower[j], upper[j]]));
+        }
+    }
+    let mut bottom = rings[rings.len() - 1].clone();
+    bottom.reverse();
+    out.extend(Polygon::new(bottom));
+    out
+}
+
+fn cylinder(r1: f64, r2: f64, z1: f64, z2: f64, ctx: &Context) -> Solid {
+    let fragments = ctx.fragments(r1.max(r2));
+    let bottom: Vec<_> = ring(r1, fragments)
+        .into_iter()
+        .map(|[x, y]| Vec3::new(x, y, z1))
+        .collect();
+    let top: Vec<_> = ring(r2, fragments)
+        .into_iter()
+        .map(|[x, y]| Vec3::new(x, y, z2))
+        .collect();
+
+    let mut out = vec![];
+    for i in 0..fragments {
+        let j = (i + 1) % fragments;
+        out.extend(Polygon::new(vec![bottom[i], bottom[j], top[j], top[i]]));
+    }
+    if r1 > 0.0 {
+        out.extend(Polygon::new(bottom.into_iter().rev().collect()));
+    }
+    if r2 > 0.0 {
+        out.extend(Polygon::new(top));
+    }
+    out
+}
+
+fn polyhedron(polyhedron: &Polyhedron) -> Result<Solid> {
+    let points: Vec<_> = match &polyhedron.points {
+        Some(points) => points.0.iter().map(vec3).collect::<Result<_, _>>()?,
+        None => vec![],
+    };
+
+    let mut out = vec![];
+    for face in polyhedron.faces.iter().flat_map(|faces| &faces.0) {
+        let vertices = face
+            .0
+            .iter()
+            .rev() // OpenSCAD faces are clockwise when viewed from outside
+            .map(|idx| {
+                points.get(*idx).copied().ok_or_else(|| {
+                    MeshError::Invalid(format!("polyhedron face uses missing point {idx}"))
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // faces can be concave, so split them into triangles in their own plane
+        for [a, b, c] in triangulate(&project_face(&vertices)) {
+            out.extend(Polygon::new(vec![vertices[a], vertices[b], vertices[c]]));
+        }
+    }
+    Ok(out)
+}
+
+/// Flattens a planar face onto its plane, keeping it counter-clockwise
+fn project_face(vertices: &[Vec3]) -> Vec<[f64; 2]> {
+    // Newell's method, which points the normal the way the face winds even if it's concave
+    let mut normal = Vec3::default();
+    for (idx, a) in vertices.iter().enumerate() {
+        let b = vertices[(idx + 1) % vertices.len()];
+        normal = normal + a.cross(b);
+    }
+    let axis = if normal.x.abs() < normal.y.abs().max(normal.z.abs()) {
+        Vec3::new(1.0, 0.0, 0.0)
+    } else {
+        Vec3::new(0.0, 1.0, 0.0)
+    };
+    // `u` and `v` follow the right hand rule around the normal
+    let u = normal.cross(axis);
+    let v = normal.cross(u);
+    vertices.iter().map(|p| [p.dot(u), p.dot(v)]).collect()
+}
+
+fn signed_area(points: &[[f64; 2]]) -> f64 {
+    let mut area = 0.0;
+    for (idx, a) in points.iter().enumerate() {
+        let b = points[(idx + 1) % points.len()];
+        area += a[0] * b[1] - b[0] * a[1];
+    }
+    area / 2.0
+}
+
+/// Returns the points in counter-clockwise order
+fn ccw(points: &[[f64; 2]]) -> Vec<[f64; 2]> {
+    let mut points = points.to_vec();
+    if signed_area(&points) < 0.0 {
+        points.reverse();
+    }
+    points
+}
+
+/// Splits a counter-clockwise simple polygon into triangles by clipping ears
+fn triangulate(points: &[[f64; 2]]) -> Vec<[usize; 3]> {
+    let cross = |o: [f64; 2], a: [f64; 2], b: [f64; 2]| {
+        (a[0] - o[0]) * (b[1] - o[1]) - (a[1] - o[1]) * (b[0] - o[0])
+    };
+
+    let mut remaining: Vec<usize> = (0..points.len()).collect();
+    let mut out = vec![];
+
+    while remaining.len() > 3 {
+        let count = remaining.len();
+        let ear = (0..count).find(|&i| {
+            let (a, b, c) = (
+                remaining[(i + count - 1) % count],
+                remaining[i],
+                remaining[(i + 1) % count],
+            );
+            let (pa, pb, pc) = (points[a], points[b], points[c]);
+            if cross(pa, pb, pc) <= 0.0 {
+                return false;
+            }
+            // no other point can be inside of the ear
+            remaining.iter().all(|&p| {
+                if p == a || p == b || p == c {
+                    return true;
+                }
+                let pp = points[p];
+                !(cross(pa, pb, pp) >= 0.0 && cross(pb, pc, pp) >= 0.0 && cross(pc, pa, pp) >= 0.0)
+            })
+        });
+
+        // fall back to clipping any vertex if the polygon isn't simple
+        let i = ear.unwrap_or(0);
+        out.push([
+            remaining[(i + count - 1) % count],
+            remaining[i],
+            remaining[(i + 1) % count],
+        ]);
+        remaining.remove(i);
+    }
+
+    if let [a, b, c] = remaining[..] {
+        out.push([a, b, c]);
+    }
+    out
+}
+
+/// Connects two copies of a contour, adding caps at both ends
+fn loft(layers: &[Vec<Vec3>], contour: &[[f64; 2]], planar: bool) -> Solid {
+    let mut out = vec![];
+    let count = contour.len();
+
+    for pair in layers.windows(2) {
+        let (lower, upper) = (&pair[0], &pair[1]);
+        for i in 0..count {
+            let j = (i + 1) % count;
+            if planar {
+                out.extend(Polygon::new(vec![lower[i], lower[j], upper[j], upper[i]]));
+            } else {
+                out.extend(Polygon::new(vec![lower[i], lower[j], upper[j]]));
+                out.extend(Polygon::new(vec![lower[i], upper[j], upper[i]]));
+            }
+        }
+    }
+
+    let (first, last) = (&layers[0], &layers[layers.len() - 1]);
+    for [a, b, c] in triangulate(contour) {
+        out.extend(Polygon::new(vec![first[c], first[b], first[a]]));
+        out.extend(Polygon::new(vec![last[a], last[b], last[c]]));
+    }
+    out
+}
+
+fn linear_extrude(extrude: &LinearExtrude, shape: &Shape, ctx: &Context) -> Result<Solid> {
+    extrude
+        .validate()
+        .map_err(|err| MeshError::Invalid(err.to_string()))?;
+    let direction = match &extrude.vector {
+        Some(v) => vec3(v)?,
+        None => Vec3::new(0.0, 0.0, 1.0),
+    };
+    let default_height = if extrude.vector.is_some() {
+        direction.length()
+    } else {
+        100.0
+    };
+    let height = length(&extrude.height, default_height)?;
+    if direction.z <= 0.0 || height <= 0.0 {
+        return Ok(vec![]);
+    }
+    let offset = direction / direction.length() * height;
+    let start = if extrude.center == Some(true) {
+        offset * -0.5
+    } else {
+        Vec3::default()
+    };
+
+    let ctx = ctx.with(
+        &extrude.fragment_resolution,
+        &extrude.fragment_angle,
+        &extrude.fragment_size,
+    )?;
+    let twist = extrude.twist.as_ref().map_or(Ok(0.0), |t| num(&t.0))?;
+    let [sx, sy] = crate::extrude::scale(extrude)?;
+    let slices = match &extrude.slices {
+        Some(slices) => num(&slices.0)?.max(1.0) as usize,
+        None if twist == 0.0 => 1,
+        None => {
+            let fragments = ctx.fragments(shape.radius()) as f64;
+            (fragments * twist.abs() / 360.0).ceil().max(1.0) as usize
+        }
+    };
+    let segments = extrude.segments.as_ref().map_or(Ok(0.0), |s| num(&s.0))?;
+    // the sides only stay flat while each edge keeps its direction
+    let planar = twist == 0.0 && sx == sy;
+
+    shape.to_solid(&|contour| {
+        let contour = &subdivide(contour, segments as usize);
+        let layers: Vec<Vec<Vec3>> = (0..=slices)
+            .map(|slice| {
+                let t = slice as f64 / slices as f64;
+                // positive twists turn clockwise when looking down the Z axis
+                let m = Affine::translate(start + offset * t)
+                    * Affine::rotate_axis(Vec3::new(0.0, 0.0, 1.0), -twist * t)
+                    * Affine::scale(Vec3::new(1.0 + (sx - 1.0) * t, 1.0 + (sy - 1.0) * t, 1.0));
+                contour
+                    .iter()
+                    .map(|[x, y]| m.apply(Vec3::new(*x, *y, 0.0)))
+                    .collect()
+            })
+            .collect();
+        Ok(loft(&layers, contour, planar))
+    })
+}
+
+/// Splits each edge of a contour evenly, so that it has at least the given number of points
+fn subdivide(contour: &[[f64; 2]], points: usize) -> Vec<[f64; 2]> {
+    let count = contour.len();
+    if count == 0 || points <= count {
+        return contour.to_vec();
+    }
+
+    let pieces = points.div_ceil(count);
+    (0..count)
+        .flat_map(|i| {
+            let (a, b) = (contour[i], contour[(i + 1) % count]);
+            (0..pieces).map(move |piece| {
+                let t = piece as f64 / pieces as f64;
+                [a[0] + (b[0] - a[0]) * t, a[1] + (b[1] - a[1]) * t]
+            })
+        })
+        .collect()
+}
+
+fn rotate_extrude(shape: &Shape, angle: f64, ctx: &Context) -> Result<Solid> {
+    let angle = angle.clamp(-360.0, 360.0);
+    if angle == 0.0 {
+        return Ok(vec![]);
+    }
+
+    let sweep = angle.abs();
+    let fragments = ctx.fragments(shape.max_x()) as f64;
+    let segments = (fragments * sweep / 360.0).ceil().max(1.0) as usize;
+
+    let solid = shape.to_solid(&|contour| {
+        if contour.iter().any(|p| p[0] < 0.0) {
+            return Err(MeshError::Invalid(
+                "all points for rotate_extrude must have the same X coordinate sign".into(),
+            ));
+        }
+
+        let layers: Vec<Vec<Vec3>> = (0..=segments)
+            .map(|idx| {
+                let a = sweep * idx as f64 / segments as f64;
+                let (s, c) = a.to_radians().sin_cos();
+                contour
+                    .iter()
+                    .map(|[x, y]| Vec3::new(x * c, x * s, *y))
+                    .collect()
+            })
+            .collect();
+
+        let count = contour.len();
+        let mut out = vec![];
+        for pair in layers.windows(2) {
+            let (a, b) = (&pair[0], &pair[1]);
+            for i in 0..count {
+                let j = (i + 1) % count;
+                out.extend(Polygon::new(vec![a[i], b[i], b[j], a[j]]));
+            }
+        }
+
+        if sweep < 360.0 {
+            let (first, last) = (&layers[0], &layers[layers.len() - 1]);
+            for [a, b, c] in triangulate(contour) {
+                out.extend(Polygon::new(vec![first[a], first[b], first[c]]));
+                out.extend(Polygon::new(vec![last[c], last[b], last[a]]));
+            }
+        }
+        Ok(out)
+    })?;
+
+    // negative angles sweep clockwise, which is the mirror image of the positive sweep
+    Ok(if angle < 0.0 {
+        transform(solid, &Affine::mirror(Vec3::new(0.0, 1.0, 0.0)))
+    } else {
+        solid
+    })
+}
@@ -1,26 +1,44 @@
 use crate::{
     scad::{self, Scad},
-    ObjectExt, Operator,
+    Node, ObjectExt, Operator,
 };
-use core::{fmt, ops};
+use core::{any::Any, fmt, ops};
 use std::sync::Arc;
 
-trait ObjectValue: 'static + Send + Sync + fmt::Debug + fmt::Display + Scad {}
+pub(crate) trait ObjectValue:
+    'static + Send + Sync + fmt::Debug + fmt::Display + Scad + Node
+{
+    fn as_any(&self) -> &dyn Any;
+}
 
-impl<T> ObjectValue for T where T: 'static + Send + Sync + fmt::Debug + fmt::Display + Scad {}
+impl<T> ObjectValue for T
+where
+    T: 'static + Send + Sync + fmt::Debug + fmt::Display + Scad + Node,
+{
+    #[inline]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
 
 #[derive(Clone)]
 #[must_use = "Objects must be returned in order to be rendered"]
-pub struct Object<const DIMENSIONS: usize = 3>(Arc<dyn ObjectValue>);
+pub struct Object<const DIMENSIONS: usize = 3>(pub(crate) Arc<dyn ObjectValue>);
 
 impl<const DIMENSIONS: usize> Object<DIMENSIONS> {
     #[inline]
     pub fn new<T>(value: T) -> Self
     where
-        T: 'static + Send + Sync + fmt::Debug + fmt::Display + Scad,
+        T: 'static + Send + Sync + fmt::Debug + fmt::Display + Scad + Node,
     {
         Self(Arc::new(value))
     }
+
+    /// Returns the object as its concrete type, like [`Cube`](crate::Cube)
+    #[inline]
+    pub fn downcast_ref<T: 'static>(&self) -> Option<&T> {
+        self.0.as_any().downcast_ref()
+    }
 }
 
 impl<const DIMENSIONS: usize> fmt::Debug for Object<DIMENSIONS> {
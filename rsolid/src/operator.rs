@@ -28,6 +28,7 @@ impl<const DIMENSIONS_IN: usize, const DIMENSIONS_OUT: usize> Scad
     for Wrapped<DIMENSIONS_IN, DIMENSIONS_OUT>
 {
     fn assign(&self, f: &mut scad::Formatter) -> scad::Assignment {
+        self.parent.0.report(f);
         let child = self.child.assign(f);
         let parent = self.parent.assign(f);
         f.emit(format_args!("{parent} {child}"), scad::AssignmentType::Call)
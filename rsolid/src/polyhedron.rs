@@ -0,0 +1,100 @@
+//! Checks for [`Polyhedron`] faces that OpenSCAD would reject or render as a broken mesh
+
+use crate::Polyhedron;
+use core::fmt;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PolyhedronError {
+    /// A face uses a point index past the end of the points
+    MissingPoint {
+        face: usize,
+        index: usize,
+        points: usize,
+    },
+    /// A face has fewer than 3 points, so it has no area
+    DegenerateFace { face: usize, points: usize },
+}
+
+impl fmt::Display for PolyhedronError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::MissingPoint {
+                face,
+                index,
+                points,
+            } => write!(
+                f,
+                "polyhedron face {face} uses point {index}, but there are only {points} points"
+            ),
+            Self::DegenerateFace { face, points } => {
+                write!(f, "polyhedron face {face} only has {points} points")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PolyhedronError {}
+
+impl Polyhedron {
+    /// Checks that every face has at least 3 points and only uses points that exist
+    ///
+    /// OpenSCAD only reports bad indices when rendering, so this catches them while the model
+    /// is being built.
+    ///
+    /// ```
+    /// use rsolid::*;
+    ///
+    /// let points = [[0, 0, 0], [1, 0, 0], [0, 1, 0], [0, 0, 1]];
+    /// assert!(polyhedron(points, [[0, 1, 2], [0, 1, 4]]).validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<(), PolyhedronError> {
+        let points = self.points.as_ref().map_or(0, |points| points.0.len());
+        let faces = self.faces.iter().flat_map(|faces| &faces.0);
+        for (face, indices) in faces.enumerate() {
+            if indices.0.len() < 3 {
+                return Err(PolyhedronError::DegenerateFace {
+                    face,
+                    points: indices.0.len(),
+                });
+            }
+            if let Some(&index) = indices.0.iter().find(|index| **index >= points) {
+                return Err(PolyhedronError::MissingPoint {
+                    face,
+                    index,
+                    points,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn validate_test() {
+    use crate::*;
+
+    let points = [[0, 0, 0], [1, 0, 0], [0, 1, 0], [0, 0, 1]];
+    let faces = [[0, 1, 2], [0, 3, 1], [0, 2, 3], [1, 3, 2]];
+    assert_eq!(polyhedron(points, faces).validate(), Ok(()));
+    assert_eq!(
+        polyhedron(points, [[0, 1, 2], [0, 3, 4]]).validate(),
+        Err(PolyhedronError::MissingPoint {
+            face: 1,
+            index: 4,
+            points: 4
+        })
+    );
+    assert_eq!(
+        polyhedron(points, [vec![0, 1]]).validate(),
+        Err(PolyhedronError::DegenerateFace { face: 0, points: 2 })
+    );
+}
+
+#[test]
+fn polyhedron_test() {
+    use crate::*;
+
+    let points = [[0, 0, 0], [1, 0, 0], [0, 1, 0], [0, 0, 1]];
+    let faces = [[0, 1, 2], [0, 3, 1], [0, 2, 3], [1, 3, 2]];
+    insta::assert_snapshot!(polyhedron(points, faces).convexity(2));
+}
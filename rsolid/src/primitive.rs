@@ -1,5 +1,9 @@
 mod extrude;
 pub use extrude::*;
+mod math;
+pub use math::*;
+mod projection;
+pub use projection::*;
 mod set;
 pub use set::*;
 mod three_d;
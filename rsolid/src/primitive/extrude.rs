@@ -7,14 +7,99 @@
 /// Although the extrusion is linear along the V vector, a twist parameter is available that causes the object to be rotated around the V vector as it is extruding upward. This can be used to rotate the object at its center, as if it is a spiral pillar, or produce a helical extrusion around the V vector, like a pig's tail.
 ///
 /// A scale parameter is also included so that the object can be expanded or contracted over the extent of the extrusion, allowing extrusions to be flared inward or outward.
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Default)]
 #[must_use = "Objects must be returned in order to be rendered"]
 pub struct LinearExtrude {
-    center: Option<bool>,
-    convexity: Option<crate::types::Scalar>,
-    height: Option<crate::types::Length>,
-    twist: Option<crate::types::Angle>,
-    vector: Option<crate::types::Length3>,
+    pub(crate) center: Option<bool>,
+    pub(crate) convexity: Option<crate::types::Scalar>,
+    pub(crate) fragment_angle: Option<crate::types::Angle>,
+    pub(crate) fragment_resolution: Option<crate::types::FragmentResolution>,
+    pub(crate) fragment_size: Option<crate::types::Length>,
+    pub(crate) height: Option<crate::types::Length>,
+    pub(crate) scale: Option<LinearExtrudeScale>,
+    pub(crate) segments: Option<crate::types::Scalar>,
+    pub(crate) slices: Option<crate::types::Scalar>,
+    pub(crate) twist: Option<crate::types::Angle>,
+    pub(crate) vector: Option<crate::types::Length3>,
+}
+
+/// Values accepted by [`LinearExtrude::scale`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum LinearExtrudeScale {
+    Scalar2(crate::types::Scalar2),
+    Scalar(crate::types::Scalar),
+}
+
+impl From<[f64; 2]> for LinearExtrudeScale {
+    #[inline]
+    fn from(value: [f64; 2]) -> Self {
+        Self::Scalar2(value.into())
+    }
+}
+
+impl From<[i64; 2]> for LinearExtrudeScale {
+    #[inline]
+    fn from(value: [i64; 2]) -> Self {
+        Self::Scalar2(value.into())
+    }
+}
+
+impl From<[crate::types::Scalar; 2]> for LinearExtrudeScale {
+    #[inline]
+    fn from(value: [crate::types::Scalar; 2]) -> Self {
+        Self::Scalar2(value.into())
+    }
+}
+
+impl From<crate::types::Scalar2> for LinearExtrudeScale {
+    #[inline]
+    fn from(value: crate::types::Scalar2) -> Self {
+        Self::Scalar2(value)
+    }
+}
+
+impl From<f64> for LinearExtrudeScale {
+    #[inline]
+    fn from(value: f64) -> Self {
+        Self::Scalar(value.into())
+    }
+}
+
+impl From<i64> for LinearExtrudeScale {
+    #[inline]
+    fn from(value: i64) -> Self {
+        Self::Scalar(value.into())
+    }
+}
+
+impl From<crate::Var<crate::types::Scalar>> for LinearExtrudeScale {
+    #[inline]
+    fn from(value: crate::Var<crate::types::Scalar>) -> Self {
+        Self::Scalar(value.into())
+    }
+}
+
+impl From<&crate::Var<crate::types::Scalar>> for LinearExtrudeScale {
+    #[inline]
+    fn from(value: &crate::Var<crate::types::Scalar>) -> Self {
+        Self::Scalar(value.into())
+    }
+}
+
+impl From<crate::types::Scalar> for LinearExtrudeScale {
+    #[inline]
+    fn from(value: crate::types::Scalar) -> Self {
+        Self::Scalar(value)
+    }
+}
+
+impl crate::scad::Scad for LinearExtrudeScale {
+    fn assign(&self, f: &mut crate::scad::Formatter) -> crate::scad::Assignment {
+        match self {
+            Self::Scalar2(value) => crate::scad::Scad::assign(value, f),
+            Self::Scalar(value) => crate::scad::Scad::assign(value, f),
+        }
+    }
 }
 
 #[inline]
@@ -35,12 +120,58 @@ impl LinearExtrude {
         self
     }
 
+    /// Fragment angle in degrees
+    #[inline]
+    pub fn fragment_angle<T: Into<crate::types::Angle>>(mut self, fragment_angle: T) -> Self {
+        self.fragment_angle = Some(fragment_angle.into());
+        self
+    }
+
+    /// Fragment resolution
+    #[inline]
+    pub fn fragment_resolution<T: Into<crate::types::FragmentResolution>>(
+        mut self,
+        fragment_resolution: T,
+    ) -> Self {
+        self.fragment_resolution = Some(fragment_resolution.into());
+        self
+    }
+
+    /// Fragment size in mm
+    #[inline]
+    pub fn fragment_size<T: Into<crate::types::Length>>(mut self, fragment_size: T) -> Self {
+        self.fragment_size = Some(fragment_size.into());
+        self
+    }
+
     #[inline]
     pub fn height<T: Into<crate::types::Length>>(mut self, height: T) -> Self {
         self.height = Some(height.into());
         self
     }
 
+    /// Scales the shape over the extrusion, by the same factor or per axis. A factor of 0 ends the extrusion in a point or a line.
+    #[inline]
+    pub fn scale<T: Into<LinearExtrudeScale>>(mut self, scale: T) -> Self {
+        self.scale = Some(scale.into());
+        self
+    }
+
+    /// Adds points along the edges of the shape, so that twisted or scaled sides bend more smoothly.
+    #[inline]
+    pub fn segments<T: Into<crate::types::Scalar>>(mut self, segments: T) -> Self {
+        self.segments = Some(segments.into());
+        self
+    }
+
+    /// The number of layers the extrusion is split into, which smooths out twists. Defaults to a value based on the twist and the fragment settings.
+    #[inline]
+    pub fn slices<T: Into<crate::types::Scalar>>(mut self, slices: T) -> Self {
+        self.slices = Some(slices.into());
+        self
+    }
+
+    /// The number of degrees the shape is rotated through over the extrusion. Positive values turn clockwise when looking down the extrusion.
     #[inline]
     pub fn twist<T: Into<crate::types::Angle>>(mut self, twist: T) -> Self {
         self.twist = Some(twist.into());
@@ -64,9 +195,27 @@ impl ::core::fmt::Debug for LinearExtrude {
         if let Some(value) = self.convexity.as_ref() {
             s.field("convexity", value);
         }
+        if let Some(value) = self.fragment_angle.as_ref() {
+            s.field("$fa", value);
+        }
+        if let Some(value) = self.fragment_resolution.as_ref() {
+            s.field("$fn", value);
+        }
+        if let Some(value) = self.fragment_size.as_ref() {
+            s.field("$fs", value);
+        }
         if let Some(value) = self.height.as_ref() {
             s.field("height", value);
         }
+        if let Some(value) = self.scale.as_ref() {
+            s.field("scale", value);
+        }
+        if let Some(value) = self.segments.as_ref() {
+            s.field("segments", value);
+        }
+        if let Some(value) = self.slices.as_ref() {
+            s.field("slices", value);
+        }
         if let Some(value) = self.twist.as_ref() {
             s.field("twist", value);
         }
@@ -93,12 +242,48 @@ impl crate::scad::Scad for LinearExtrude {
                     .as_ref()
                     .map(|value| crate::scad::Scad::assign(value, f)),
             ),
+            (
+                "$fa",
+                self.fragment_angle
+                    .as_ref()
+                    .map(|value| crate::scad::Scad::assign(value, f)),
+            ),
+            (
+                "$fn",
+                self.fragment_resolution
+                    .as_ref()
+                    .map(|value| crate::scad::Scad::assign(value, f)),
+            ),
+            (
+                "$fs",
+                self.fragment_size
+                    .as_ref()
+                    .map(|value| crate::scad::Scad::assign(value, f)),
+            ),
             (
                 "height",
                 self.height
                     .as_ref()
                     .map(|value| crate::scad::Scad::assign(value, f)),
             ),
+            (
+                "scale",
+                self.scale
+                    .as_ref()
+                    .map(|value| crate::scad::Scad::assign(value, f)),
+            ),
+            (
+                "segments",
+                self.segments
+                    .as_ref()
+                    .map(|value| crate::scad::Scad::assign(value, f)),
+            ),
+            (
+                "slices",
+                self.slices
+                    .as_ref()
+                    .map(|value| crate::scad::Scad::assign(value, f)),
+            ),
             (
                 "twist",
                 self.twist
@@ -122,6 +307,58 @@ impl ::core::fmt::Display for LinearExtrude {
     }
 }
 
+impl crate::Node for LinearExtrude {
+    fn name(&self) -> &str {
+        "linear_extrude"
+    }
+
+    fn kind(&self) -> crate::NodeKind {
+        crate::NodeKind::Operator
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        let mut out: Vec<(&'static str, &dyn ::core::any::Any)> = Vec::new();
+        if let Some(value) = self.center.as_ref() {
+            out.push(("center", value));
+        }
+        if let Some(value) = self.convexity.as_ref() {
+            out.push(("convexity", value));
+        }
+        if let Some(value) = self.fragment_angle.as_ref() {
+            out.push(("fragment_angle", value));
+        }
+        if let Some(value) = self.fragment_resolution.as_ref() {
+            out.push(("fragment_resolution", value));
+        }
+        if let Some(value) = self.fragment_size.as_ref() {
+            out.push(("fragment_size", value));
+        }
+        if let Some(value) = self.height.as_ref() {
+            out.push(("height", value));
+        }
+        if let Some(value) = self.scale.as_ref() {
+            out.push(("scale", value));
+        }
+        if let Some(value) = self.segments.as_ref() {
+            out.push(("segments", value));
+        }
+        if let Some(value) = self.slices.as_ref() {
+            out.push(("slices", value));
+        }
+        if let Some(value) = self.twist.as_ref() {
+            out.push(("twist", value));
+        }
+        if let Some(value) = self.vector.as_ref() {
+            out.push(("vector", value));
+        }
+        out
+    }
+
+    fn report(&self, f: &mut crate::scad::Formatter) {
+        crate::extrude::report(self, f)
+    }
+}
+
 impl crate::Operator<2> for LinearExtrude {
     type Output = crate::Object<3>;
 
@@ -200,14 +437,14 @@ impl crate::IntoObject<3> for LinearExtrude {
 /// It can not be used to produce a helix or screw threads. (These things can be done with [`linear_extrude`] using the twist parameter.)
 ///
 /// The 2D shape must lie completely on either the right (recommended) or the left side of the Y-axis. More precisely speaking, every vertex of the shape must have either x >= 0 or x <= 0. If the shape spans the X axis a warning appears in the console windows and the rotate_extrude() is ignored. If the 2D shape touches the Y axis, i.e. at x=0, it must be a line that touches, not a point, as a point results in a zero thickness 3D object, which is invalid and results in a CGAL error. For OpenSCAD versions prior to 2016.xxxx, if the shape is in the negative axis the resulting faces are oriented inside-out, which may cause undesired effects.
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Default)]
 #[must_use = "Objects must be returned in order to be rendered"]
 pub struct RotateExtrude {
-    angle: Option<crate::types::Angle>,
-    convexity: Option<crate::types::Scalar>,
-    fragment_angle: Option<crate::types::Angle>,
-    fragment_resolution: Option<crate::types::FragmentResolution>,
-    fragment_size: Option<crate::types::Length>,
+    pub(crate) angle: Option<crate::types::Angle>,
+    pub(crate) convexity: Option<crate::types::Scalar>,
+    pub(crate) fragment_angle: Option<crate::types::Angle>,
+    pub(crate) fragment_resolution: Option<crate::types::FragmentResolution>,
+    pub(crate) fragment_size: Option<crate::types::Length>,
 }
 
 #[inline]
@@ -323,6 +560,36 @@ impl ::core::fmt::Display for RotateExtrude {
     }
 }
 
+impl crate::Node for RotateExtrude {
+    fn name(&self) -> &str {
+        "rotate_extrude"
+    }
+
+    fn kind(&self) -> crate::NodeKind {
+        crate::NodeKind::Operator
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        let mut out: Vec<(&'static str, &dyn ::core::any::Any)> = Vec::new();
+        if let Some(value) = self.angle.as_ref() {
+            out.push(("angle", value));
+        }
+        if let Some(value) = self.convexity.as_ref() {
+            out.push(("convexity", value));
+        }
+        if let Some(value) = self.fragment_angle.as_ref() {
+            out.push(("fragment_angle", value));
+        }
+        if let Some(value) = self.fragment_resolution.as_ref() {
+            out.push(("fragment_resolution", value));
+        }
+        if let Some(value) = self.fragment_size.as_ref() {
+            out.push(("fragment_size", value));
+        }
+        out
+    }
+}
+
 impl crate::Operator<2> for RotateExtrude {
     type Output = crate::Object<3>;
 
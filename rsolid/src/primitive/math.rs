@@ -0,0 +1,211 @@
+/// Looks up a value in a table, and linearly interpolates if there's no exact match. The first argument is the value to look up. The second is the lookup vector -- a vector of key-value pairs.
+#[derive(Clone, Default)]
+#[must_use = "Values must be used in order to be rendered"]
+pub struct Lookup {
+    key: Option<crate::types::Scalar>,
+    table: Option<crate::types::VecScalar2>,
+}
+
+/// Looks up a value in a table, and linearly interpolates if there's no exact match. The first argument is the value to look up. The second is the lookup vector -- a vector of key-value pairs.
+#[inline]
+pub fn lookup(
+    key: impl Into<crate::types::Scalar>,
+    table: impl Into<crate::types::VecScalar2>,
+) -> Lookup {
+    Lookup::default().key(key).table(table)
+}
+
+impl Lookup {
+    /// The key to look up.
+    #[inline]
+    pub fn key<T: Into<crate::types::Scalar>>(mut self, key: T) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// A vector of key-value pairs. If the key is outside of the range of the table, the first or last value is used.
+    #[inline]
+    pub fn table<T: Into<crate::types::VecScalar2>>(mut self, table: T) -> Self {
+        self.table = Some(table.into());
+        self
+    }
+}
+
+impl ::core::fmt::Debug for Lookup {
+    #[allow(clippy::write_literal)]
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        let mut s = f.debug_struct("lookup");
+        if let Some(value) = self.key.as_ref() {
+            s.field("key", value);
+        }
+        if let Some(value) = self.table.as_ref() {
+            s.field("table", value);
+        }
+        s.finish()
+    }
+}
+
+impl crate::scad::Scad for Lookup {
+    fn assign(&self, f: &mut crate::scad::Formatter) -> crate::scad::Assignment {
+        let name = "lookup";
+        let args = [
+            (
+                "",
+                self.key
+                    .as_ref()
+                    .map(|value| crate::scad::Scad::assign(value, f)),
+            ),
+            (
+                "",
+                self.table
+                    .as_ref()
+                    .map(|value| crate::scad::Scad::assign(value, f)),
+            ),
+        ];
+        f.function(name, args)
+    }
+}
+
+impl ::core::fmt::Display for Lookup {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        f.write_str(&crate::scad::Scad::to_scad(self))
+    }
+}
+
+impl From<Lookup> for crate::types::Scalar {
+    #[inline]
+    fn from(value: Lookup) -> Self {
+        Self(crate::Expr::value(value))
+    }
+}
+
+/// Returns the euclidean norm of a vector.
+#[derive(Clone, Default)]
+#[must_use = "Values must be used in order to be rendered"]
+pub struct Norm {
+    vector: Option<NormVector>,
+}
+
+/// Values accepted by [`Norm::vector`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum NormVector {
+    Scalar3(crate::types::Scalar3),
+    Scalar2(crate::types::Scalar2),
+}
+
+impl From<[f64; 3]> for NormVector {
+    #[inline]
+    fn from(value: [f64; 3]) -> Self {
+        Self::Scalar3(value.into())
+    }
+}
+
+impl From<[i64; 3]> for NormVector {
+    #[inline]
+    fn from(value: [i64; 3]) -> Self {
+        Self::Scalar3(value.into())
+    }
+}
+
+impl From<[crate::types::Scalar; 3]> for NormVector {
+    #[inline]
+    fn from(value: [crate::types::Scalar; 3]) -> Self {
+        Self::Scalar3(value.into())
+    }
+}
+
+impl From<crate::types::Scalar3> for NormVector {
+    #[inline]
+    fn from(value: crate::types::Scalar3) -> Self {
+        Self::Scalar3(value)
+    }
+}
+
+impl From<[f64; 2]> for NormVector {
+    #[inline]
+    fn from(value: [f64; 2]) -> Self {
+        Self::Scalar2(value.into())
+    }
+}
+
+impl From<[i64; 2]> for NormVector {
+    #[inline]
+    fn from(value: [i64; 2]) -> Self {
+        Self::Scalar2(value.into())
+    }
+}
+
+impl From<[crate::types::Scalar; 2]> for NormVector {
+    #[inline]
+    fn from(value: [crate::types::Scalar; 2]) -> Self {
+        Self::Scalar2(value.into())
+    }
+}
+
+impl From<crate::types::Scalar2> for NormVector {
+    #[inline]
+    fn from(value: crate::types::Scalar2) -> Self {
+        Self::Scalar2(value)
+    }
+}
+
+impl crate::scad::Scad for NormVector {
+    fn assign(&self, f: &mut crate::scad::Formatter) -> crate::scad::Assignment {
+        match self {
+            Self::Scalar3(value) => crate::scad::Scad::assign(value, f),
+            Self::Scalar2(value) => crate::scad::Scad::assign(value, f),
+        }
+    }
+}
+
+/// Returns the euclidean norm of a vector.
+#[inline]
+pub fn norm(vector: impl Into<NormVector>) -> Norm {
+    Norm::default().vector(vector)
+}
+
+impl Norm {
+    /// The vector to compute the length of.
+    #[inline]
+    pub fn vector<T: Into<NormVector>>(mut self, vector: T) -> Self {
+        self.vector = Some(vector.into());
+        self
+    }
+}
+
+impl ::core::fmt::Debug for Norm {
+    #[allow(clippy::write_literal)]
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        let mut s = f.debug_struct("norm");
+        if let Some(value) = self.vector.as_ref() {
+            s.field("vector", value);
+        }
+        s.finish()
+    }
+}
+
+impl crate::scad::Scad for Norm {
+    fn assign(&self, f: &mut crate::scad::Formatter) -> crate::scad::Assignment {
+        let name = "norm";
+        let args = [(
+            "",
+            self.vector
+                .as_ref()
+                .map(|value| crate::scad::Scad::assign(value, f)),
+        )];
+        f.function(name, args)
+    }
+}
+
+impl ::core::fmt::Display for Norm {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        f.write_str(&crate::scad::Scad::to_scad(self))
+    }
+}
+
+impl From<Norm> for crate::types::Scalar {
+    #[inline]
+    fn from(value: Norm) -> Self {
+        Self(crate::Expr::value(value))
+    }
+}
@@ -0,0 +1,132 @@
+/// Using the projection() function, you can create 2d drawings from 3d models, and export them to the dxf format. It works by projecting a 3D model to the (x,y) plane, with z at 0.
+///
+/// If `cut` is true, only points with z=0 are considered (effectively cutting the object), with `cut` false (the default), points above and below the plane are considered as well (creating a proper projection).
+#[derive(Clone, Copy, Default)]
+#[must_use = "Objects must be returned in order to be rendered"]
+pub struct Projection {
+    pub(crate) cut: Option<bool>,
+}
+
+#[inline]
+pub fn projection() -> Projection {
+    Projection::default()
+}
+
+impl Projection {
+    /// When true, only the cross-section of the object at z=0 is kept instead of its full shadow.
+    #[inline]
+    pub fn cut<T: Into<bool>>(mut self, cut: T) -> Self {
+        self.cut = Some(cut.into());
+        self
+    }
+}
+
+impl ::core::fmt::Debug for Projection {
+    #[allow(clippy::write_literal)]
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        let mut s = f.debug_struct("projection");
+        if let Some(value) = self.cut.as_ref() {
+            s.field("cut", value);
+        }
+        s.finish()
+    }
+}
+
+impl crate::scad::Scad for Projection {
+    fn assign(&self, f: &mut crate::scad::Formatter) -> crate::scad::Assignment {
+        let name = "projection";
+        let args = [(
+            "cut",
+            self.cut
+                .as_ref()
+                .map(|value| crate::scad::Scad::assign(value, f)),
+        )];
+        f.call(name, args, true)
+    }
+}
+
+impl ::core::fmt::Display for Projection {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        f.write_str(&crate::scad::Scad::to_scad(self))
+    }
+}
+
+impl crate::Node for Projection {
+    fn name(&self) -> &str {
+        "projection"
+    }
+
+    fn kind(&self) -> crate::NodeKind {
+        crate::NodeKind::Operator
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        let mut out: Vec<(&'static str, &dyn ::core::any::Any)> = Vec::new();
+        if let Some(value) = self.cut.as_ref() {
+            out.push(("cut", value));
+        }
+        out
+    }
+}
+
+impl crate::Operator<3> for Projection {
+    type Output = crate::Object<2>;
+
+    fn apply(self, child: &crate::Object<3>) -> Self::Output {
+        let obj: crate::operator::Wrapped<3, 2> = crate::operator::Wrapped {
+            parent: self.into(),
+            child: child.clone(),
+        };
+        crate::Object::new(obj)
+    }
+}
+
+impl<T: crate::IntoObject<2>> ::core::ops::Add<T> for Projection {
+    type Output = crate::Object<2>;
+
+    fn add(self, other: T) -> Self::Output {
+        use crate::IntoObject as _;
+        self.into_object().add(other.into_object())
+    }
+}
+
+impl<T: crate::IntoObject<2>> ::core::ops::Sub<T> for Projection {
+    type Output = crate::Object<2>;
+
+    fn sub(self, other: T) -> Self::Output {
+        use crate::IntoObject as _;
+        self.into_object().sub(other.into_object())
+    }
+}
+
+impl<T: crate::IntoObject<2>> ::core::ops::BitOr<T> for Projection {
+    type Output = crate::Object<2>;
+
+    fn bitor(self, other: T) -> Self::Output {
+        use crate::IntoObject as _;
+        self.into_object().bitor(other.into_object())
+    }
+}
+
+impl<F: crate::Operator<2>> ::core::ops::Shr<F> for Projection {
+    type Output = F::Output;
+
+    fn shr(self, f: F) -> Self::Output {
+        use crate::IntoObject as _;
+        self.into_object() >> f
+    }
+}
+
+impl From<Projection> for crate::Object<2> {
+    #[inline]
+    fn from(value: Projection) -> Self {
+        crate::Object::new(value)
+    }
+}
+
+impl crate::IntoObject<2> for Projection {
+    #[inline]
+    fn into_object(self) -> crate::Object<2> {
+        crate::Object::new(self)
+    }
+}
@@ -33,6 +33,20 @@ impl<const DIMENSIONS: usize> ::core::fmt::Display for Difference<DIMENSIONS> {
     }
 }
 
+impl<const DIMENSIONS: usize> crate::Node for Difference<DIMENSIONS> {
+    fn name(&self) -> &str {
+        "difference"
+    }
+
+    fn kind(&self) -> crate::NodeKind {
+        crate::NodeKind::Operator
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        Vec::new()
+    }
+}
+
 impl<const DIMENSIONS: usize> crate::Operator<DIMENSIONS> for Difference<DIMENSIONS> {
     type Output = crate::Object<DIMENSIONS>;
 
@@ -140,6 +154,20 @@ impl<const DIMENSIONS: usize> ::core::fmt::Display for Intersection<DIMENSIONS>
     }
 }
 
+impl<const DIMENSIONS: usize> crate::Node for Intersection<DIMENSIONS> {
+    fn name(&self) -> &str {
+        "intersection"
+    }
+
+    fn kind(&self) -> crate::NodeKind {
+        crate::NodeKind::Operator
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        Vec::new()
+    }
+}
+
 impl<const DIMENSIONS: usize> crate::Operator<DIMENSIONS> for Intersection<DIMENSIONS> {
     type Output = crate::Object<DIMENSIONS>;
 
@@ -245,6 +273,20 @@ impl<const DIMENSIONS: usize> ::core::fmt::Display for Union<DIMENSIONS> {
     }
 }
 
+impl<const DIMENSIONS: usize> crate::Node for Union<DIMENSIONS> {
+    fn name(&self) -> &str {
+        "union"
+    }
+
+    fn kind(&self) -> crate::NodeKind {
+        crate::NodeKind::Operator
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        Vec::new()
+    }
+}
+
 impl<const DIMENSIONS: usize> crate::Operator<DIMENSIONS> for Union<DIMENSIONS> {
     type Output = crate::Object<DIMENSIONS>;
 
@@ -1,13 +1,92 @@
 /// Creates a cube or rectangular prism (i.e., a "box") in the first octant.
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Default)]
 #[must_use = "Objects must be returned in order to be rendered"]
 pub struct Cube {
-    center: Option<bool>,
-    size: Option<crate::types::Length3>,
+    pub(crate) center: Option<bool>,
+    pub(crate) size: Option<CubeSize>,
+}
+
+/// Values accepted by [`Cube::size`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum CubeSize {
+    Length3(crate::types::Length3),
+    Length(crate::types::Length),
+}
+
+impl From<[f64; 3]> for CubeSize {
+    #[inline]
+    fn from(value: [f64; 3]) -> Self {
+        Self::Length3(value.into())
+    }
+}
+
+impl From<[i64; 3]> for CubeSize {
+    #[inline]
+    fn from(value: [i64; 3]) -> Self {
+        Self::Length3(value.into())
+    }
+}
+
+impl From<[crate::types::Length; 3]> for CubeSize {
+    #[inline]
+    fn from(value: [crate::types::Length; 3]) -> Self {
+        Self::Length3(value.into())
+    }
+}
+
+impl From<crate::types::Length3> for CubeSize {
+    #[inline]
+    fn from(value: crate::types::Length3) -> Self {
+        Self::Length3(value)
+    }
+}
+
+impl From<f64> for CubeSize {
+    #[inline]
+    fn from(value: f64) -> Self {
+        Self::Length(value.into())
+    }
+}
+
+impl From<i64> for CubeSize {
+    #[inline]
+    fn from(value: i64) -> Self {
+        Self::Length(value.into())
+    }
+}
+
+impl From<crate::Var<crate::types::Length>> for CubeSize {
+    #[inline]
+    fn from(value: crate::Var<crate::types::Length>) -> Self {
+        Self::Length(value.into())
+    }
+}
+
+impl From<&crate::Var<crate::types::Length>> for CubeSize {
+    #[inline]
+    fn from(value: &crate::Var<crate::types::Length>) -> Self {
+        Self::Length(value.into())
+    }
+}
+
+impl From<crate::types::Length> for CubeSize {
+    #[inline]
+    fn from(value: crate::types::Length) -> Self {
+        Self::Length(value)
+    }
+}
+
+impl crate::scad::Scad for CubeSize {
+    fn assign(&self, f: &mut crate::scad::Formatter) -> crate::scad::Assignment {
+        match self {
+            Self::Length3(value) => crate::scad::Scad::assign(value, f),
+            Self::Length(value) => crate::scad::Scad::assign(value, f),
+        }
+    }
 }
 
 #[inline]
-pub fn cube(size: impl Into<crate::types::Length3>) -> Cube {
+pub fn cube(size: impl Into<CubeSize>) -> Cube {
     Cube::default().size(size)
 }
 
@@ -22,7 +101,7 @@ impl Cube {
     /// When a single value is provided, the cube's sides will all be this length.
     /// When a coordinate is provided, a rectangular prism with dimensions x, y and z will be created.
     #[inline]
-    pub fn size<T: Into<crate::types::Length3>>(mut self, size: T) -> Self {
+    pub fn size<T: Into<CubeSize>>(mut self, size: T) -> Self {
         self.size = Some(size.into());
         self
     }
@@ -69,6 +148,23 @@ impl ::core::fmt::Display for Cube {
     }
 }
 
+impl crate::Node for Cube {
+    fn name(&self) -> &str {
+        "cube"
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        let mut out: Vec<(&'static str, &dyn ::core::any::Any)> = Vec::new();
+        if let Some(value) = self.center.as_ref() {
+            out.push(("center", value));
+        }
+        if let Some(value) = self.size.as_ref() {
+            out.push(("size", value));
+        }
+        out
+    }
+}
+
 impl<T: crate::IntoObject<3>> ::core::ops::Add<T> for Cube {
     type Output = crate::Object<3>;
 
@@ -120,20 +216,99 @@ impl crate::IntoObject<3> for Cube {
 }
 
 /// Creates a cylinder or cone centered about the z axis.
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Default)]
 #[must_use = "Objects must be returned in order to be rendered"]
 pub struct Cylinder {
-    center: Option<bool>,
-    height: Option<crate::types::Length>,
-    radius: Option<crate::types::Length>,
-    radius_bottom: Option<crate::types::Length>,
-    radius_top: Option<crate::types::Length>,
+    pub(crate) center: Option<bool>,
+    pub(crate) height: Option<crate::types::Length>,
+    pub(crate) radius: Option<CylinderRadius>,
+    pub(crate) radius_bottom: Option<crate::types::Length>,
+    pub(crate) radius_top: Option<crate::types::Length>,
+}
+
+/// Values accepted by [`Cylinder::radius`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum CylinderRadius {
+    Length(crate::types::Length),
+    Length2(crate::types::Length2),
+}
+
+impl From<f64> for CylinderRadius {
+    #[inline]
+    fn from(value: f64) -> Self {
+        Self::Length(value.into())
+    }
+}
+
+impl From<i64> for CylinderRadius {
+    #[inline]
+    fn from(value: i64) -> Self {
+        Self::Length(value.into())
+    }
+}
+
+impl From<crate::Var<crate::types::Length>> for CylinderRadius {
+    #[inline]
+    fn from(value: crate::Var<crate::types::Length>) -> Self {
+        Self::Length(value.into())
+    }
+}
+
+impl From<&crate::Var<crate::types::Length>> for CylinderRadius {
+    #[inline]
+    fn from(value: &crate::Var<crate::types::Length>) -> Self {
+        Self::Length(value.into())
+    }
+}
+
+impl From<crate::types::Length> for CylinderRadius {
+    #[inline]
+    fn from(value: crate::types::Length) -> Self {
+        Self::Length(value)
+    }
+}
+
+impl From<[f64; 2]> for CylinderRadius {
+    #[inline]
+    fn from(value: [f64; 2]) -> Self {
+        Self::Length2(value.into())
+    }
+}
+
+impl From<[i64; 2]> for CylinderRadius {
+    #[inline]
+    fn from(value: [i64; 2]) -> Self {
+        Self::Length2(value.into())
+    }
+}
+
+impl From<[crate::types::Length; 2]> for CylinderRadius {
+    #[inline]
+    fn from(value: [crate::types::Length; 2]) -> Self {
+        Self::Length2(value.into())
+    }
+}
+
+impl From<crate::types::Length2> for CylinderRadius {
+    #[inline]
+    fn from(value: crate::types::Length2) -> Self {
+        Self::Length2(value)
+    }
+}
+
+impl crate::scad::Scad for CylinderRadius {
+    fn assign(&self, f: &mut crate::scad::Formatter) -> crate::scad::Assignment {
+        match self {
+            Self::Length(value) => crate::scad::Scad::assign(value, f),
+            Self::Length2(value) => crate::scad::Scad::assign(value, f),
+        }
+    }
 }
 
 #[inline]
 pub fn cylinder(
     height: impl Into<crate::types::Length>,
-    radius: impl Into<crate::types::Length>,
+    radius: impl Into<CylinderRadius>,
 ) -> Cylinder {
     Cylinder::default().height(height).radius(radius)
 }
@@ -166,8 +341,10 @@ impl Cylinder {
     }
 
     /// Radius of the cylinder
+    ///
+    /// When a pair is provided, the first value is the bottom radius and the second is the top radius of a cone.
     #[inline]
-    pub fn radius<T: Into<crate::types::Length>>(mut self, radius: T) -> Self {
+    pub fn radius<T: Into<CylinderRadius>>(mut self, radius: T) -> Self {
         self.radius = Some(radius.into());
         self
     }
@@ -228,9 +405,28 @@ impl crate::scad::Scad for Cylinder {
             ),
             (
                 "r",
-                self.radius
-                    .as_ref()
-                    .map(|value| crate::scad::Scad::assign(value, f)),
+                match self.radius.as_ref() {
+                    Some(CylinderRadius::Length2(_)) | None => None,
+                    Some(value) => Some(crate::scad::Scad::assign(value, f)),
+                },
+            ),
+            (
+                "r1",
+                match self.radius.as_ref() {
+                    Some(CylinderRadius::Length2(value)) => {
+                        Some(crate::scad::Scad::assign(&value.0[0], f))
+                    }
+                    _ => None,
+                },
+            ),
+            (
+                "r2",
+                match self.radius.as_ref() {
+                    Some(CylinderRadius::Length2(value)) => {
+                        Some(crate::scad::Scad::assign(&value.0[1], f))
+                    }
+                    _ => None,
+                },
             ),
             (
                 "r1",
@@ -255,6 +451,32 @@ impl ::core::fmt::Display for Cylinder {
     }
 }
 
+impl crate::Node for Cylinder {
+    fn name(&self) -> &str {
+        "cylinder"
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        let mut out: Vec<(&'static str, &dyn ::core::any::Any)> = Vec::new();
+        if let Some(value) = self.center.as_ref() {
+            out.push(("center", value));
+        }
+        if let Some(value) = self.height.as_ref() {
+            out.push(("height", value));
+        }
+        if let Some(value) = self.radius.as_ref() {
+            out.push(("radius", value));
+        }
+        if let Some(value) = self.radius_bottom.as_ref() {
+            out.push(("radius_bottom", value));
+        }
+        if let Some(value) = self.radius_top.as_ref() {
+            out.push(("radius_top", value));
+        }
+        out
+    }
+}
+
 impl<T: crate::IntoObject<3>> ::core::ops::Add<T> for Cylinder {
     type Output = crate::Object<3>;
 
@@ -305,14 +527,176 @@ impl crate::IntoObject<3> for Cylinder {
     }
 }
 
+/// A polyhedron is the most general 3D primitive solid. It can be used to create any regular or irregular shape including those with concave as well as convex features. Curved surfaces are approximated by a series of flat surfaces.
+#[derive(Clone, Default)]
+#[must_use = "Objects must be returned in order to be rendered"]
+pub struct Polyhedron {
+    pub(crate) convexity: Option<crate::types::Scalar>,
+    pub(crate) faces: Option<crate::types::VecFace>,
+    pub(crate) points: Option<crate::types::VecLength3>,
+}
+
+#[inline]
+pub fn polyhedron(
+    points: impl Into<crate::types::VecLength3>,
+    faces: impl Into<crate::types::VecFace>,
+) -> Polyhedron {
+    Polyhedron::default().points(points).faces(faces)
+}
+
+impl Polyhedron {
+    /// Integer. The convexity parameter specifies the maximum number of faces a ray intersecting the object might penetrate. This parameter is needed only for correct display of the object in OpenSCAD preview mode. It has no effect on the polyhedron rendering.
+    #[inline]
+    pub fn convexity<T: Into<crate::types::Scalar>>(mut self, convexity: T) -> Self {
+        self.convexity = Some(convexity.into());
+        self
+    }
+
+    /// Vector of faces that collectively enclose the solid. Each face is a vector containing the indices (0 based) of 3 or more points from the points vector.
+    /// Faces may be defined in any order, but the points of each face must be ordered clockwise when looking at the face from outside inward.
+    #[inline]
+    pub fn faces<T: Into<crate::types::VecFace>>(mut self, faces: T) -> Self {
+        self.faces = Some(faces.into());
+        self
+    }
+
+    /// Vector of 3d points or vertices. Each point is in turn a vector, [x,y,z], of its coordinates.
+    /// Points may be defined in any order. N points are referenced, in the order defined, as 0 to N-1.
+    #[inline]
+    pub fn points<T: Into<crate::types::VecLength3>>(mut self, points: T) -> Self {
+        self.points = Some(points.into());
+        self
+    }
+}
+
+impl ::core::fmt::Debug for Polyhedron {
+    #[allow(clippy::write_literal)]
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        let mut s = f.debug_struct("polyhedron");
+        if let Some(value) = self.convexity.as_ref() {
+            s.field("convexity", value);
+        }
+        if let Some(value) = self.faces.as_ref() {
+            s.field("faces", value);
+        }
+        if let Some(value) = self.points.as_ref() {
+            s.field("points", value);
+        }
+        s.finish()
+    }
+}
+
+impl crate::scad::Scad for Polyhedron {
+    fn assign(&self, f: &mut crate::scad::Formatter) -> crate::scad::Assignment {
+        let name = "polyhedron";
+        let args = [
+            (
+                "convexity",
+                self.convexity
+                    .as_ref()
+                    .map(|value| crate::scad::Scad::assign(value, f)),
+            ),
+            (
+                "faces",
+                self.faces
+                    .as_ref()
+                    .map(|value| crate::scad::Scad::assign(value, f)),
+            ),
+            (
+                "points",
+                self.points
+                    .as_ref()
+                    .map(|value| crate::scad::Scad::assign(value, f)),
+            ),
+        ];
+        f.call(name, args, false)
+    }
+}
+
+impl ::core::fmt::Display for Polyhedron {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        f.write_str(&crate::scad::Scad::to_scad(self))
+    }
+}
+
+impl crate::Node for Polyhedron {
+    fn name(&self) -> &str {
+        "polyhedron"
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        let mut out: Vec<(&'static str, &dyn ::core::any::Any)> = Vec::new();
+        if let Some(value) = self.convexity.as_ref() {
+            out.push(("convexity", value));
+        }
+        if let Some(value) = self.faces.as_ref() {
+            out.push(("faces", value));
+        }
+        if let Some(value) = self.points.as_ref() {
+            out.push(("points", value));
+        }
+        out
+    }
+}
+
+impl<T: crate::IntoObject<3>> ::core::ops::Add<T> for Polyhedron {
+    type Output = crate::Object<3>;
+
+    fn add(self, other: T) -> Self::Output {
+        use crate::IntoObject as _;
+        self.into_object().add(other.into_object())
+    }
+}
+
+impl<T: crate::IntoObject<3>> ::core::ops::Sub<T> for Polyhedron {
+    type Output = crate::Object<3>;
+
+    fn sub(self, other: T) -> Self::Output {
+        use crate::IntoObject as _;
+        self.into_object().sub(other.into_object())
+    }
+}
+
+impl<T: crate::IntoObject<3>> ::core::ops::BitOr<T> for Polyhedron {
+    type Output = crate::Object<3>;
+
+    fn bitor(self, other: T) -> Self::Output {
+        use crate::IntoObject as _;
+        self.into_object().bitor(other.into_object())
+    }
+}
+
+impl<F: crate::Operator<3>> ::core::ops::Shr<F> for Polyhedron {
+    type Output = F::Output;
+
+    fn shr(self, f: F) -> Self::Output {
+        use crate::IntoObject as _;
+        self.into_object() >> f
+    }
+}
+
+impl From<Polyhedron> for crate::Object<3> {
+    #[inline]
+    fn from(value: Polyhedron) -> Self {
+        crate::Object::new(value)
+    }
+}
+
+impl crate::IntoObject<3> for Polyhedron {
+    #[inline]
+    fn into_object(self) -> crate::Object<3> {
+        crate::Object::new(self)
+    }
+}
+
 /// Creates a sphere at the origin of the coordinate system.
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Default)]
 #[must_use = "Objects must be returned in order to be rendered"]
 pub struct Sphere {
-    fragment_angle: Option<crate::types::Angle>,
-    fragment_resolution: Option<crate::types::FragmentResolution>,
-    fragment_size: Option<crate::types::Length>,
-    radius: Option<crate::types::Length>,
+    pub(crate) fragment_angle: Option<crate::types::Angle>,
+    pub(crate) fragment_resolution: Option<crate::types::FragmentResolution>,
+    pub(crate) fragment_size: Option<crate::types::Length>,
+    pub(crate) radius: Option<crate::types::Length>,
 }
 
 #[inline]
@@ -412,6 +796,29 @@ impl ::core::fmt::Display for Sphere {
     }
 }
 
+impl crate::Node for Sphere {
+    fn name(&self) -> &str {
+        "sphere"
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        let mut out: Vec<(&'static str, &dyn ::core::any::Any)> = Vec::new();
+        if let Some(value) = self.fragment_angle.as_ref() {
+            out.push(("fragment_angle", value));
+        }
+        if let Some(value) = self.fragment_resolution.as_ref() {
+            out.push(("fragment_resolution", value));
+        }
+        if let Some(value) = self.fragment_size.as_ref() {
+            out.push(("fragment_size", value));
+        }
+        if let Some(value) = self.radius.as_ref() {
+            out.push(("radius", value));
+        }
+        out
+    }
+}
+
 impl<T: crate::IntoObject<3>> ::core::ops::Add<T> for Sphere {
     type Output = crate::Object<3>;
 
@@ -1,3 +1,169 @@
+/// Displays the child elements using the specified color.
+///
+/// The color can be one of the CSS/SVG color names (e.g. "red"), a hex string (e.g. "#ff0000" or "#ff000080") or a list of `[r, g, b]` or `[r, g, b, a]` channels in the range 0.0 to 1.0. Invalid literals panic, so use `parse` or `Color::try_rgba` for colors that are only known at runtime.
+///
+/// The color is only used in the preview and for export formats that support it.
+#[derive(Clone, Default)]
+#[must_use = "Objects must be returned in order to be rendered"]
+pub struct ColorOperator<const DIMENSIONS: usize> {
+    pub(crate) alpha: Option<crate::types::Scalar>,
+    pub(crate) color: Option<crate::types::Color>,
+}
+
+#[inline]
+pub fn color<const DIMENSIONS: usize>(
+    color: impl Into<crate::types::Color>,
+) -> ColorOperator<DIMENSIONS> {
+    ColorOperator::default().color(color)
+}
+
+impl<const DIMENSIONS: usize> ColorOperator<DIMENSIONS> {
+    /// Opacity of the color in the range 0.0 (transparent) to 1.0 (opaque). Overrides the alpha channel of the color, if any.
+    #[inline]
+    pub fn alpha<T: Into<crate::types::Scalar>>(mut self, alpha: T) -> Self {
+        self.alpha = Some(alpha.into());
+        self
+    }
+
+    #[inline]
+    pub fn color<T: Into<crate::types::Color>>(mut self, color: T) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+}
+
+impl<const DIMENSIONS: usize> ::core::fmt::Debug for ColorOperator<DIMENSIONS> {
+    #[allow(clippy::write_literal)]
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        let mut s = f.debug_struct("color");
+        if let Some(value) = self.alpha.as_ref() {
+            s.field("alpha", value);
+        }
+        if let Some(value) = self.color.as_ref() {
+            s.field("c", value);
+        }
+        s.finish()
+    }
+}
+
+impl<const DIMENSIONS: usize> crate::scad::Scad for ColorOperator<DIMENSIONS> {
+    fn assign(&self, f: &mut crate::scad::Formatter) -> crate::scad::Assignment {
+        let name = "color";
+        let args = [
+            (
+                "alpha",
+                self.alpha
+                    .as_ref()
+                    .map(|value| crate::scad::Scad::assign(value, f)),
+            ),
+            (
+                "c",
+                self.color
+                    .as_ref()
+                    .map(|value| crate::scad::Scad::assign(value, f)),
+            ),
+        ];
+        f.call(name, args, true)
+    }
+}
+
+impl<const DIMENSIONS: usize> ::core::fmt::Display for ColorOperator<DIMENSIONS> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        f.write_str(&crate::scad::Scad::to_scad(self))
+    }
+}
+
+impl<const DIMENSIONS: usize> crate::Node for ColorOperator<DIMENSIONS> {
+    fn name(&self) -> &str {
+        "color"
+    }
+
+    fn kind(&self) -> crate::NodeKind {
+        crate::NodeKind::Operator
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        let mut out: Vec<(&'static str, &dyn ::core::any::Any)> = Vec::new();
+        if let Some(value) = self.alpha.as_ref() {
+            out.push(("alpha", value));
+        }
+        if let Some(value) = self.color.as_ref() {
+            out.push(("color", value));
+        }
+        out
+    }
+}
+
+impl<const DIMENSIONS: usize> crate::Operator<DIMENSIONS> for ColorOperator<DIMENSIONS> {
+    type Output = crate::Object<DIMENSIONS>;
+
+    fn apply(self, child: &crate::Object<DIMENSIONS>) -> Self::Output {
+        let obj: crate::operator::Wrapped<DIMENSIONS, DIMENSIONS> = crate::operator::Wrapped {
+            parent: self.into(),
+            child: child.clone(),
+        };
+        crate::Object::new(obj)
+    }
+}
+
+impl<T: crate::IntoObject<DIMENSIONS>, const DIMENSIONS: usize> ::core::ops::Add<T>
+    for ColorOperator<DIMENSIONS>
+{
+    type Output = crate::Object<DIMENSIONS>;
+
+    fn add(self, other: T) -> Self::Output {
+        use crate::IntoObject as _;
+        self.into_object().add(other.into_object())
+    }
+}
+
+impl<T: crate::IntoObject<DIMENSIONS>, const DIMENSIONS: usize> ::core::ops::Sub<T>
+    for ColorOperator<DIMENSIONS>
+{
+    type Output = crate::Object<DIMENSIONS>;
+
+    fn sub(self, other: T) -> Self::Output {
+        use crate::IntoObject as _;
+        self.into_object().sub(other.into_object())
+    }
+}
+
+impl<T: crate::IntoObject<DIMENSIONS>, const DIMENSIONS: usize> ::core::ops::BitOr<T>
+    for ColorOperator<DIMENSIONS>
+{
+    type Output = crate::Object<DIMENSIONS>;
+
+    fn bitor(self, other: T) -> Self::Output {
+        use crate::IntoObject as _;
+        self.into_object().bitor(other.into_object())
+    }
+}
+
+impl<F: crate::Operator<DIMENSIONS>, const DIMENSIONS: usize> ::core::ops::Shr<F>
+    for ColorOperator<DIMENSIONS>
+{
+    type Output = F::Output;
+
+    fn shr(self, f: F) -> Self::Output {
+        use crate::IntoObject as _;
+        self.into_object() >> f
+    }
+}
+
+impl<const DIMENSIONS: usize> From<ColorOperator<DIMENSIONS>> for crate::Object<DIMENSIONS> {
+    #[inline]
+    fn from(value: ColorOperator<DIMENSIONS>) -> Self {
+        crate::Object::new(value)
+    }
+}
+
+impl<const DIMENSIONS: usize> crate::IntoObject<DIMENSIONS> for ColorOperator<DIMENSIONS> {
+    #[inline]
+    fn into_object(self) -> crate::Object<DIMENSIONS> {
+        crate::Object::new(self)
+    }
+}
+
 /// Displays the convex hull of child nodes.
 #[derive(Clone, Copy, Default)]
 #[must_use = "Objects must be returned in order to be rendered"]
@@ -33,6 +199,20 @@ impl<const DIMENSIONS: usize> ::core::fmt::Display for Hull<DIMENSIONS> {
     }
 }
 
+impl<const DIMENSIONS: usize> crate::Node for Hull<DIMENSIONS> {
+    fn name(&self) -> &str {
+        "hull"
+    }
+
+    fn kind(&self) -> crate::NodeKind {
+        crate::NodeKind::Operator
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        Vec::new()
+    }
+}
+
 impl<const DIMENSIONS: usize> crate::Operator<DIMENSIONS> for Hull<DIMENSIONS> {
     type Output = crate::Object<DIMENSIONS>;
 
@@ -138,6 +318,20 @@ impl<const DIMENSIONS: usize> ::core::fmt::Display for Minkowski<DIMENSIONS> {
     }
 }
 
+impl<const DIMENSIONS: usize> crate::Node for Minkowski<DIMENSIONS> {
+    fn name(&self) -> &str {
+        "minkowski"
+    }
+
+    fn kind(&self) -> crate::NodeKind {
+        crate::NodeKind::Operator
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        Vec::new()
+    }
+}
+
 impl<const DIMENSIONS: usize> crate::Operator<DIMENSIONS> for Minkowski<DIMENSIONS> {
     type Output = crate::Object<DIMENSIONS>;
 
@@ -211,10 +405,10 @@ impl<const DIMENSIONS: usize> crate::IntoObject<DIMENSIONS> for Minkowski<DIMENS
 /// Transforms the child element to a mirror of the original, as if it were the mirror image seen through a plane intersecting the origin.
 ///
 /// The argument to mirror() is the normal vector of the origin-intersecting mirror plane used, meaning the vector coming perpendicularly out of the plane. Each coordinate of the original object is altered such that it becomes equidistant on the other side of this plane from the closest point on the plane. For example, mirror([1,0,0]), corresponding to a normal vector pointing in the x-axis direction, produces an object such that all positive x coordinates become negative x coordinates, and all negative x coordinates become positive x coordinates.
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Default)]
 #[must_use = "Objects must be returned in order to be rendered"]
 pub struct Mirror<const DIMENSIONS: usize> {
-    vector: Option<crate::types::Length3>,
+    pub(crate) vector: Option<crate::types::Length3>,
 }
 
 #[inline]
@@ -262,6 +456,24 @@ impl<const DIMENSIONS: usize> ::core::fmt::Display for Mirror<DIMENSIONS> {
     }
 }
 
+impl<const DIMENSIONS: usize> crate::Node for Mirror<DIMENSIONS> {
+    fn name(&self) -> &str {
+        "mirror"
+    }
+
+    fn kind(&self) -> crate::NodeKind {
+        crate::NodeKind::Operator
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        let mut out: Vec<(&'static str, &dyn ::core::any::Any)> = Vec::new();
+        if let Some(value) = self.vector.as_ref() {
+            out.push(("vector", value));
+        }
+        out
+    }
+}
+
 impl<const DIMENSIONS: usize> crate::Operator<DIMENSIONS> for Mirror<DIMENSIONS> {
     type Output = crate::Object<DIMENSIONS>;
 
@@ -345,12 +557,12 @@ impl<const DIMENSIONS: usize> crate::IntoObject<DIMENSIONS> for Mirror<DIMENSION
 ///
 /// Fillet: offset(r=-3) offset(delta=+3) rounds all inside (concave) corners, and leaves flat walls unchanged. However, holes less than 2*r in diameter vanish.
 ///     Round: offset(r=+3) offset(delta=-3) rounds all outside (convex) corners, and leaves flat walls unchanged. However, walls less than 2*r thick vanish.
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Default)]
 #[must_use = "Objects must be returned in order to be rendered"]
 pub struct Offset<const DIMENSIONS: usize> {
-    chamfer: Option<bool>,
-    delta: Option<crate::types::Length>,
-    radius: Option<crate::types::Length>,
+    pub(crate) chamfer: Option<bool>,
+    pub(crate) delta: Option<crate::types::Length>,
+    pub(crate) radius: Option<crate::types::Length>,
 }
 
 #[inline]
@@ -432,6 +644,30 @@ impl<const DIMENSIONS: usize> ::core::fmt::Display for Offset<DIMENSIONS> {
     }
 }
 
+impl<const DIMENSIONS: usize> crate::Node for Offset<DIMENSIONS> {
+    fn name(&self) -> &str {
+        "offset"
+    }
+
+    fn kind(&self) -> crate::NodeKind {
+        crate::NodeKind::Operator
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        let mut out: Vec<(&'static str, &dyn ::core::any::Any)> = Vec::new();
+        if let Some(value) = self.chamfer.as_ref() {
+            out.push(("chamfer", value));
+        }
+        if let Some(value) = self.delta.as_ref() {
+            out.push(("delta", value));
+        }
+        if let Some(value) = self.radius.as_ref() {
+            out.push(("radius", value));
+        }
+        out
+    }
+}
+
 impl<const DIMENSIONS: usize> crate::Operator<DIMENSIONS> for Offset<DIMENSIONS> {
     type Output = crate::Object<DIMENSIONS>;
 
@@ -505,10 +741,10 @@ impl<const DIMENSIONS: usize> crate::IntoObject<DIMENSIONS> for Offset<DIMENSION
 /// Modifies the size of the child object to match the given x,y, and z.
 ///
 /// resize() is a CGAL operation, and like others such as render() operates with full geometry, so even in preview this takes time to process.
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Default)]
 #[must_use = "Objects must be returned in order to be rendered"]
 pub struct Resize<const DIMENSIONS: usize> {
-    size: Option<crate::types::Length3>,
+    pub(crate) size: Option<crate::types::Length3>,
 }
 
 #[inline]
@@ -556,6 +792,24 @@ impl<const DIMENSIONS: usize> ::core::fmt::Display for Resize<DIMENSIONS> {
     }
 }
 
+impl<const DIMENSIONS: usize> crate::Node for Resize<DIMENSIONS> {
+    fn name(&self) -> &str {
+        "resize"
+    }
+
+    fn kind(&self) -> crate::NodeKind {
+        crate::NodeKind::Operator
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        let mut out: Vec<(&'static str, &dyn ::core::any::Any)> = Vec::new();
+        if let Some(value) = self.size.as_ref() {
+            out.push(("size", value));
+        }
+        out
+    }
+}
+
 impl<const DIMENSIONS: usize> crate::Operator<DIMENSIONS> for Resize<DIMENSIONS> {
     type Output = crate::Object<DIMENSIONS>;
 
@@ -627,23 +881,100 @@ impl<const DIMENSIONS: usize> crate::IntoObject<DIMENSIONS> for Resize<DIMENSION
 }
 
 /// Rotates a child 'a degrees about the axis around an arbitrary axis.
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Default)]
 #[must_use = "Objects must be returned in order to be rendered"]
 pub struct Rotate<const DIMENSIONS: usize> {
-    angle: Option<crate::types::Angle3>,
-    value: Option<crate::types::Length3>,
+    pub(crate) angle: Option<RotateAngle>,
+    pub(crate) value: Option<crate::types::Length3>,
+}
+
+/// Values accepted by [`Rotate::angle`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum RotateAngle {
+    Angle3(crate::types::Angle3),
+    Angle(crate::types::Angle),
+}
+
+impl From<[f64; 3]> for RotateAngle {
+    #[inline]
+    fn from(value: [f64; 3]) -> Self {
+        Self::Angle3(value.into())
+    }
+}
+
+impl From<[i64; 3]> for RotateAngle {
+    #[inline]
+    fn from(value: [i64; 3]) -> Self {
+        Self::Angle3(value.into())
+    }
+}
+
+impl From<[crate::types::Angle; 3]> for RotateAngle {
+    #[inline]
+    fn from(value: [crate::types::Angle; 3]) -> Self {
+        Self::Angle3(value.into())
+    }
+}
+
+impl From<crate::types::Angle3> for RotateAngle {
+    #[inline]
+    fn from(value: crate::types::Angle3) -> Self {
+        Self::Angle3(value)
+    }
+}
+
+impl From<f64> for RotateAngle {
+    #[inline]
+    fn from(value: f64) -> Self {
+        Self::Angle(value.into())
+    }
+}
+
+impl From<i64> for RotateAngle {
+    #[inline]
+    fn from(value: i64) -> Self {
+        Self::Angle(value.into())
+    }
+}
+
+impl From<crate::Var<crate::types::Angle>> for RotateAngle {
+    #[inline]
+    fn from(value: crate::Var<crate::types::Angle>) -> Self {
+        Self::Angle(value.into())
+    }
+}
+
+impl From<&crate::Var<crate::types::Angle>> for RotateAngle {
+    #[inline]
+    fn from(value: &crate::Var<crate::types::Angle>) -> Self {
+        Self::Angle(value.into())
+    }
+}
+
+impl From<crate::types::Angle> for RotateAngle {
+    #[inline]
+    fn from(value: crate::types::Angle) -> Self {
+        Self::Angle(value)
+    }
+}
+
+impl crate::scad::Scad for RotateAngle {
+    fn assign(&self, f: &mut crate::scad::Formatter) -> crate::scad::Assignment {
+        match self {
+            Self::Angle3(value) => crate::scad::Scad::assign(value, f),
+            Self::Angle(value) => crate::scad::Scad::assign(value, f),
+        }
+    }
 }
 
 #[inline]
-pub fn rotate<const DIMENSIONS: usize>(
-    angle: impl Into<crate::types::Angle3>,
-) -> Rotate<DIMENSIONS> {
+pub fn rotate<const DIMENSIONS: usize>(angle: impl Into<RotateAngle>) -> Rotate<DIMENSIONS> {
     Rotate::default().angle(angle)
 }
 
 #[inline]
 pub fn rotate_around<const DIMENSIONS: usize>(
-    angle: impl Into<crate::types::Angle3>,
+    angle: impl Into<RotateAngle>,
     value: impl Into<crate::types::Length3>,
 ) -> Rotate<DIMENSIONS> {
     Rotate::default().angle(angle).value(value)
@@ -651,7 +982,7 @@ pub fn rotate_around<const DIMENSIONS: usize>(
 
 impl<const DIMENSIONS: usize> Rotate<DIMENSIONS> {
     #[inline]
-    pub fn angle<T: Into<crate::types::Angle3>>(mut self, angle: T) -> Self {
+    pub fn angle<T: Into<RotateAngle>>(mut self, angle: T) -> Self {
         self.angle = Some(angle.into());
         self
     }
@@ -704,6 +1035,27 @@ impl<const DIMENSIONS: usize> ::core::fmt::Display for Rotate<DIMENSIONS> {
     }
 }
 
+impl<const DIMENSIONS: usize> crate::Node for Rotate<DIMENSIONS> {
+    fn name(&self) -> &str {
+        "rotate"
+    }
+
+    fn kind(&self) -> crate::NodeKind {
+        crate::NodeKind::Operator
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        let mut out: Vec<(&'static str, &dyn ::core::any::Any)> = Vec::new();
+        if let Some(value) = self.angle.as_ref() {
+            out.push(("angle", value));
+        }
+        if let Some(value) = self.value.as_ref() {
+            out.push(("value", value));
+        }
+        out
+    }
+}
+
 impl<const DIMENSIONS: usize> crate::Operator<DIMENSIONS> for Rotate<DIMENSIONS> {
     type Output = crate::Object<DIMENSIONS>;
 
@@ -775,22 +1127,99 @@ impl<const DIMENSIONS: usize> crate::IntoObject<DIMENSIONS> for Rotate<DIMENSION
 }
 
 /// Scales its child elements using the specified vector.
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Default)]
 #[must_use = "Objects must be returned in order to be rendered"]
 pub struct Scale<const DIMENSIONS: usize> {
-    value: Option<crate::types::Scalar3>,
+    pub(crate) value: Option<ScaleValue>,
+}
+
+/// Values accepted by [`Scale::value`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum ScaleValue {
+    Scalar3(crate::types::Scalar3),
+    Scalar(crate::types::Scalar),
+}
+
+impl From<[f64; 3]> for ScaleValue {
+    #[inline]
+    fn from(value: [f64; 3]) -> Self {
+        Self::Scalar3(value.into())
+    }
+}
+
+impl From<[i64; 3]> for ScaleValue {
+    #[inline]
+    fn from(value: [i64; 3]) -> Self {
+        Self::Scalar3(value.into())
+    }
+}
+
+impl From<[crate::types::Scalar; 3]> for ScaleValue {
+    #[inline]
+    fn from(value: [crate::types::Scalar; 3]) -> Self {
+        Self::Scalar3(value.into())
+    }
+}
+
+impl From<crate::types::Scalar3> for ScaleValue {
+    #[inline]
+    fn from(value: crate::types::Scalar3) -> Self {
+        Self::Scalar3(value)
+    }
+}
+
+impl From<f64> for ScaleValue {
+    #[inline]
+    fn from(value: f64) -> Self {
+        Self::Scalar(value.into())
+    }
+}
+
+impl From<i64> for ScaleValue {
+    #[inline]
+    fn from(value: i64) -> Self {
+        Self::Scalar(value.into())
+    }
+}
+
+impl From<crate::Var<crate::types::Scalar>> for ScaleValue {
+    #[inline]
+    fn from(value: crate::Var<crate::types::Scalar>) -> Self {
+        Self::Scalar(value.into())
+    }
+}
+
+impl From<&crate::Var<crate::types::Scalar>> for ScaleValue {
+    #[inline]
+    fn from(value: &crate::Var<crate::types::Scalar>) -> Self {
+        Self::Scalar(value.into())
+    }
+}
+
+impl From<crate::types::Scalar> for ScaleValue {
+    #[inline]
+    fn from(value: crate::types::Scalar) -> Self {
+        Self::Scalar(value)
+    }
+}
+
+impl crate::scad::Scad for ScaleValue {
+    fn assign(&self, f: &mut crate::scad::Formatter) -> crate::scad::Assignment {
+        match self {
+            Self::Scalar3(value) => crate::scad::Scad::assign(value, f),
+            Self::Scalar(value) => crate::scad::Scad::assign(value, f),
+        }
+    }
 }
 
 #[inline]
-pub fn scale<const DIMENSIONS: usize>(
-    value: impl Into<crate::types::Scalar3>,
-) -> Scale<DIMENSIONS> {
+pub fn scale<const DIMENSIONS: usize>(value: impl Into<ScaleValue>) -> Scale<DIMENSIONS> {
     Scale::default().value(value)
 }
 
 impl<const DIMENSIONS: usize> Scale<DIMENSIONS> {
     #[inline]
-    pub fn value<T: Into<crate::types::Scalar3>>(mut self, value: T) -> Self {
+    pub fn value<T: Into<ScaleValue>>(mut self, value: T) -> Self {
         self.value = Some(value.into());
         self
     }
@@ -826,6 +1255,24 @@ impl<const DIMENSIONS: usize> ::core::fmt::Display for Scale<DIMENSIONS> {
     }
 }
 
+impl<const DIMENSIONS: usize> crate::Node for Scale<DIMENSIONS> {
+    fn name(&self) -> &str {
+        "scale"
+    }
+
+    fn kind(&self) -> crate::NodeKind {
+        crate::NodeKind::Operator
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        let mut out: Vec<(&'static str, &dyn ::core::any::Any)> = Vec::new();
+        if let Some(value) = self.value.as_ref() {
+            out.push(("value", value));
+        }
+        out
+    }
+}
+
 impl<const DIMENSIONS: usize> crate::Operator<DIMENSIONS> for Scale<DIMENSIONS> {
     type Output = crate::Object<DIMENSIONS>;
 
@@ -897,10 +1344,10 @@ impl<const DIMENSIONS: usize> crate::IntoObject<DIMENSIONS> for Scale<DIMENSIONS
 }
 
 /// Translates (moves) its child elements along the specified vector.
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Default)]
 #[must_use = "Objects must be returned in order to be rendered"]
 pub struct Translate<const DIMENSIONS: usize> {
-    value: Option<crate::types::Length3>,
+    pub(crate) value: Option<crate::types::Length3>,
 }
 
 #[inline]
@@ -948,6 +1395,24 @@ impl<const DIMENSIONS: usize> ::core::fmt::Display for Translate<DIMENSIONS> {
     }
 }
 
+impl<const DIMENSIONS: usize> crate::Node for Translate<DIMENSIONS> {
+    fn name(&self) -> &str {
+        "translate"
+    }
+
+    fn kind(&self) -> crate::NodeKind {
+        crate::NodeKind::Operator
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        let mut out: Vec<(&'static str, &dyn ::core::any::Any)> = Vec::new();
+        if let Some(value) = self.value.as_ref() {
+            out.push(("value", value));
+        }
+        out
+    }
+}
+
 impl<const DIMENSIONS: usize> crate::Operator<DIMENSIONS> for Translate<DIMENSIONS> {
     type Output = crate::Object<DIMENSIONS>;
 
@@ -1,11 +1,11 @@
 /// Creates a circle at the origin.
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Default)]
 #[must_use = "Objects must be returned in order to be rendered"]
 pub struct Circle {
-    fragment_angle: Option<crate::types::Angle>,
-    fragment_resolution: Option<crate::types::FragmentResolution>,
-    fragment_size: Option<crate::types::Length>,
-    radius: Option<crate::types::Length>,
+    pub(crate) fragment_angle: Option<crate::types::Angle>,
+    pub(crate) fragment_resolution: Option<crate::types::FragmentResolution>,
+    pub(crate) fragment_size: Option<crate::types::Length>,
+    pub(crate) radius: Option<crate::types::Length>,
 }
 
 #[inline]
@@ -105,6 +105,29 @@ impl ::core::fmt::Display for Circle {
     }
 }
 
+impl crate::Node for Circle {
+    fn name(&self) -> &str {
+        "circle"
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        let mut out: Vec<(&'static str, &dyn ::core::any::Any)> = Vec::new();
+        if let Some(value) = self.fragment_angle.as_ref() {
+            out.push(("fragment_angle", value));
+        }
+        if let Some(value) = self.fragment_resolution.as_ref() {
+            out.push(("fragment_resolution", value));
+        }
+        if let Some(value) = self.fragment_size.as_ref() {
+            out.push(("fragment_size", value));
+        }
+        if let Some(value) = self.radius.as_ref() {
+            out.push(("radius", value));
+        }
+        out
+    }
+}
+
 impl<T: crate::IntoObject<2>> ::core::ops::Add<T> for Circle {
     type Output = crate::Object<2>;
 
@@ -159,9 +182,9 @@ impl crate::IntoObject<2> for Circle {
 #[derive(Clone, Default)]
 #[must_use = "Objects must be returned in order to be rendered"]
 pub struct Polygon {
-    convexity: Option<crate::types::Scalar>,
-    paths: Option<crate::types::VecLength2>,
-    points: Option<crate::types::VecLength2>,
+    pub(crate) convexity: Option<crate::types::Scalar>,
+    pub(crate) paths: Option<crate::types::VecLength2>,
+    pub(crate) points: Option<crate::types::VecLength2>,
 }
 
 #[inline]
@@ -252,6 +275,26 @@ impl ::core::fmt::Display for Polygon {
     }
 }
 
+impl crate::Node for Polygon {
+    fn name(&self) -> &str {
+        "polygon"
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        let mut out: Vec<(&'static str, &dyn ::core::any::Any)> = Vec::new();
+        if let Some(value) = self.convexity.as_ref() {
+            out.push(("convexity", value));
+        }
+        if let Some(value) = self.paths.as_ref() {
+            out.push(("paths", value));
+        }
+        if let Some(value) = self.points.as_ref() {
+            out.push(("points", value));
+        }
+        out
+    }
+}
+
 impl<T: crate::IntoObject<2>> ::core::ops::Add<T> for Polygon {
     type Output = crate::Object<2>;
 
@@ -303,15 +346,94 @@ impl crate::IntoObject<2> for Polygon {
 }
 
 /// Creates a square or rectangle in the first quadrant.
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Default)]
 #[must_use = "Objects must be returned in order to be rendered"]
 pub struct Square {
-    center: Option<bool>,
-    size: Option<crate::types::Length2>,
+    pub(crate) center: Option<bool>,
+    pub(crate) size: Option<SquareSize>,
+}
+
+/// Values accepted by [`Square::size`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum SquareSize {
+    Length2(crate::types::Length2),
+    Length(crate::types::Length),
+}
+
+impl From<[f64; 2]> for SquareSize {
+    #[inline]
+    fn from(value: [f64; 2]) -> Self {
+        Self::Length2(value.into())
+    }
+}
+
+impl From<[i64; 2]> for SquareSize {
+    #[inline]
+    fn from(value: [i64; 2]) -> Self {
+        Self::Length2(value.into())
+    }
+}
+
+impl From<[crate::types::Length; 2]> for SquareSize {
+    #[inline]
+    fn from(value: [crate::types::Length; 2]) -> Self {
+        Self::Length2(value.into())
+    }
+}
+
+impl From<crate::types::Length2> for SquareSize {
+    #[inline]
+    fn from(value: crate::types::Length2) -> Self {
+        Self::Length2(value)
+    }
+}
+
+impl From<f64> for SquareSize {
+    #[inline]
+    fn from(value: f64) -> Self {
+        Self::Length(value.into())
+    }
+}
+
+impl From<i64> for SquareSize {
+    #[inline]
+    fn from(value: i64) -> Self {
+        Self::Length(value.into())
+    }
+}
+
+impl From<crate::Var<crate::types::Length>> for SquareSize {
+    #[inline]
+    fn from(value: crate::Var<crate::types::Length>) -> Self {
+        Self::Length(value.into())
+    }
+}
+
+impl From<&crate::Var<crate::types::Length>> for SquareSize {
+    #[inline]
+    fn from(value: &crate::Var<crate::types::Length>) -> Self {
+        Self::Length(value.into())
+    }
+}
+
+impl From<crate::types::Length> for SquareSize {
+    #[inline]
+    fn from(value: crate::types::Length) -> Self {
+        Self::Length(value)
+    }
+}
+
+impl crate::scad::Scad for SquareSize {
+    fn assign(&self, f: &mut crate::scad::Formatter) -> crate::scad::Assignment {
+        match self {
+            Self::Length2(value) => crate::scad::Scad::assign(value, f),
+            Self::Length(value) => crate::scad::Scad::assign(value, f),
+        }
+    }
 }
 
 #[inline]
-pub fn square(size: impl Into<crate::types::Length2>) -> Square {
+pub fn square(size: impl Into<SquareSize>) -> Square {
     Square::default().size(size)
 }
 
@@ -327,7 +449,7 @@ impl Square {
     /// single value, square with both sides this length
     /// 2 value array [x,y], rectangle with dimensions x and y
     #[inline]
-    pub fn size<T: Into<crate::types::Length2>>(mut self, size: T) -> Self {
+    pub fn size<T: Into<SquareSize>>(mut self, size: T) -> Self {
         self.size = Some(size.into());
         self
     }
@@ -374,6 +496,23 @@ impl ::core::fmt::Display for Square {
     }
 }
 
+impl crate::Node for Square {
+    fn name(&self) -> &str {
+        "square"
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        let mut out: Vec<(&'static str, &dyn ::core::any::Any)> = Vec::new();
+        if let Some(value) = self.center.as_ref() {
+            out.push(("center", value));
+        }
+        if let Some(value) = self.size.as_ref() {
+            out.push(("size", value));
+        }
+        out
+    }
+}
+
 impl<T: crate::IntoObject<2>> ::core::ops::Add<T> for Square {
     type Output = crate::Object<2>;
 
@@ -424,15 +563,14 @@ impl crate::IntoObject<2> for Square {
     }
 }
 
-///
 #[derive(Clone, Default)]
 #[must_use = "Objects must be returned in order to be rendered"]
 pub struct Text {
-    font: Option<String>,
-    halign: Option<String>,
-    size: Option<crate::types::Scalar>,
-    text: Option<String>,
-    valign: Option<String>,
+    pub(crate) font: Option<String>,
+    pub(crate) halign: Option<String>,
+    pub(crate) size: Option<crate::types::Scalar>,
+    pub(crate) text: Option<String>,
+    pub(crate) valign: Option<String>,
 }
 
 #[inline]
@@ -545,6 +683,32 @@ impl ::core::fmt::Display for Text {
     }
 }
 
+impl crate::Node for Text {
+    fn name(&self) -> &str {
+        "text"
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, &dyn ::core::any::Any)> {
+        let mut out: Vec<(&'static str, &dyn ::core::any::Any)> = Vec::new();
+        if let Some(value) = self.font.as_ref() {
+            out.push(("font", value));
+        }
+        if let Some(value) = self.halign.as_ref() {
+            out.push(("halign", value));
+        }
+        if let Some(value) = self.size.as_ref() {
+            out.push(("size", value));
+        }
+        if let Some(value) = self.text.as_ref() {
+            out.push(("text", value));
+        }
+        if let Some(value) = self.valign.as_ref() {
+            out.push(("valign", value));
+        }
+        out
+    }
+}
+
 impl<T: crate::IntoObject<2>> ::core::ops::Add<T> for Text {
     type Output = crate::Object<2>;
 
@@ -0,0 +1,11 @@
+//! Snapshots of the generated [`Projection`](crate::Projection) operator
+
+#[test]
+fn projection_test() {
+    use crate::*;
+
+    insta::assert_snapshot!("projection_operator", projection().cut(true));
+    // only 2D objects can be combined with a square
+    let outline: Object<2> = (cube(1) >> projection().cut(true)) + square(2);
+    insta::assert_snapshot!(outline);
+}
@@ -0,0 +1,235 @@
+//! Hand-written OpenSCAD code, for library features that aren't bound yet
+//!
+//! ```
+//! use rsolid::*;
+//!
+//! let part = raw::<3>("cuboid([20, 10, 5], rounding=1);").includes("<BOSL2/std.scad>");
+//! let ring = circle(5) >> raw_operator::<2, 3>("rotate_extrude(angle=270) children();");
+//!
+//! let _ = part + ring;
+//! ```
+
+use crate::{
+    operator::Wrapped,
+    scad::{self, Scad},
+    IntoObject, Node, NodeKind, Object, Operator,
+};
+use core::{fmt, ops};
+
+#[derive(Clone, Debug, Default)]
+struct Source {
+    code: String,
+    uses: Vec<String>,
+    includes: Vec<String>,
+    definitions: Vec<String>,
+}
+
+impl Source {
+    fn new(code: impl Into<String>) -> Self {
+        let code = code.into();
+        // the code is emitted as a single statement
+        let code = code.trim();
+        let code = code.strip_suffix(';').unwrap_or(code).trim_end();
+
+        Self {
+            code: code.to_string(),
+            ..Self::default()
+        }
+    }
+
+    fn assign(&self, f: &mut scad::Formatter) -> scad::Assignment {
+        for path in &self.uses {
+            f.uses(path);
+        }
+        for path in &self.includes {
+            f.includes(path);
+        }
+        for definition in &self.definitions {
+            f.definition(definition);
+        }
+        // code that's only there for its imports, like an `include`, isn't a statement itself
+        if self.code.is_empty() {
+            return scad::Assignment::Inline {
+                code: String::new(),
+            };
+        }
+        f.emit(&self.code, scad::AssignmentType::Call)
+    }
+}
+
+/// Creates an object from a single OpenSCAD statement
+#[inline]
+pub fn raw<const DIMENSIONS: usize>(code: impl Into<String>) -> Raw<DIMENSIONS> {
+    Raw(Source::new(code))
+}
+
+/// Creates an operator from a single OpenSCAD statement, which uses `children()` to place the
+/// object it's applied to
+#[inline]
+pub fn raw_operator<const DIMENSIONS_IN: usize, const DIMENSIONS_OUT: usize>(
+    code: impl Into<String>,
+) -> RawOperator<DIMENSIONS_IN, DIMENSIONS_OUT> {
+    RawOperator(Source::new(code))
+}
+
+#[derive(Clone, Debug)]
+#[must_use = "Objects must be returned in order to be rendered"]
+pub struct Raw<const DIMENSIONS: usize>(Source);
+
+#[derive(Clone, Debug)]
+#[must_use = "Operators must be applied to an object in order to be rendered"]
+pub struct RawOperator<const DIMENSIONS_IN: usize, const DIMENSIONS_OUT: usize>(Source);
+
+macro_rules! impl_source {
+    ($name:ident<$($dim:ident),*>) => {
+        impl<$(const $dim: usize),*> $name<$($dim),*> {
+            /// Imports the modules and functions of a library with `use`, like
+            /// `"<MCAD/boxes.scad>"`
+            #[inline]
+            pub fn uses(mut self, path: impl fmt::Display) -> Self {
+                self.0.uses.push(path.to_string());
+                self
+            }
+
+            /// Includes a library with `include`, which also brings in its variables, like
+            /// `"<BOSL2/std.scad>"`
+            #[inline]
+            pub fn includes(mut self, path: impl fmt::Display) -> Self {
+                self.0.includes.push(path.to_string());
+                self
+            }
+
+            /// Declares a top-level statement the code needs, like a module or function
+            /// definition, which is emitted once per file
+            #[inline]
+            pub fn definition(mut self, code: impl fmt::Display) -> Self {
+                self.0.definitions.push(code.to_string());
+                self
+            }
+        }
+
+        impl<$(const $dim: usize),*> Scad for $name<$($dim),*> {
+            #[inline]
+            fn assign(&self, f: &mut scad::Formatter) -> scad::Assignment {
+                self.0.assign(f)
+            }
+        }
+
+        impl<$(const $dim: usize),*> fmt::Display for $name<$($dim),*> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str(&self.to_scad())
+            }
+        }
+    };
+}
+
+impl_source!(Raw<DIMENSIONS>);
+impl_source!(RawOperator<DIMENSIONS_IN, DIMENSIONS_OUT>);
+
+impl<const DIMENSIONS: usize> Node for Raw<DIMENSIONS> {
+    #[inline]
+    fn name(&self) -> &str {
+        "raw"
+    }
+
+    #[inline]
+    fn parameters(&self) -> Vec<(&'static str, &dyn core::any::Any)> {
+        vec![("code", &self.0.code)]
+    }
+}
+
+impl<const DIMENSIONS_IN: usize, const DIMENSIONS_OUT: usize> Node
+    for RawOperator<DIMENSIONS_IN, DIMENSIONS_OUT>
+{
+    #[inline]
+    fn name(&self) -> &str {
+        "raw"
+    }
+
+    #[inline]
+    fn kind(&self) -> NodeKind {
+        NodeKind::Operator
+    }
+
+    #[inline]
+    fn parameters(&self) -> Vec<(&'static str, &dyn core::any::Any)> {
+        vec![("code", &self.0.code)]
+    }
+}
+
+impl<const DIMENSIONS: usize> IntoObject<DIMENSIONS> for Raw<DIMENSIONS> {
+    #[inline]
+    fn into_object(self) -> Object<DIMENSIONS> {
+        Object::new(self)
+    }
+}
+
+impl<const DIMENSIONS: usize> From<Raw<DIMENSIONS>> for Object<DIMENSIONS> {
+    #[inline]
+    fn from(value: Raw<DIMENSIONS>) -> Self {
+        Object::new(value)
+    }
+}
+
+impl<T: IntoObject<DIMENSIONS>, const DIMENSIONS: usize> ops::Add<T> for Raw<DIMENSIONS> {
+    type Output = Object<DIMENSIONS>;
+
+    fn add(self, other: T) -> Self::Output {
+        self.into_object().add(other.into_object())
+    }
+}
+
+impl<T: IntoObject<DIMENSIONS>, const DIMENSIONS: usize> ops::Sub<T> for Raw<DIMENSIONS> {
+    type Output = Object<DIMENSIONS>;
+
+    fn sub(self, other: T) -> Self::Output {
+        self.into_object().sub(other.into_object())
+    }
+}
+
+impl<T: IntoObject<DIMENSIONS>, const DIMENSIONS: usize> ops::BitOr<T> for Raw<DIMENSIONS> {
+    type Output = Object<DIMENSIONS>;
+
+    fn bitor(self, other: T) -> Self::Output {
+        self.into_object().bitor(other.into_object())
+    }
+}
+
+impl<F: Operator<DIMENSIONS>, const DIMENSIONS: usize> ops::Shr<F> for Raw<DIMENSIONS> {
+    type Output = F::Output;
+
+    fn shr(self, f: F) -> Self::Output {
+        self.into_object() >> f
+    }
+}
+
+impl<const DIMENSIONS_IN: usize, const DIMENSIONS_OUT: usize> Operator<DIMENSIONS_IN>
+    for RawOperator<DIMENSIONS_IN, DIMENSIONS_OUT>
+{
+    type Output = Object<DIMENSIONS_OUT>;
+
+    fn apply(self, child: &Object<DIMENSIONS_IN>) -> Self::Output {
+        let obj: Wrapped<DIMENSIONS_IN, DIMENSIONS_OUT> = Wrapped {
+            parent: Object::new(self),
+            child: child.clone(),
+        };
+        Object::new(obj)
+    }
+}
+
+#[test]
+fn raw_test() {
+    use crate::*;
+
+    let part = raw::<3>("cuboid([20, 10, 5], rounding=1);").includes("<BOSL2/std.scad>")
+        - raw::<3>("xcyl(l=30, d=4)").includes("<BOSL2/std.scad>");
+    let ring = circle(5).right(10)
+        >> raw_operator::<2, 3>("rotate_extrude(angle=270) children()").uses("<ring.scad>");
+    let spikes = sphere(1)
+        >> raw_operator::<3, 3>("spikes(4) children()")
+            .definition("module spikes(n) { for (i = [0:n-1]) rotate(i * 360 / n) children(); }");
+
+    assert_eq!(ring.name(), "raw");
+    assert_eq!(ring.kind(), NodeKind::Operator);
+    insta::assert_snapshot!(part + ring + spikes);
+}
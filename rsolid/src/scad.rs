@@ -1,31 +1,65 @@
 use core::fmt;
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    sync::Arc,
+};
+
+pub mod parse;
+
+pub use parse::{parse, ParseError};
 
 pub trait Scad {
     fn assign(&self, f: &mut Formatter) -> Assignment;
 
     fn to_scad(&self) -> String {
+        self.to_scad_with_warnings().0
+    }
+
+    /// Like [`to_scad`](Self::to_scad), but also returns the problems found while emitting the
+    /// code, like two customizer parameters with the same name and different defaults
+    fn to_scad_with_warnings(&self) -> (String, Vec<String>) {
         let mut formatter = Formatter::default();
         let assignment = self.assign(&mut formatter);
-        format!("{formatter}\n{assignment};")
+        let source = format!("{formatter}\n{assignment};");
+        (source, formatter.warnings)
     }
 }
 
-pub trait Parameter: 'static + fmt::Display + fmt::Debug {}
+/// A top-level variable declaration, displayed in the OpenSCAD Customizer syntax
+pub trait Parameter: 'static + Send + Sync + fmt::Display + fmt::Debug {
+    fn name(&self) -> &str;
+
+    fn group(&self) -> Option<&str>;
+}
 
 #[derive(Debug, Default)]
 pub struct Formatter {
     imports: Vec<Import>,
-    // TODO
-    #[allow(dead_code)]
-    parameters: BTreeMap<String, Box<dyn Parameter>>,
+    definitions: Vec<String>,
+    parameters: BTreeMap<String, Arc<dyn Parameter>>,
     assignments: HashMap<String, Assignment>,
     assign_idx: usize,
     outputs: BTreeMap<String, Assignment>,
+    warnings: Vec<String>,
 }
 
 impl fmt::Display for Formatter {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // the customizer only picks up parameters that come before any other statements
+        let mut parameters: Vec<_> = self.parameters.values().collect();
+        parameters.sort_by(|a, b| (a.group(), a.name()).cmp(&(b.group(), b.name())));
+
+        let mut group = None;
+        for param in parameters {
+            if param.group() != group {
+                group = param.group();
+                if let Some(group) = group {
+                    writeln!(f, "\n/* [{group}] */")?;
+                }
+            }
+            writeln!(f, "{param}")?;
+        }
+
         let mut seen_imports = HashSet::new();
         for i in &self.imports {
             if seen_imports.insert(i) {
@@ -33,6 +67,10 @@ impl fmt::Display for Formatter {
             }
         }
 
+        for definition in &self.definitions {
+            writeln!(f, "{definition}")?;
+        }
+
         let mut a: Vec<_> = self
             .assignments
             .iter()
@@ -87,6 +125,34 @@ impl Formatter {
         self.emit(v, AssignmentType::Module)
     }
 
+    pub fn parameter(&mut self, param: Arc<dyn Parameter>) {
+        let name = param.name().to_string();
+
+        if let Some(prev) = self.parameters.get(&name) {
+            if prev.to_string() != param.to_string() {
+                self.warn(format_args!(
+                    "conflicting declarations for parameter {name:?}, only the first one is used"
+                ));
+            }
+            return;
+        }
+
+        self.parameters.insert(name, param);
+    }
+
+    /// Records a problem with the emitted code, which the exporter reports as a warning
+    pub fn warn<V: fmt::Display>(&mut self, v: V) {
+        let v = v.to_string();
+        if !self.warnings.contains(&v) {
+            self.warnings.push(v);
+        }
+    }
+
+    #[inline]
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
     pub fn uses<V: fmt::Display>(&mut self, v: V) {
         self.imports.push(Import::Use(v.to_string()))
     }
@@ -95,6 +161,15 @@ impl Formatter {
         self.imports.push(Import::Include(v.to_string()))
     }
 
+    /// Declares a top-level statement, like a module or function definition, that is emitted
+    /// verbatim after the imports
+    pub fn definition<V: fmt::Display>(&mut self, v: V) {
+        let v = v.to_string();
+        if !self.definitions.contains(&v) {
+            self.definitions.push(v);
+        }
+    }
+
     pub fn call<'a, N: fmt::Display, A: IntoIterator<Item = (&'a str, Option<Assignment>)>>(
         &mut self,
         name: N,
@@ -127,6 +202,48 @@ impl Formatter {
         self.emit(out, AssignmentType::Call)
     }
 
+    pub fn function<'a, N: fmt::Display, A: IntoIterator<Item = (&'a str, Option<Assignment>)>>(
+        &mut self,
+        name: N,
+        args: A,
+    ) -> Assignment {
+        let mut out = format!("{name}(");
+
+        let mut first = true;
+        // positional arguments that were skipped but may need to be filled in with `undef`
+        let mut skipped = 0;
+        for (name, value) in args {
+            let Some(value) = value else {
+                if name.is_empty() {
+                    skipped += 1;
+                }
+                continue;
+            };
+
+            if name.is_empty() {
+                for _ in 0..core::mem::take(&mut skipped) {
+                    if !core::mem::take(&mut first) {
+                        out.push_str(", ");
+                    }
+                    out.push_str("undef");
+                }
+            }
+
+            if !core::mem::take(&mut first) {
+                out.push_str(", ");
+            }
+
+            if !name.is_empty() {
+                out.push_str(name);
+                out.push('=');
+            }
+            out.push_str(&value.to_string());
+        }
+        out.push(')');
+
+        self.value(out)
+    }
+
     pub fn output(&mut self, name: impl Into<String>, assignment: Assignment) {
         self.outputs.insert(name.into(), assignment);
     }
@@ -179,3 +296,24 @@ impl fmt::Display for Import {
         }
     }
 }
+
+#[test]
+fn function_args_test() {
+    let mut f = Formatter::default();
+    let a = f.value(1);
+    let b = f.value(2);
+
+    let positional = f.function("fn", [("", Some(a.clone())), ("", None), ("b", Some(b))]);
+    let skipped = f.function("fn", [("", None), ("", Some(a.clone())), ("", None)]);
+    let named = f.function("fn", [("a", Some(a)), ("b", None)]);
+
+    insta::assert_snapshot!(format!("{f}\n{positional};\n{skipped};\n{named};"));
+}
+
+#[test]
+fn function_value_test() {
+    use crate::{bosl::math, *};
+
+    let clamped: Scalar = math::constrain(2, 0, 1).into();
+    insta::assert_snapshot!(sphere(math::hypot(3, 4)) + sphere(Length::from(1) * clamped));
+}
@@ -0,0 +1,968 @@
+//! Parsing OpenSCAD source into objects
+//!
+//! Module calls, transforms, booleans and literals are mapped onto the matching builders, and
+//! constant expressions, `let`, `for` and `if` are evaluated while parsing. Anything that can't
+//! be mapped, like calls to user-defined modules or values from included files, is kept as raw
+//! code along with the definitions it might depend on, so the result still renders the same.
+//!
+//! ```
+//! use rsolid::{scad, Object};
+//!
+//! let part: Object<3> = scad::parse("difference() { cube(10); sphere(6); }").unwrap();
+//! assert_eq!(part.children().len(), 2);
+//! ```
+
+mod eval;
+mod syntax;
+
+use self::{
+    eval::{Binding, Env, Value},
+    syntax::{Instance, Stmt},
+};
+use crate::{
+    block::{Block, Boolean, BooleanKind},
+    hull, mirror, raw, resize, translate, AnyObject, Circle, Color, ColorOperator, Cube, Cylinder,
+    IntoObject, LinearExtrude, Object, Offset, Polygon, Polyhedron, Projection, Rotate,
+    RotateExtrude, Scale, Sphere, Square, Text,
+};
+use core::{cell::Cell, fmt, ops::Range};
+use std::collections::HashMap;
+
+/// The special variables that can be set with [`fragment_count`](crate::fragment_count) and
+/// friends instead of raw code
+const FRAGMENT_VARS: [&str; 3] = ["$fn", "$fa", "$fs"];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// The line of the error, starting from 1
+    pub line: usize,
+    /// The column of the error in characters, starting from 1
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    fn at(source: &str, offset: usize, message: impl Into<String>) -> Self {
+        let before = &source[..offset];
+        let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses OpenSCAD source into an object
+///
+/// Only syntax errors fail, anything that can't be mapped onto a builder, including shapes
+/// with the wrong number of dimensions, is kept as raw code.
+pub fn parse<const DIMENSIONS: usize>(source: &str) -> Result<Object<DIMENSIONS>, ParseError> {
+    let program = syntax::parse(source)?;
+    let mut builder = Builder {
+        source,
+        uses: vec![],
+        includes: vec![],
+        definitions: vec![],
+        failed_at: Cell::new(None),
+    };
+
+    let mut functions = HashMap::new();
+    for stmt in &program {
+        match stmt {
+            Stmt::Use { path } => builder.uses.push(path.clone()),
+            Stmt::Include { path } => builder.includes.push(path.clone()),
+            Stmt::Function { name, function, .. } => {
+                functions.insert(name.clone(), function.clone());
+            }
+            _ => {}
+        }
+    }
+
+    let mut env = Env::new(functions);
+    for stmt in &program {
+        if let Stmt::Assign {
+            name, value, span, ..
+        } = stmt
+        {
+            let value = env.eval(value);
+            if let Some(error) = env.error.take() {
+                return Err(ParseError::at(source, span.start, error));
+            }
+            env.vars.push(Binding {
+                name: name.clone(),
+                value,
+                source: None,
+            });
+        }
+    }
+    env.globals = env.vars.len();
+
+    // top-level fragment variables are set with operators if they're constant, since
+    // definitions are only emitted along with raw code
+    let fragments: Vec<_> = FRAGMENT_VARS
+        .into_iter()
+        .filter_map(|name| Some((name, env.get(name)?)))
+        .filter(|(_, value)| value.num().is_some_and(f64::is_finite))
+        .collect();
+
+    for stmt in &program {
+        match stmt {
+            Stmt::Module { span } | Stmt::Function { span, .. } => {
+                builder.definitions.push(source[span.clone()].to_string());
+            }
+            Stmt::Assign { name, span, .. }
+                if !fragments.iter().any(|(fragment, _)| fragment == name) =>
+            {
+                builder.definitions.push(source[span.clone()].to_string());
+            }
+            _ => {}
+        }
+    }
+
+    let objects = program
+        .iter()
+        .filter_map(|stmt| builder.statement(stmt, &mut env))
+        .collect();
+    if let Some(error) = env.error {
+        let offset = builder.failed_at.get().unwrap_or_default();
+        return Err(ParseError::at(source, offset, error));
+    }
+    let object = combine(objects).unwrap_or_else(|| Block(vec![]).into_object());
+
+    let object = fragments
+        .into_iter()
+        .try_fold(object, |object, (name, value)| {
+            fragment(object, name, value)
+        })
+        .unwrap_or_else(|Unmapped| unreachable!("fragment variables are numbers"));
+
+    Ok(object)
+}
+
+/// Sets a field if the argument is given
+macro_rules! set {
+    ($object:ident. $field:ident($value:expr)) => {
+        if let Some(value) = $value {
+            $object = $object.$field(value);
+        }
+    };
+}
+
+/// The error for anything that can't be mapped onto a builder
+struct Unmapped;
+
+type Mapped<T> = Result<T, Unmapped>;
+
+struct Builder<'a> {
+    source: &'a str,
+    uses: Vec<String>,
+    includes: Vec<String>,
+    definitions: Vec<String>,
+    /// The start of the innermost statement that was being built when evaluation gave up
+    failed_at: Cell<Option<usize>>,
+}
+
+impl Builder<'_> {
+    /// Remembers where evaluation gave up, if it did
+    fn check(&self, env: &Env, start: usize) {
+        if env.error.is_some() && self.failed_at.get().is_none() {
+            self.failed_at.set(Some(start));
+        }
+    }
+
+    /// Returns the source of a statement, without the trailing semicolon
+    fn code(&self, span: &Range<usize>) -> String {
+        let code = self.source[span.clone()].trim();
+        code.strip_suffix(';')
+            .unwrap_or(code)
+            .trim_end()
+            .to_string()
+    }
+
+    fn raw<const DIMENSIONS: usize>(&self, span: &Range<usize>, env: &Env) -> Object<DIMENSIONS> {
+        self.raw_code(self.code(span), env)
+    }
+
+    fn raw_code<const DIMENSIONS: usize>(&self, code: String, env: &Env) -> Object<DIMENSIONS> {
+        // the local variables aren't declared anywhere else
+        let locals: Vec<_> = env
+            .locals()
+            .iter()
+            .map(|var| match (&var.value, &var.source) {
+                (Some(value), _) => format!("{} = {value}", var.name),
+                (None, Some(source)) => format!("{} = {source}", var.name),
+                (None, None) => format!("{} = undef", var.name),
+            })
+            .collect();
+
+        let code = if locals.is_empty() || code.is_empty() {
+            code
+        } else {
+            format!("let ({}) {code}", locals.join(", "))
+        };
+
+        let mut raw = raw(code);
+        for path in &self.uses {
+            raw = raw.uses(path);
+        }
+        for path in &self.includes {
+            raw = raw.includes(path);
+        }
+        for definition in &self.definitions {
+            raw = raw.definition(definition);
+        }
+        raw.into_object()
+    }
+
+    fn statement<const DIMENSIONS: usize>(
+        &self,
+        stmt: &Stmt,
+        env: &mut Env,
+    ) -> Option<Object<DIMENSIONS>> {
+        match stmt {
+            Stmt::Instance(instance) => {
+                let object = self
+                    .instance(instance, env)
+                    .unwrap_or_else(|Unmapped| Some(self.raw(&instance.span, env)));
+                self.check(env, instance.span.start);
+                object
+            }
+            Stmt::Block(stmts) => combine(self.statements(stmts, env)),
+            Stmt::If {
+                cond,
+                then,
+                otherwise,
+                span,
+            } => {
+                let cond = env.eval(cond);
+                self.check(env, span.start);
+                match cond {
+                    Some(cond) if cond.truthy() => self.statement(then, env),
+                    Some(_) => otherwise
+                        .as_ref()
+                        .and_then(|otherwise| self.statement(otherwise, env)),
+                    None => Some(self.raw(span, env)),
+                }
+            }
+            // the included file can have top-level objects too, so it needs to be kept
+            Stmt::Include { .. } => Some(self.raw_code(String::new(), env)),
+            Stmt::Assign { .. }
+            | Stmt::Module { .. }
+            | Stmt::Function { .. }
+            | Stmt::Use { .. } => None,
+        }
+    }
+
+    /// Builds the statements of a block, which can have their own variables
+    fn statements<const DIMENSIONS: usize>(
+        &self,
+        stmts: &[Stmt],
+        env: &mut Env,
+    ) -> Vec<Object<DIMENSIONS>> {
+        let mark = env.vars.len();
+        for stmt in stmts {
+            if let Stmt::Assign {
+                name,
+                value,
+                value_span,
+                span,
+            } = stmt
+            {
+                let value = env.eval(value);
+                self.check(env, span.start);
+                env.vars.push(Binding {
+                    name: name.clone(),
+                    value,
+                    source: Some(self.source[value_span.clone()].to_string()),
+                });
+            }
+        }
+
+        let objects = stmts
+            .iter()
+            .filter_map(|stmt| self.statement(stmt, env))
+            .collect();
+        env.vars.truncate(mark);
+        objects
+    }
+
+    fn children<const DIMENSIONS: usize>(
+        &self,
+        instance: &Instance,
+        env: &mut Env,
+    ) -> Option<Object<DIMENSIONS>> {
+        combine(self.statements(&instance.children, env))
+    }
+
+    fn instance<const DIMENSIONS: usize>(
+        &self,
+        instance: &Instance,
+        env: &mut Env,
+    ) -> Mapped<Option<Object<DIMENSIONS>>> {
+        let object = match instance.name.as_str() {
+            name @ ("for" | "intersection_for") => {
+                let mut objects = vec![];
+                env.iterate(&instance.args, &mut |env| {
+                    objects.extend(self.children(instance, env));
+                    Some(())
+                })
+                .ok_or(Unmapped)?;
+
+                if name == "for" {
+                    combine(objects)
+                } else {
+                    boolean(BooleanKind::Intersection, objects)
+                }
+            }
+            "let" => {
+                let mark = env.vars.len();
+                for arg in &instance.args {
+                    let name = arg.name.clone().ok_or(Unmapped)?;
+                    let value = env.eval(&arg.value);
+                    env.vars.push(Binding {
+                        name,
+                        value,
+                        source: Some(self.source[arg.span.clone()].to_string()),
+                    });
+                }
+                let object = self.children(instance, env);
+                env.vars.truncate(mark);
+                object
+            }
+            name => {
+                let mut args = Args::eval(instance, env)?;
+                let object = self.module(name, &mut args, instance, env)?;
+                let fragments = args.finish()?;
+                match object {
+                    Some(object) => Some(
+                        fragments
+                            .into_iter()
+                            .try_fold(object, |object, (name, value)| {
+                                fragment(object, name, value)
+                            })?,
+                    ),
+                    None => None,
+                }
+            }
+        };
+
+        Ok(object.map(|object| {
+            // the modifier closest to the name applies first
+            instance
+                .modifiers
+                .iter()
+                .rev()
+                .fold(object, |object, modifier| match *modifier {
+                    "!" => object >> crate::root(),
+                    "#" => object >> crate::dbg(),
+                    "%" => object >> crate::bg(),
+                    _ => object >> crate::disable(),
+                })
+        }))
+    }
+
+    /// Maps a builtin module onto its builder
+    fn module<const DIMENSIONS: usize>(
+        &self,
+        name: &str,
+        args: &mut Args,
+        instance: &Instance,
+        env: &mut Env,
+    ) -> Mapped<Option<Object<DIMENSIONS>>> {
+        let is_shape = matches!(
+            name,
+            "cube"
+                | "sphere"
+                | "cylinder"
+                | "polyhedron"
+                | "square"
+                | "circle"
+                | "polygon"
+                | "text"
+        );
+        if is_shape {
+            if !instance.children.is_empty() {
+                return Err(Unmapped);
+            }
+            return shape(name, args).map(Some);
+        }
+
+        Ok(match name {
+            "translate" | "rotate" | "scale" | "mirror" | "resize" | "color" | "offset"
+            | "hull" => {
+                let child = self.children::<DIMENSIONS>(instance, env);
+                return operator(name, args, child);
+            }
+            "linear_extrude" | "rotate_extrude" if DIMENSIONS == 3 => {
+                let child = self.children::<2>(instance, env);
+                return operator(name, args, child);
+            }
+            "projection" if DIMENSIONS == 2 => {
+                let child = self.children::<3>(instance, env);
+                return operator(name, args, child);
+            }
+            "union" => boolean(BooleanKind::Union, self.statements(&instance.children, env)),
+            "difference" => boolean(
+                BooleanKind::Difference,
+                self.statements(&instance.children, env),
+            ),
+            "intersection" => boolean(
+                BooleanKind::Intersection,
+                self.statements(&instance.children, env),
+            ),
+            "minkowski" => boolean(
+                BooleanKind::Minkowski,
+                self.statements(&instance.children, env),
+            ),
+            _ => return Err(Unmapped),
+        })
+    }
+}
+
+/// Maps a builtin shape onto its builder
+///
+/// This is kept apart from [`Builder::module`] so the builders don't take up stack space while
+/// the children of operators are built.
+fn shape<const DIMENSIONS: usize>(name: &str, args: &mut Args) -> Mapped<Object<DIMENSIONS>> {
+    Ok(match name {
+        "cube" => {
+            let mut cube = Cube::default();
+            match args.take(0, "size") {
+                Some(Value::Num(size)) if size.is_finite() => cube = cube.size(size),
+                size => set!(cube.size(vector::<3>(size, None)?)),
+            }
+            set!(cube.center(flag(args.take(1, "center"))?));
+            fit(cube.into_object())?
+        }
+        "sphere" => {
+            let mut sphere = Sphere::default();
+            set!(sphere.radius(radius(args, 0, "r", "d")?));
+            set!(sphere.fragment_angle(num(args.take(None, "$fa"))?));
+            set!(sphere.fragment_size(num(args.take(None, "$fs"))?));
+            set!(sphere.fragment_resolution(num(args.take(None, "$fn"))?));
+            fit(sphere.into_object())?
+        }
+        "cylinder" => {
+            let mut cylinder = Cylinder::default();
+            set!(cylinder.height(num(args.take(0, "h"))?));
+            set!(cylinder.radius_bottom(radius(args, 1, "r1", "d1")?));
+            set!(cylinder.radius_top(radius(args, 2, "r2", "d2")?));
+            set!(cylinder.center(flag(args.take(3, "center"))?));
+            set!(cylinder.radius(radius(args, None, "r", "d")?));
+            fit(cylinder.into_object())?
+        }
+        "polyhedron" => {
+            let mut polyhedron = Polyhedron::default();
+            set!(polyhedron.points(points::<3>(args.take(0, "points"))?));
+            set!(polyhedron.faces(faces(args.take(1, "faces"))?));
+            set!(polyhedron.convexity(num(args.take(2, "convexity"))?));
+            fit(polyhedron.into_object())?
+        }
+        "square" => {
+            let mut square = Square::default();
+            match args.take(0, "size") {
+                Some(Value::Num(size)) if size.is_finite() => square = square.size(size),
+                size => set!(square.size(vector::<2>(size, None)?)),
+            }
+            set!(square.center(flag(args.take(1, "center"))?));
+            fit(square.into_object())?
+        }
+        "circle" => {
+            let mut circle = Circle::default();
+            set!(circle.radius(radius(args, 0, "r", "d")?));
+            set!(circle.fragment_angle(num(args.take(None, "$fa"))?));
+            set!(circle.fragment_size(num(args.take(None, "$fs"))?));
+            set!(circle.fragment_resolution(num(args.take(None, "$fn"))?));
+            fit(circle.into_object())?
+        }
+        "polygon" => {
+            // the paths can have any number of indices, which the builder doesn't support
+            if !matches!(args.take(1, "paths"), None | Some(Value::Undef)) {
+                return Err(Unmapped);
+            }
+            let mut polygon = Polygon::default();
+            set!(polygon.points(points::<2>(args.take(0, "points"))?));
+            set!(polygon.convexity(num(args.take(2, "convexity"))?));
+            fit(polygon.into_object())?
+        }
+        "text" => {
+            let mut text = Text::default();
+            set!(text.text(string(args.take(0, "text"))?));
+            set!(text.size(num(args.take(1, "size"))?));
+            set!(text.font(string(args.take(2, "font"))?));
+            set!(text.halign(string(args.take(3, "halign"))?));
+            set!(text.valign(string(args.take(4, "valign"))?));
+            fit(text.into_object())?
+        }
+        _ => return Err(Unmapped),
+    })
+}
+
+/// Maps a builtin operator onto its builder and applies it to the children
+///
+/// Like [`shape`], this is kept apart from [`Builder::module`] and only called once the
+/// children are built, so the builders don't take up stack space while they are.
+fn operator<const IN: usize, const OUT: usize>(
+    name: &str,
+    args: &mut Args,
+    child: Option<Object<IN>>,
+) -> Mapped<Option<Object<OUT>>> {
+    let object = match name {
+        "translate" => {
+            let v = vector::<3>(args.take(0, "v"), Some(0.0))?.unwrap_or_default();
+            child.map(|child| AnyObject::from(child >> translate(v)))
+        }
+        "rotate" => {
+            let mut rotate = Rotate::default();
+            match args.take(0, "a") {
+                Some(Value::Num(a)) if a.is_finite() => rotate = rotate.angle(a),
+                a => set!(rotate.angle(vector::<3>(a, Some(0.0))?)),
+            }
+            set!(rotate.value(vector::<3>(args.take(1, "v"), Some(0.0))?));
+            child.map(|child| AnyObject::from(child >> rotate))
+        }
+        "scale" => {
+            let mut scale = Scale::default();
+            match args.take(0, "v") {
+                Some(Value::Num(v)) if v.is_finite() => scale = scale.value(v),
+                v => set!(scale.value(vector::<3>(v, Some(1.0))?)),
+            }
+            child.map(|child| AnyObject::from(child >> scale))
+        }
+        "mirror" => {
+            let v = vector::<3>(args.take(0, "v"), Some(0.0))?.unwrap_or_default();
+            child.map(|child| AnyObject::from(child >> mirror(v)))
+        }
+        "resize" => {
+            let size = vector::<3>(args.take(0, "newsize"), Some(0.0))?.unwrap_or_default();
+            child.map(|child| AnyObject::from(child >> resize(size)))
+        }
+        "color" => {
+            let mut color = ColorOperator::default();
+            set!(color.color(self::color(args.take(0, "c"))?));
+            set!(color.alpha(num(args.take(1, "alpha"))?));
+            child.map(|child| AnyObject::from(child >> color))
+        }
+        "offset" => {
+            let mut offset = Offset::default();
+            set!(offset.radius(num(args.take(0, "r"))?));
+            set!(offset.delta(num(args.take(None, "delta"))?));
+            set!(offset.chamfer(flag(args.take(None, "chamfer"))?));
+            child.map(|child| AnyObject::from(child >> offset))
+        }
+        "hull" => child.map(|child| AnyObject::from(child >> hull())),
+        "linear_extrude" => {
+            let mut extrude = LinearExtrude::default();
+            set!(extrude.height(num(args.take(0, "height"))?));
+            set!(extrude.center(flag(args.take(1, "center"))?));
+            set!(extrude.convexity(num(args.take(2, "convexity"))?));
+            set!(extrude.twist(num(args.take(3, "twist"))?));
+            set!(extrude.slices(num(args.take(4, "slices"))?));
+            match args.take(5, "scale") {
+                Some(Value::Num(scale)) if scale.is_finite() => extrude = extrude.scale(scale),
+                scale => set!(extrude.scale(vector::<2>(scale, None)?)),
+            }
+            set!(extrude.segments(num(args.take(None, "segments"))?));
+            set!(extrude.vector(vector::<3>(args.take(None, "v"), None)?));
+            set!(extrude.fragment_angle(num(args.take(None, "$fa"))?));
+            set!(extrude.fragment_size(num(args.take(None, "$fs"))?));
+            set!(extrude.fragment_resolution(num(args.take(None, "$fn"))?));
+            match child {
+                Some(child) => Some(AnyObject::from(fit::<2, IN>(child)? >> extrude)),
+                None => None,
+            }
+        }
+        "rotate_extrude" => {
+            let mut extrude = RotateExtrude::default();
+            set!(extrude.angle(num(args.take(None, "angle"))?));
+            set!(extrude.convexity(num(args.take(None, "convexity"))?));
+            set!(extrude.fragment_angle(num(args.take(None, "$fa"))?));
+            set!(extrude.fragment_size(num(args.take(None, "$fs"))?));
+            set!(extrude.fragment_resolution(num(args.take(None, "$fn"))?));
+            match child {
+                Some(child) => Some(AnyObject::from(fit::<2, IN>(child)? >> extrude)),
+                None => None,
+            }
+        }
+        "projection" => {
+            let mut projection = Projection::default();
+            set!(projection.cut(flag(args.take(0, "cut"))?));
+            match child {
+                Some(child) => Some(AnyObject::from(fit::<3, IN>(child)? >> projection)),
+                None => None,
+            }
+        }
+        _ => return Err(Unmapped),
+    };
+    object
+        .map(|object| object.downcast().ok_or(Unmapped))
+        .transpose()
+}
+
+/// The evaluated arguments of a module call
+struct Args {
+    positional: Vec<Option<Value>>,
+    named: Vec<(String, Option<Value>)>,
+}
+
+impl Args {
+    fn eval(instance: &Instance, env: &mut Env) -> Mapped<Self> {
+        let mut args = Self {
+            positional: vec![],
+            named: vec![],
+        };
+        for arg in &instance.args {
+            let value = env.eval(&arg.value).ok_or(Unmapped)?;
+            match &arg.name {
+                Some(name) => args.named.push((name.clone(), Some(value))),
+                None => args.positional.push(Some(value)),
+            }
+        }
+        Ok(args)
+    }
+
+    /// Takes the argument with the given name, or at the given position
+    fn take(&mut self, position: impl Into<Option<usize>>, name: &str) -> Option<Value> {
+        if let Some((_, value)) = self.named.iter_mut().find(|(n, _)| n == name) {
+            return value.take();
+        }
+        let position = position.into()?;
+        self.positional.get_mut(position)?.take()
+    }
+
+    /// Returns the special variables that are left, or fails if there are any other arguments
+    /// that weren't used
+    fn finish(self) -> Mapped<Vec<(&'static str, Value)>> {
+        if self.positional.iter().any(Option::is_some) {
+            return Err(Unmapped);
+        }
+
+        let mut fragments = vec![];
+        for (name, value) in self.named {
+            let Some(value) = value else {
+                continue;
+            };
+            let name = FRAGMENT_VARS
+                .into_iter()
+                .find(|var| *var == name)
+                .ok_or(Unmapped)?;
+            fragments.push((name, value));
+        }
+        Ok(fragments)
+    }
+}
+
+fn combine<const DIMENSIONS: usize>(
+    mut objects: Vec<Object<DIMENSIONS>>,
+) -> Option<Object<DIMENSIONS>> {
+    match objects.len() {
+        0 => None,
+        1 => objects.pop(),
+        _ => Some(Block(objects).into_object()),
+    }
+}
+
+fn boolean<const DIMENSIONS: usize>(
+    kind: BooleanKind,
+    children: Vec<Object<DIMENSIONS>>,
+) -> Option<Object<DIMENSIONS>> {
+    if children.is_empty() {
+        return None;
+    }
+    Some(Boolean { kind, children }.into_object())
+}
+
+/// Converts an object to the expected dimensions, failing if they don't match
+fn fit<const DIMENSIONS: usize, const IN: usize>(object: Object<IN>) -> Mapped<Object<DIMENSIONS>> {
+    AnyObject::from(object).downcast().ok_or(Unmapped)
+}
+
+/// Wraps an object so it uses the given special variable
+fn fragment<const DIMENSIONS: usize>(
+    object: Object<DIMENSIONS>,
+    name: &str,
+    value: Value,
+) -> Mapped<Object<DIMENSIONS>> {
+    let value = value.num().ok_or(Unmapped)?;
+    Ok(match name {
+        "$fn" => object >> crate::fragment_count(value),
+        "$fa" => object >> crate::fragment_angle(value),
+        _ => object >> crate::fragment_size(value),
+    })
+}
+
+/// Returns `undef` values as missing, like OpenSCAD does for defaults
+#[inline]
+fn defined(value: Option<Value>) -> Option<Value> {
+    value.filter(|value| value != &Value::Undef)
+}
+
+/// Takes a number, which has to be finite since OpenSCAD can't read back `inf` or `nan`
+fn num(value: Option<Value>) -> Mapped<Option<f64>> {
+    match defined(value) {
+        Some(Value::Num(value)) if value.is_finite() => Ok(Some(value)),
+        Some(_) => Err(Unmapped),
+        None => Ok(None),
+    }
+}
+
+fn flag(value: Option<Value>) -> Mapped<Option<bool>> {
+    match defined(value) {
+        Some(Value::Bool(value)) => Ok(Some(value)),
+        Some(_) => Err(Unmapped),
+        None => Ok(None),
+    }
+}
+
+fn string(value: Option<Value>) -> Mapped<Option<String>> {
+    match defined(value) {
+        Some(Value::Str(value)) => Ok(Some(value)),
+        Some(_) => Err(Unmapped),
+        None => Ok(None),
+    }
+}
+
+/// Converts a list of numbers to an array, filling in missing values if `fill` is given
+fn vector<const N: usize>(value: Option<Value>, fill: Option<f64>) -> Mapped<Option<[f64; N]>> {
+    let Some(value) = defined(value) else {
+        return Ok(None);
+    };
+    let Value::List(values) = value else {
+        return Err(Unmapped);
+    };
+    if values.len() > N || (fill.is_none() && values.len() != N) {
+        return Err(Unmapped);
+    }
+
+    let mut out = [fill.unwrap_or_default(); N];
+    for (out, value) in out.iter_mut().zip(values) {
+        *out = num(Some(value))?.ok_or(Unmapped)?;
+    }
+    Ok(Some(out))
+}
+
+fn points<const N: usize>(value: Option<Value>) -> Mapped<Option<Vec<[f64; N]>>> {
+    let Some(value) = defined(value) else {
+        return Ok(None);
+    };
+    let Value::List(values) = value else {
+        return Err(Unmapped);
+    };
+    values
+        .into_iter()
+        .map(|point| vector::<N>(Some(point), None)?.ok_or(Unmapped))
+        .collect::<Mapped<_>>()
+        .map(Some)
+}
+
+fn faces(value: Option<Value>) -> Mapped<Option<Vec<Vec<usize>>>> {
+    let Some(value) = defined(value) else {
+        return Ok(None);
+    };
+    let Value::List(faces) = value else {
+        return Err(Unmapped);
+    };
+
+    let index = |value: Value| match value {
+        Value::Num(idx) if idx >= 0.0 && idx.fract() == 0.0 => Ok(idx as usize),
+        _ => Err(Unmapped),
+    };
+    faces
+        .into_iter()
+        .map(|face| match face {
+            Value::List(face) => face.into_iter().map(index).collect(),
+            _ => Err(Unmapped),
+        })
+        .collect::<Mapped<_>>()
+        .map(Some)
+}
+
+/// Takes a radius that can also be given as a diameter
+fn radius(
+    args: &mut Args,
+    position: impl Into<Option<usize>>,
+    radius: &str,
+    diameter: &str,
+) -> Mapped<Option<f64>> {
+    let r = num(args.take(position, radius))?;
+    let d = num(args.take(None, diameter))?;
+    Ok(r.or(d.map(|d| d / 2.0)))
+}
+
+fn color(value: Option<Value>) -> Mapped<Option<Color>> {
+    let Some(value) = defined(value) else {
+        return Ok(None);
+    };
+    match value {
+        Value::Str(name) => name.parse().map(Some).map_err(|_| Unmapped),
+        value => {
+            let [r, g, b, a] = match value {
+                Value::List(ref values) if values.len() == 4 => {
+                    vector::<4>(Some(value), None)?.unwrap_or_default()
+                }
+                value => {
+                    let [r, g, b] = vector::<3>(Some(value), None)?.unwrap_or_default();
+                    [r, g, b, 1.0]
+                }
+            };
+            Color::try_rgba(r, g, b, a).map(Some).map_err(|_| Unmapped)
+        }
+    }
+}
+
+#[test]
+fn parse_test() {
+    let part = parse::<3>(
+        r#"
+        // a box with holes through it
+        size = 10;
+        $fn = 32;
+
+        difference() {
+            cube(size, center = true);
+            for (axis = [[0, 0, 0], [90, 0, 0], [0, 90, 0]])
+                rotate(axis) cylinder(h = size * 2, d = size / 2, center = true);
+        }
+
+        translate([0, 0, size]) #sphere(r = 3);
+        color("red") linear_extrude(height = 2, twist = 45) square([4, 2]);
+        linear_extrude(3, slices = 8, scale = [1, 0.5], $fn = 16) circle(2);
+        "#,
+    )
+    .unwrap();
+
+    insta::assert_snapshot!(part);
+}
+
+#[test]
+fn parse_builders_test() {
+    use crate::{scad::Scad, *};
+
+    let parsed = parse::<2>("translate([1, 2]) { circle(d = 4); square(3, true); }").unwrap();
+    let built = (circle(2), square(3).center(true)).into_object() >> translate([1, 2, 0]);
+    assert_eq!(parsed.to_scad(), built.to_scad());
+
+    let parsed = parse::<3>("rotate(a = 90, v = [1, 0, 0]) cylinder(5, 2, 1);").unwrap();
+    let built = cone(5, 2, 1) >> rotate_around(90, [1, 0, 0]);
+    assert_eq!(parsed.to_scad(), built.to_scad());
+
+    let parsed = parse::<3>(
+        "function half(x) = x / 2;
+         points = [for (i = [0 : 3]) [i, half(i), 0]];
+         polyhedron(points, [[0, 1, 2], [0, 2, 3]]);",
+    )
+    .unwrap();
+    let points: Vec<[f64; 3]> = (0..4).map(|i| [i as f64, i as f64 / 2.0, 0.0]).collect();
+    let built = polyhedron(points, [[0, 1, 2], [0, 2, 3]]);
+    assert_eq!(parsed.to_scad(), built.to_scad());
+}
+
+#[test]
+fn parse_raw_test() {
+    let part = parse::<3>(
+        "use <MCAD/boxes.scad>
+         module peg(h) { cylinder(h, 1, 1); }
+         spacing = 5;
+
+         for (i = [0 : 1]) translate([i * spacing, 0, 0]) peg(i + 2);
+         square(2);
+         roundedBox([10, 10, 2], 1, true);",
+    )
+    .unwrap();
+
+    let names: Vec<_> = part
+        .children()
+        .iter()
+        .map(|child| child.name().to_string())
+        .collect();
+    assert_eq!(names, ["group", "raw", "raw"]);
+    insta::assert_snapshot!(part);
+
+    let included = parse::<3>("include <lib.scad>\ncube(1);").unwrap();
+    assert_eq!(
+        included.to_string(),
+        "include <lib.scad>;\nfunction _v0() = 1;\nmodule _v1() { cube(size=_v0()); }\n\n{ _v1(); };"
+    );
+    // constants that aren't finite can't be written back out
+    assert_eq!(parse::<3>("cube(1/0);").unwrap().name(), "raw");
+    assert_eq!(parse::<3>("sphere(0/0);").unwrap().name(), "raw");
+}
+
+#[test]
+fn parse_error_test() {
+    assert_eq!(
+        parse::<3>("cube(1);\ntranslate([1, 0 0]) cube(1);").unwrap_err(),
+        ParseError {
+            line: 2,
+            column: 17,
+            message: "expected `]`, found `0`".into()
+        }
+    );
+    assert_eq!(
+        parse::<3>("cube(1").unwrap_err().to_string(),
+        "1:7: expected `)`, found end of file"
+    );
+}
+
+#[test]
+fn depth_test() {
+    use crate::scad::Scad;
+
+    // recursion is evaluated up to the limit, then fails instead of overflowing the stack
+    let recursive = |n| format!("function f(n) = n <= 0 ? 0 : 1 + f(n - 1);\ncube(f({n}));");
+    let part = parse::<3>(&recursive(100)).unwrap();
+    assert!(part.to_scad().contains("100"), "{}", part.to_scad());
+    assert_eq!(
+        parse::<3>(&recursive(900)).unwrap_err().to_string(),
+        "2:1: function calls nest deeper than 128 levels"
+    );
+
+    // so do function bodies that nest deeply on every call
+    let nested = "function f(n) = n <= 0 ? 0 : [for (i = [1]) for (j = [1]) f(n - 1)];";
+    assert_eq!(
+        parse::<3>(&format!("{nested}\nx = f(100);")).unwrap_err(),
+        ParseError {
+            line: 2,
+            column: 1,
+            message: "expressions nest deeper than 512 levels when evaluated".into()
+        }
+    );
+
+    // lists can be nested through variables too
+    let lists: String = (1..=600)
+        .map(|i| format!("a{i} = [a{}];\n", i - 1))
+        .collect();
+    let err = parse::<3>(&format!("a0 = 1;\n{lists}")).unwrap_err();
+    assert_eq!(err.message, "lists nest deeper than 512 levels");
+    assert_eq!(err.line, 514);
+
+    let parens = |n| format!("cube({}1{});", "(".repeat(n), ")".repeat(n));
+    assert!(parse::<3>(&parens(100)).is_ok());
+    assert_eq!(
+        parse::<3>(&parens(100_000)).unwrap_err().to_string(),
+        "1:133: nesting is deeper than 128 levels"
+    );
+
+    let chain = |n| format!("cube({});", vec!["1"; n].join(" + "));
+    assert!(parse::<3>(&chain(100)).is_ok());
+    assert_eq!(
+        parse::<3>(&chain(100_000)).unwrap_err().to_string(),
+        "1:514: nesting is deeper than 128 levels"
+    );
+
+    let translates = |n| format!("{}cube(1);", "translate([1, 0, 0]) ".repeat(n));
+    assert!(parse::<3>(&translates(100)).is_ok());
+    assert_eq!(
+        parse::<3>(&translates(20_000)).unwrap_err().to_string(),
+        "1:2658: nesting is deeper than 128 levels"
+    );
+}
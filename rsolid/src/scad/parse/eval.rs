@@ -0,0 +1,657 @@
+//! Evaluating constant OpenSCAD expressions
+//!
+//! Evaluation returns `None` when a value can't be known while parsing, like a variable from
+//! an included file, so the code using it can be kept as raw code instead.
+
+use super::syntax::{Arg, Element, Expr, Function};
+use crate::math::sin_cos_degrees;
+use core::fmt;
+use std::{collections::HashMap, rc::Rc};
+
+/// How deep user-defined functions can recurse before giving up
+const MAX_CALLS: usize = 128;
+
+/// How deeply expressions can nest while they're evaluated, including the bodies of the
+/// functions they call, before giving up
+///
+/// The parser limits how deeply the source nests, but recursive functions can still nest
+/// evaluation without bound, so this keeps it from overflowing the stack.
+const MAX_DEPTH: usize = 512;
+
+/// How many values a range can have before giving up on expanding it
+const MAX_RANGE: f64 = 1e6;
+
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum Value {
+    Undef,
+    Bool(bool),
+    Num(f64),
+    Str(String),
+    List(Vec<Value>),
+    /// `[start : step : end]`
+    Range(f64, f64, f64),
+}
+
+impl Value {
+    pub fn truthy(&self) -> bool {
+        match self {
+            Self::Undef => false,
+            Self::Bool(value) => *value,
+            Self::Num(value) => *value != 0.0,
+            Self::Str(value) => !value.is_empty(),
+            Self::List(values) => !values.is_empty(),
+            Self::Range(..) => true,
+        }
+    }
+
+    #[inline]
+    pub fn num(&self) -> Option<f64> {
+        match self {
+            Self::Num(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns the values a `for` loop over this value iterates over
+    pub fn items(&self) -> Option<Vec<Value>> {
+        Some(match self {
+            Self::List(values) => values.clone(),
+            &Self::Range(start, step, end) => {
+                if step == 0.0 || (end - start) * step.signum() < 0.0 {
+                    return Some(vec![]);
+                }
+                let count = ((end - start) / step + 1e-9).floor();
+                if count > MAX_RANGE {
+                    return None;
+                }
+                (0..=count as usize)
+                    .map(|idx| Self::Num(start + idx as f64 * step))
+                    .collect()
+            }
+            Self::Str(value) => value.chars().map(|c| Self::Str(c.into())).collect(),
+            Self::Undef => vec![],
+            value => vec![value.clone()],
+        })
+    }
+
+    /// How many lists deep the value is
+    fn depth(&self) -> usize {
+        match self {
+            Self::List(values) => 1 + values.iter().map(Value::depth).max().unwrap_or_default(),
+            _ => 0,
+        }
+    }
+
+    fn nums(values: &[Value]) -> Option<Vec<f64>> {
+        values.iter().map(Value::num).collect()
+    }
+}
+
+/// Displays the value as an OpenSCAD literal
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Undef => f.write_str("undef"),
+            Self::Bool(value) => write!(f, "{value}"),
+            Self::Num(value) if value.is_nan() => f.write_str("(0 / 0)"),
+            Self::Num(value) if value.is_infinite() => {
+                f.write_str(if *value > 0.0 { "1e1000" } else { "-1e1000" })
+            }
+            Self::Num(value) => write!(f, "{value}"),
+            Self::Str(value) => write!(f, "{value:?}"),
+            Self::List(values) => {
+                f.write_str("[")?;
+                for (idx, value) in values.iter().enumerate() {
+                    if idx != 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                f.write_str("]")
+            }
+            Self::Range(start, step, end) => write!(f, "[{start} : {step} : {end}]"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct Binding {
+    pub name: String,
+    /// `None` if the value isn't known while parsing
+    pub value: Option<Value>,
+    /// The source of the value, for local variables that raw code might use
+    pub source: Option<String>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub(super) struct Env {
+    pub vars: Vec<Binding>,
+    /// The number of variables that are top-level assignments
+    pub globals: usize,
+    pub functions: Rc<HashMap<String, Rc<Function>>>,
+    /// Why evaluation gave up, if it nested too deeply
+    ///
+    /// Values that can't be evaluated are usually kept as raw code, but this is reported as
+    /// an error since OpenSCAD would fail on it too.
+    pub error: Option<String>,
+    calls: usize,
+    depth: usize,
+}
+
+impl Env {
+    pub fn new(functions: HashMap<String, Rc<Function>>) -> Self {
+        Self {
+            functions: Rc::new(functions),
+            ..Self::default()
+        }
+    }
+
+    /// Returns the variables that aren't top-level assignments, like `for` loop variables
+    #[inline]
+    pub fn locals(&self) -> &[Binding] {
+        &self.vars[self.globals..]
+    }
+
+    pub fn get(&self, name: &str) -> Option<Value> {
+        match self.vars.iter().rev().find(|var| var.name == name) {
+            Some(var) => var.value.clone(),
+            None if name == "PI" => Some(Value::Num(core::f64::consts::PI)),
+            // could come from an included file
+            None => None,
+        }
+    }
+
+    /// Calls `f` for every combination of the values of `for` loop assignments
+    pub fn iterate(
+        &mut self,
+        args: &[Arg],
+        f: &mut dyn FnMut(&mut Self) -> Option<()>,
+    ) -> Option<()> {
+        let Some((first, rest)) = args.split_first() else {
+            return f(self);
+        };
+
+        let name = first.name.clone()?;
+        for value in self.eval(&first.value)?.items()? {
+            self.vars.push(Binding {
+                name: name.clone(),
+                value: Some(value),
+                source: None,
+            });
+            let result = self.nested(|env| env.iterate(rest, f));
+            self.vars.pop();
+            result?;
+        }
+        Some(())
+    }
+
+    /// Records why evaluation gave up, keeping the first reason
+    fn fail(&mut self, error: impl FnOnce() -> String) {
+        self.error.get_or_insert_with(error);
+    }
+
+    /// Evaluates something a level deeper than the current one
+    fn nested<T>(&mut self, f: impl FnOnce(&mut Self) -> Option<T>) -> Option<T> {
+        if self.depth == MAX_DEPTH {
+            self.fail(|| format!("expressions nest deeper than {MAX_DEPTH} levels when evaluated"));
+            return None;
+        }
+        self.depth += 1;
+        let value = f(self);
+        self.depth -= 1;
+        value
+    }
+
+    pub fn eval(&mut self, expr: &Expr) -> Option<Value> {
+        self.nested(|env| env.eval_expr(expr))
+    }
+
+    fn eval_expr(&mut self, expr: &Expr) -> Option<Value> {
+        // this recurses for every nested expression, so anything that needs more than a few
+        // locals is kept in its own function to keep the stack frames small in debug builds
+        match expr {
+            Expr::Num(value) => Some(Value::Num(*value)),
+            Expr::Bool(value) => Some(Value::Bool(*value)),
+            Expr::Str(value) => Some(Value::Str(value.clone())),
+            Expr::Undef => Some(Value::Undef),
+            Expr::Ident(name) => self.get(name),
+            Expr::Vector(elements) => self.eval_vector(elements),
+            Expr::Range(start, step, end) => self.eval_range(start, step.as_deref(), end),
+            Expr::Unary(op, value) => self.eval_unary(op, value),
+            Expr::Binary(op, a, b) => self.eval_binary(op, a, b),
+            Expr::Ternary(cond, a, b) => {
+                let branch = if self.eval(cond)?.truthy() { a } else { b };
+                self.eval(branch)
+            }
+            Expr::Call(callee, args) => match &**callee {
+                Expr::Ident(name) => self.call(name, args),
+                _ => None,
+            },
+            Expr::Index(value, idx) => self.eval_index(value, idx),
+            Expr::Member(value, member) => self.eval_member(value, member),
+            Expr::Let(args, body) => self.eval_let(args, body),
+            Expr::Check(Some(body)) => self.eval(body),
+            Expr::Check(None) => Some(Value::Undef),
+            Expr::Lambda => None,
+        }
+    }
+
+    fn eval_vector(&mut self, elements: &[Element]) -> Option<Value> {
+        let mut values = vec![];
+        for element in elements {
+            self.element(element, &mut values)?;
+        }
+        // lists can be nested through variables without nesting evaluation, and they're
+        // cloned and displayed recursively
+        if values.iter().any(|value| value.depth() >= MAX_DEPTH) {
+            self.fail(|| format!("lists nest deeper than {MAX_DEPTH} levels"));
+            return None;
+        }
+        Some(Value::List(values))
+    }
+
+    fn eval_range(&mut self, start: &Expr, step: Option<&Expr>, end: &Expr) -> Option<Value> {
+        let mut start = self.eval(start)?.num()?;
+        let mut end = self.eval(end)?.num()?;
+        let step = match step {
+            Some(step) => self.eval(step)?.num()?,
+            None => {
+                // OpenSCAD swaps the ends of ranges without a step
+                if start > end {
+                    core::mem::swap(&mut start, &mut end);
+                }
+                1.0
+            }
+        };
+        Some(Value::Range(start, step, end))
+    }
+
+    fn eval_unary(&mut self, op: &str, value: &Expr) -> Option<Value> {
+        let value = self.eval(value)?;
+        Some(match (op, value) {
+            ("!", value) => Value::Bool(!value.truthy()),
+            ("+", value @ (Value::Num(_) | Value::List(_))) => value,
+            ("-", value) => negate(value),
+            _ => Value::Undef,
+        })
+    }
+
+    fn eval_binary(&mut self, op: &str, a: &Expr, b: &Expr) -> Option<Value> {
+        Some(match op {
+            "&&" => Value::Bool(self.eval(a)?.truthy() && self.eval(b)?.truthy()),
+            "||" => Value::Bool(self.eval(a)?.truthy() || self.eval(b)?.truthy()),
+            op => {
+                let a = self.eval(a)?;
+                let b = self.eval(b)?;
+                binary(op, a, b)
+            }
+        })
+    }
+
+    fn eval_index(&mut self, value: &Expr, idx: &Expr) -> Option<Value> {
+        let value = self.eval(value)?;
+        let idx = self.eval(idx)?;
+        Some(index(value, idx))
+    }
+
+    fn eval_member(&mut self, value: &Expr, member: &str) -> Option<Value> {
+        let value = self.eval(value)?;
+        let idx = match member {
+            "x" => 0.0,
+            "y" => 1.0,
+            "z" => 2.0,
+            _ => return Some(Value::Undef),
+        };
+        Some(index(value, Value::Num(idx)))
+    }
+
+    fn eval_let(&mut self, args: &[Arg], body: &Expr) -> Option<Value> {
+        let mark = self.vars.len();
+        for arg in args {
+            let value = self.eval(&arg.value);
+            self.vars.push(Binding {
+                name: arg.name.clone()?,
+                value,
+                source: None,
+            });
+        }
+        let value = self.eval(body);
+        self.vars.truncate(mark);
+        value
+    }
+
+    fn element(&mut self, element: &Element, out: &mut Vec<Value>) -> Option<()> {
+        self.nested(|env| env.eval_element(element, out))
+    }
+
+    fn eval_element(&mut self, element: &Element, out: &mut Vec<Value>) -> Option<()> {
+        match element {
+            Element::Expr(expr) => out.push(self.eval(expr)?),
+            Element::For(args, body) => {
+                self.iterate(args, &mut |env| env.element(body, out))?;
+            }
+            Element::If(cond, then, otherwise) => {
+                if self.eval(cond)?.truthy() {
+                    self.element(then, out)?;
+                } else if let Some(otherwise) = otherwise {
+                    self.element(otherwise, out)?;
+                }
+            }
+            Element::Each(body) => {
+                let mut values = vec![];
+                self.element(body, &mut values)?;
+                for value in values {
+                    match value {
+                        Value::List(_) | Value::Range(..) => out.extend(value.items()?),
+                        value => out.push(value),
+                    }
+                }
+            }
+            Element::Let(args, body) => {
+                let mark = self.vars.len();
+                for arg in args {
+                    let value = self.eval(&arg.value);
+                    self.vars.push(Binding {
+                        name: arg.name.clone()?,
+                        value,
+                        source: None,
+                    });
+                }
+                let result = self.element(body, out);
+                self.vars.truncate(mark);
+                result?;
+            }
+        }
+        Some(())
+    }
+
+    fn call(&mut self, name: &str, args: &[Arg]) -> Option<Value> {
+        if let Some(function) = self.functions.get(name).cloned() {
+            return self.call_function(&function, args);
+        }
+
+        if args.iter().any(|arg| arg.name.is_some()) {
+            return None;
+        }
+        let args = args
+            .iter()
+            .map(|arg| self.eval(&arg.value))
+            .collect::<Option<Vec<_>>>()?;
+        builtin(name, &args)
+    }
+
+    fn call_function(&mut self, function: &Function, args: &[Arg]) -> Option<Value> {
+        if self.calls == MAX_CALLS {
+            self.fail(|| format!("function calls nest deeper than {MAX_CALLS} levels"));
+            return None;
+        }
+
+        let mut positional = vec![];
+        let mut named = vec![];
+        for arg in args {
+            let value = self.eval(&arg.value)?;
+            match &arg.name {
+                Some(name) => named.push((name.as_str(), value)),
+                None => positional.push(value),
+            }
+        }
+
+        // functions only see the top-level variables, not the caller's
+        let mut env = Self {
+            vars: self.vars[..self.globals].to_vec(),
+            globals: self.globals,
+            functions: self.functions.clone(),
+            error: None,
+            calls: self.calls + 1,
+            depth: self.depth,
+        };
+        let value = env.call_body(function, positional, named);
+        if let Some(error) = env.error {
+            self.fail(|| error);
+        }
+        value
+    }
+
+    /// Binds the arguments of a call and evaluates the body of the function
+    fn call_body(
+        &mut self,
+        function: &Function,
+        positional: Vec<Value>,
+        mut named: Vec<(&str, Value)>,
+    ) -> Option<Value> {
+        for (idx, param) in function.params.iter().enumerate() {
+            let value = match named.iter().position(|(name, _)| *name == param.name) {
+                Some(idx) => named.swap_remove(idx).1,
+                None => match (positional.get(idx), &param.default) {
+                    (Some(value), _) => value.clone(),
+                    (None, Some(default)) => self.eval(default)?,
+                    (None, None) => Value::Undef,
+                },
+            };
+            self.vars.push(Binding {
+                name: param.name.clone(),
+                value: Some(value),
+                source: None,
+            });
+        }
+
+        self.eval(&function.body)
+    }
+}
+
+fn negate(value: Value) -> Value {
+    match value {
+        Value::Num(value) => Value::Num(-value),
+        Value::List(values) => Value::List(values.into_iter().map(negate).collect()),
+        _ => Value::Undef,
+    }
+}
+
+fn binary(op: &str, a: Value, b: Value) -> Value {
+    use Value::*;
+
+    match (op, a, b) {
+        ("==", a, b) => Bool(a == b),
+        ("!=", a, b) => Bool(a != b),
+        ("<", Num(a), Num(b)) => Bool(a < b),
+        ("<=", Num(a), Num(b)) => Bool(a <= b),
+        (">", Num(a), Num(b)) => Bool(a > b),
+        (">=", Num(a), Num(b)) => Bool(a >= b),
+        ("<", Str(a), Str(b)) => Bool(a < b),
+        ("<=", Str(a), Str(b)) => Bool(a <= b),
+        (">", Str(a), Str(b)) => Bool(a > b),
+        (">=", Str(a), Str(b)) => Bool(a >= b),
+        ("+", Num(a), Num(b)) => Num(a + b),
+        ("-", Num(a), Num(b)) => Num(a - b),
+        ("*", Num(a), Num(b)) => Num(a * b),
+        ("/", Num(a), Num(b)) => Num(a / b),
+        ("%", Num(a), Num(b)) => Num(a % b),
+        ("^", Num(a), Num(b)) => Num(a.powf(b)),
+        (op @ ("+" | "-"), List(a), List(b)) => List(
+            a.into_iter()
+                .zip(b)
+                .map(|(a, b)| binary(op, a, b))
+                .collect(),
+        ),
+        ("*" | "/", List(a), Num(b)) => {
+            List(a.into_iter().map(|a| binary(op, a, Num(b))).collect())
+        }
+        ("*", Num(a), List(b)) => List(b.into_iter().map(|b| binary(op, Num(a), b)).collect()),
+        ("*", List(a), List(b)) => multiply(a, b),
+        _ => Undef,
+    }
+}
+
+/// Multiplies vectors and matrices, like OpenSCAD's `*` on lists
+fn multiply(a: Vec<Value>, b: Vec<Value>) -> Value {
+    let dot = |a: &[f64], b: &[f64]| -> Value {
+        if a.len() == b.len() {
+            Value::Num(a.iter().zip(b).map(|(a, b)| a * b).sum())
+        } else {
+            Value::Undef
+        }
+    };
+    let rows = |m: &[Value]| -> Option<Vec<Vec<f64>>> {
+        m.iter()
+            .map(|row| match row {
+                Value::List(row) => Value::nums(row),
+                _ => None,
+            })
+            .collect()
+    };
+
+    match (Value::nums(&a), Value::nums(&b)) {
+        (Some(a), Some(b)) => dot(&a, &b),
+        (None, Some(v)) => match rows(&a) {
+            Some(m) => Value::List(m.iter().map(|row| dot(row, &v)).collect()),
+            None => Value::Undef,
+        },
+        (Some(v), None) => match rows(&b) {
+            Some(m) if m.len() == v.len() => {
+                let cols = m.first().map_or(0, Vec::len);
+                let col = |idx: usize| -> Option<Vec<f64>> {
+                    m.iter().map(|row| row.get(idx).copied()).collect()
+                };
+                Value::List(
+                    (0..cols)
+                        .map(|idx| match col(idx) {
+                            Some(col) => dot(&v, &col),
+                            None => Value::Undef,
+                        })
+                        .collect(),
+                )
+            }
+            _ => Value::Undef,
+        },
+        (None, None) => match rows(&b) {
+            Some(_) => Value::List(
+                a.into_iter()
+                    .map(|row| binary("*", row, Value::List(b.clone())))
+                    .collect(),
+            ),
+            None => Value::Undef,
+        },
+    }
+}
+
+fn index(value: Value, idx: Value) -> Value {
+    let Value::Num(idx) = idx else {
+        return Value::Undef;
+    };
+    if idx < 0.0 {
+        return Value::Undef;
+    }
+    let idx = idx as usize;
+
+    match value {
+        Value::List(mut values) if idx < values.len() => values.swap_remove(idx),
+        Value::Str(value) => value
+            .chars()
+            .nth(idx)
+            .map_or(Value::Undef, |c| Value::Str(c.into())),
+        Value::Range(start, step, end) => match idx {
+            0 => Value::Num(start),
+            1 => Value::Num(step),
+            2 => Value::Num(end),
+            _ => Value::Undef,
+        },
+        _ => Value::Undef,
+    }
+}
+
+/// Converts a value to a string like `str()`
+fn text(value: &Value) -> String {
+    match value {
+        Value::Str(value) => value.clone(),
+        value => value.to_string(),
+    }
+}
+
+fn builtin(name: &str, args: &[Value]) -> Option<Value> {
+    use Value::*;
+
+    let num = |idx: usize| args.get(idx).and_then(Value::num);
+    let unary = |f: fn(f64) -> f64| -> Option<Value> {
+        match args {
+            [Num(value)] => Some(Num(f(*value))),
+            _ => None,
+        }
+    };
+    let degrees = |f: fn(f64) -> f64| unary(f).map(|value| Num(value.num().unwrap().to_degrees()));
+
+    Some(match name {
+        "sin" => Num(sin_cos_degrees(num(0)?).0),
+        "cos" => Num(sin_cos_degrees(num(0)?).1),
+        "tan" => {
+            let (sin, cos) = sin_cos_degrees(num(0)?);
+            Num(sin / cos)
+        }
+        "asin" => degrees(f64::asin)?,
+        "acos" => degrees(f64::acos)?,
+        "atan" => degrees(f64::atan)?,
+        "atan2" => Num(num(0)?.atan2(num(1)?).to_degrees()),
+        "abs" => unary(f64::abs)?,
+        "sign" => unary(|v| if v == 0.0 { 0.0 } else { v.signum() })?,
+        "sqrt" => unary(f64::sqrt)?,
+        "exp" => unary(f64::exp)?,
+        "ln" => unary(f64::ln)?,
+        "log" if args.len() == 2 => Num(num(1)?.log(num(0)?)),
+        "log" => unary(f64::log10)?,
+        "pow" => Num(num(0)?.powf(num(1)?)),
+        "floor" => unary(f64::floor)?,
+        "ceil" => unary(f64::ceil)?,
+        "round" => unary(f64::round)?,
+        "min" | "max" => {
+            let values = match args {
+                [List(values)] => Value::nums(values)?,
+                args => Value::nums(args)?,
+            };
+            let fold = if name == "min" { f64::min } else { f64::max };
+            values.into_iter().reduce(fold).map_or(Undef, Num)
+        }
+        "len" => match args.first()? {
+            List(values) => Num(values.len() as f64),
+            Str(value) => Num(value.chars().count() as f64),
+            _ => Undef,
+        },
+        "concat" => List(
+            args.iter()
+                .flat_map(|arg| match arg {
+                    List(values) => values.clone(),
+                    value => vec![value.clone()],
+                })
+                .collect(),
+        ),
+        "norm" => match args {
+            [List(values)] => Num(Value::nums(values)?
+                .iter()
+                .map(|v| v * v)
+                .sum::<f64>()
+                .sqrt()),
+            _ => Undef,
+        },
+        "cross" => match args {
+            [List(a), List(b)] => match (Value::nums(a)?.as_slice(), Value::nums(b)?.as_slice()) {
+                ([ax, ay, az], [bx, by, bz]) => List(vec![
+                    Num(ay * bz - az * by),
+                    Num(az * bx - ax * bz),
+                    Num(ax * by - ay * bx),
+                ]),
+                ([ax, ay], [bx, by]) => Num(ax * by - ay * bx),
+                _ => Undef,
+            },
+            _ => Undef,
+        },
+        "str" => Str(args.iter().map(text).collect()),
+        "chr" => Str(Value::nums(args)?
+            .into_iter()
+            .filter_map(|code| char::from_u32(code as u32))
+            .collect()),
+        "is_undef" => Bool(matches!(args.first()?, Undef)),
+        "is_bool" => Bool(matches!(args.first()?, Bool(_))),
+        "is_num" => Bool(matches!(args.first()?, Num(value) if !value.is_nan())),
+        "is_string" => Bool(matches!(args.first()?, Str(_))),
+        "is_list" => Bool(matches!(args.first()?, List(_))),
+        _ => return None,
+    })
+}
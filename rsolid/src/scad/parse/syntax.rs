@@ -0,0 +1,841 @@
+//! Tokenizing OpenSCAD source and parsing it into a syntax tree
+
+use super::ParseError;
+use core::{fmt, ops::Range};
+use std::rc::Rc;
+
+/// A byte range in the source
+pub(super) type Span = Range<usize>;
+
+#[derive(Clone, Debug)]
+pub(super) enum Expr {
+    Num(f64),
+    Bool(bool),
+    Str(String),
+    Undef,
+    Ident(String),
+    Vector(Vec<Element>),
+    /// `[start : step : end]`
+    Range(Box<Expr>, Option<Box<Expr>>, Box<Expr>),
+    Unary(&'static str, Box<Expr>),
+    Binary(&'static str, Box<Expr>, Box<Expr>),
+    Ternary(Box<Expr>, Box<Expr>, Box<Expr>),
+    Call(Box<Expr>, Vec<Arg>),
+    Index(Box<Expr>, Box<Expr>),
+    Member(Box<Expr>, String),
+    Let(Vec<Arg>, Box<Expr>),
+    /// `echo(...) expr` and `assert(...) expr`, which pass through their expression
+    Check(Option<Box<Expr>>),
+    /// `function (x) expr`
+    Lambda,
+}
+
+/// An element of a vector literal, which can be a list comprehension
+#[derive(Clone, Debug)]
+pub(super) enum Element {
+    Expr(Expr),
+    For(Vec<Arg>, Box<Element>),
+    If(Expr, Box<Element>, Option<Box<Element>>),
+    Each(Box<Element>),
+    Let(Vec<Arg>, Box<Element>),
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct Arg {
+    pub name: Option<String>,
+    pub value: Expr,
+    /// The source of the value
+    pub span: Span,
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct Param {
+    pub name: String,
+    pub default: Option<Expr>,
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct Function {
+    pub params: Vec<Param>,
+    pub body: Expr,
+}
+
+#[derive(Clone, Debug)]
+pub(super) enum Stmt {
+    Assign {
+        name: String,
+        value: Expr,
+        value_span: Span,
+        span: Span,
+    },
+    Instance(Instance),
+    Block(Vec<Stmt>),
+    If {
+        cond: Expr,
+        then: Box<Stmt>,
+        otherwise: Option<Box<Stmt>>,
+        span: Span,
+    },
+    Module {
+        span: Span,
+    },
+    Function {
+        name: String,
+        function: Rc<Function>,
+        span: Span,
+    },
+    Use {
+        path: String,
+    },
+    Include {
+        path: String,
+    },
+}
+
+/// A module call, like `translate([1, 0, 0]) cube(1);`
+#[derive(Clone, Debug)]
+pub(super) struct Instance {
+    /// Any of `!`, `#`, `%` and `*`, in source order
+    pub modifiers: Vec<&'static str>,
+    pub name: String,
+    pub args: Vec<Arg>,
+    pub children: Vec<Stmt>,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Tok {
+    Num(f64),
+    Str(String),
+    Ident(String),
+    Sym(&'static str),
+    /// The `<path>` of `use` and `include`, including the brackets
+    Path(String),
+    Eof,
+}
+
+impl fmt::Display for Tok {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Num(value) => write!(f, "`{value}`"),
+            Self::Str(value) => write!(f, "{value:?}"),
+            Self::Ident(value) => write!(f, "`{value}`"),
+            Self::Sym(value) => write!(f, "`{value}`"),
+            Self::Path(value) => write!(f, "`{value}`"),
+            Self::Eof => f.write_str("end of file"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Token {
+    tok: Tok,
+    start: usize,
+    end: usize,
+}
+
+// longer symbols come first so they take precedence
+const SYMBOLS: [&str; 28] = [
+    "<=", ">=", "==", "!=", "&&", "||", "(", ")", "{", "}", "[", "]", ",", ";", "=", ":", "?", "!",
+    "#", "%", "*", "+", "-", "/", "<", ">", "^", ".",
+];
+
+const MODIFIERS: [&str; 4] = ["!", "#", "%", "*"];
+
+/// How deeply statements and expressions can nest before parsing gives up, so deeply nested
+/// source fails with an error instead of overflowing the stack
+const MAX_NESTING: usize = 128;
+
+fn tokenize(source: &str) -> Result<Vec<Token>, ParseError> {
+    let bytes = source.as_bytes();
+    let mut tokens = vec![];
+    let mut pos = 0;
+
+    while pos < bytes.len() {
+        let rest = &source[pos..];
+        let c = bytes[pos];
+
+        if c.is_ascii_whitespace() {
+            pos += 1;
+            continue;
+        }
+        if rest.starts_with("//") {
+            pos += rest.find('\n').unwrap_or(rest.len());
+            continue;
+        }
+        if let Some(comment) = rest.strip_prefix("/*") {
+            let end = comment
+                .find("*/")
+                .ok_or_else(|| ParseError::at(source, pos, "unterminated comment"))?;
+            pos += end + 4;
+            continue;
+        }
+
+        let start = pos;
+        let is_digit = |pos: usize| bytes.get(pos).is_some_and(u8::is_ascii_digit);
+
+        let tok = if c.is_ascii_digit() || (c == b'.' && is_digit(pos + 1)) {
+            while is_digit(pos) || bytes.get(pos) == Some(&b'.') {
+                pos += 1;
+            }
+            if matches!(bytes.get(pos), Some(b'e' | b'E')) {
+                let mut exp = pos + 1;
+                if matches!(bytes.get(exp), Some(b'+' | b'-')) {
+                    exp += 1;
+                }
+                if is_digit(exp) {
+                    pos = exp;
+                    while is_digit(pos) {
+                        pos += 1;
+                    }
+                }
+            }
+            let value = source[start..pos]
+                .parse()
+                .map_err(|_| ParseError::at(source, start, "invalid number"))?;
+            Tok::Num(value)
+        } else if c == b'"' {
+            pos += 1;
+            let mut value = String::new();
+            loop {
+                let Some(ch) = source[pos..].chars().next() else {
+                    return Err(ParseError::at(source, start, "unterminated string"));
+                };
+                pos += ch.len_utf8();
+                match ch {
+                    '"' => break,
+                    '\\' => {
+                        let Some(escaped) = source[pos..].chars().next() else {
+                            return Err(ParseError::at(source, start, "unterminated string"));
+                        };
+                        pos += escaped.len_utf8();
+                        match escaped {
+                            'n' => value.push('\n'),
+                            't' => value.push('\t'),
+                            'r' => value.push('\r'),
+                            '"' | '\\' => value.push(escaped),
+                            other => {
+                                value.push('\\');
+                                value.push(other);
+                            }
+                        }
+                    }
+                    ch => value.push(ch),
+                }
+            }
+            Tok::Str(value)
+        } else if c.is_ascii_alphabetic() || c == b'_' || c == b'$' {
+            pos += 1;
+            while bytes
+                .get(pos)
+                .is_some_and(|c| c.is_ascii_alphanumeric() || *c == b'_')
+            {
+                pos += 1;
+            }
+            let ident = &source[start..pos];
+
+            // paths aren't strings, they're only delimited by angle brackets
+            if ident == "use" || ident == "include" {
+                let after = &source[pos..];
+                let trimmed = after.trim_start();
+                if trimmed.starts_with('<') {
+                    let open = pos + after.len() - trimmed.len();
+                    let close = source[open..]
+                        .find('>')
+                        .ok_or_else(|| ParseError::at(source, open, "unterminated path"))?;
+                    let end = open + close + 1;
+
+                    tokens.push(Token {
+                        tok: Tok::Ident(ident.to_string()),
+                        start,
+                        end: pos,
+                    });
+                    tokens.push(Token {
+                        tok: Tok::Path(source[open..end].to_string()),
+                        start: open,
+                        end,
+                    });
+                    pos = end;
+                    continue;
+                }
+            }
+
+            Tok::Ident(ident.to_string())
+        } else {
+            let sym = SYMBOLS
+                .into_iter()
+                .find(|sym| rest.starts_with(sym))
+                .ok_or_else(|| {
+                    let ch = rest.chars().next().unwrap_or_default();
+                    ParseError::at(source, pos, format!("unexpected character {ch:?}"))
+                })?;
+            pos += sym.len();
+            Tok::Sym(sym)
+        };
+
+        tokens.push(Token {
+            tok,
+            start,
+            end: pos,
+        });
+    }
+
+    tokens.push(Token {
+        tok: Tok::Eof,
+        start: source.len(),
+        end: source.len(),
+    });
+    Ok(tokens)
+}
+
+pub(super) fn parse(source: &str) -> Result<Vec<Stmt>, ParseError> {
+    let mut parser = Parser {
+        source,
+        tokens: tokenize(source)?,
+        pos: 0,
+        depth: 0,
+    };
+
+    let mut program = vec![];
+    while parser.peek() != &Tok::Eof {
+        program.push(parser.statement()?);
+    }
+    Ok(program)
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    tokens: Vec<Token>,
+    pos: usize,
+    /// How deeply the current statement or expression is nested
+    depth: usize,
+}
+
+impl Parser<'_> {
+    #[inline]
+    fn peek(&self) -> &Tok {
+        self.peek_at(0)
+    }
+
+    #[inline]
+    fn peek_at(&self, offset: usize) -> &Tok {
+        let idx = (self.pos + offset).min(self.tokens.len() - 1);
+        &self.tokens[idx].tok
+    }
+
+    #[inline]
+    fn next(&mut self) -> Token {
+        let token = self.tokens[self.pos].clone();
+        if token.tok != Tok::Eof {
+            self.pos += 1;
+        }
+        token
+    }
+
+    /// The start of the next token
+    #[inline]
+    fn start(&self) -> usize {
+        self.tokens[self.pos].start
+    }
+
+    /// The end of the last token that was consumed
+    #[inline]
+    fn end(&self) -> usize {
+        self.pos
+            .checked_sub(1)
+            .map_or(0, |idx| self.tokens[idx].end)
+    }
+
+    #[inline]
+    fn is_sym(&self, sym: &str) -> bool {
+        matches!(self.peek(), Tok::Sym(s) if *s == sym)
+    }
+
+    #[inline]
+    fn is_ident(&self, ident: &str) -> bool {
+        matches!(self.peek(), Tok::Ident(s) if s == ident)
+    }
+
+    fn eat(&mut self, sym: &str) -> bool {
+        let found = self.is_sym(sym);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, sym: &str) -> Result<(), ParseError> {
+        if self.eat(sym) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected `{sym}`")))
+        }
+    }
+
+    fn ident(&mut self) -> Result<String, ParseError> {
+        match self.peek() {
+            Tok::Ident(ident) => {
+                let ident = ident.clone();
+                self.pos += 1;
+                Ok(ident)
+            }
+            _ => Err(self.error("expected an identifier")),
+        }
+    }
+
+    fn error(&self, message: impl fmt::Display) -> ParseError {
+        let token = &self.tokens[self.pos];
+        ParseError::at(
+            self.source,
+            token.start,
+            format!("{message}, found {}", token.tok),
+        )
+    }
+
+    /// Goes one level deeper, failing if the source nests too deeply
+    ///
+    /// The depth is only restored on success, since errors end the parse.
+    fn deeper(&mut self) -> Result<(), ParseError> {
+        if self.depth == MAX_NESTING {
+            return Err(ParseError::at(
+                self.source,
+                self.start(),
+                format!("nesting is deeper than {MAX_NESTING} levels"),
+            ));
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    /// Parses something a level deeper than the current one
+    fn nested<T>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<T, ParseError> {
+        self.deeper()?;
+        let value = f(self)?;
+        self.depth -= 1;
+        Ok(value)
+    }
+
+    fn statements(&mut self) -> Result<Vec<Stmt>, ParseError> {
+        let mut stmts = vec![];
+        while !self.eat("}") {
+            if self.peek() == &Tok::Eof {
+                return Err(self.error("expected `}`"));
+            }
+            stmts.push(self.statement()?);
+        }
+        Ok(stmts)
+    }
+
+    fn statement(&mut self) -> Result<Stmt, ParseError> {
+        self.nested(Self::single_statement)
+    }
+
+    fn single_statement(&mut self) -> Result<Stmt, ParseError> {
+        let start = self.start();
+        let next_is =
+            |parser: &Self, sym: &str| matches!(parser.peek_at(1), Tok::Sym(s) if *s == sym);
+
+        match self.peek() {
+            Tok::Sym(";") => {
+                self.pos += 1;
+                Ok(Stmt::Block(vec![]))
+            }
+            Tok::Sym("{") => {
+                self.pos += 1;
+                Ok(Stmt::Block(self.statements()?))
+            }
+            Tok::Ident(ident) if ident == "module" => {
+                self.pos += 1;
+                self.ident()?;
+                self.expect("(")?;
+                self.params()?;
+                self.statement()?;
+                Ok(Stmt::Module {
+                    span: start..self.end(),
+                })
+            }
+            Tok::Ident(ident)
+                if ident == "function" && matches!(self.peek_at(1), Tok::Ident(_)) =>
+            {
+                self.pos += 1;
+                let name = self.ident()?;
+                self.expect("(")?;
+                let params = self.params()?;
+                self.expect("=")?;
+                let body = self.expr()?;
+                self.expect(";")?;
+                Ok(Stmt::Function {
+                    name,
+                    function: Rc::new(Function { params, body }),
+                    span: start..self.end(),
+                })
+            }
+            Tok::Ident(ident) if matches!(self.peek_at(1), Tok::Path(_)) => {
+                let include = ident == "include";
+                self.pos += 1;
+                let Tok::Path(path) = self.next().tok else {
+                    unreachable!()
+                };
+                self.eat(";");
+                Ok(if include {
+                    Stmt::Include { path }
+                } else {
+                    Stmt::Use { path }
+                })
+            }
+            Tok::Ident(ident) if ident == "if" && next_is(self, "(") => {
+                self.pos += 2;
+                let cond = self.expr()?;
+                self.expect(")")?;
+                let then = Box::new(self.statement()?);
+                let otherwise = if self.is_ident("else") {
+                    self.pos += 1;
+                    Some(Box::new(self.statement()?))
+                } else {
+                    None
+                };
+                Ok(Stmt::If {
+                    cond,
+                    then,
+                    otherwise,
+                    span: start..self.end(),
+                })
+            }
+            Tok::Ident(_) if next_is(self, "=") => {
+                let name = self.ident()?;
+                self.pos += 1;
+                let value_start = self.start();
+                let value = self.expr()?;
+                let value_span = value_start..self.end();
+                self.expect(";")?;
+                Ok(Stmt::Assign {
+                    name,
+                    value,
+                    value_span,
+                    span: start..self.end(),
+                })
+            }
+            Tok::Ident(_) => Ok(Stmt::Instance(self.instance()?)),
+            Tok::Sym(sym) if MODIFIERS.contains(sym) => Ok(Stmt::Instance(self.instance()?)),
+            _ => Err(self.error("expected a statement")),
+        }
+    }
+
+    fn instance(&mut self) -> Result<Instance, ParseError> {
+        let start = self.start();
+
+        let mut modifiers = vec![];
+        while let Tok::Sym(sym) = self.peek() {
+            let Some(modifier) = MODIFIERS.into_iter().find(|m| m == sym) else {
+                break;
+            };
+            modifiers.push(modifier);
+            self.pos += 1;
+        }
+
+        let name = self.ident()?;
+        self.expect("(")?;
+        let args = self.args()?;
+
+        let children = if self.eat(";") {
+            vec![]
+        } else if self.eat("{") {
+            self.statements()?
+        } else {
+            vec![self.statement()?]
+        };
+
+        Ok(Instance {
+            modifiers,
+            name,
+            args,
+            children,
+            span: start..self.end(),
+        })
+    }
+
+    /// Parses arguments up to and including the closing parenthesis
+    fn args(&mut self) -> Result<Vec<Arg>, ParseError> {
+        let mut args = vec![];
+        loop {
+            if self.eat(")") {
+                break;
+            }
+
+            let name = match (self.peek(), self.peek_at(1)) {
+                (Tok::Ident(name), Tok::Sym("=")) => {
+                    let name = name.clone();
+                    self.pos += 2;
+                    Some(name)
+                }
+                _ => None,
+            };
+            let start = self.start();
+            let value = self.expr()?;
+            args.push(Arg {
+                name,
+                value,
+                span: start..self.end(),
+            });
+
+            if !self.eat(",") {
+                self.expect(")")?;
+                break;
+            }
+        }
+        Ok(args)
+    }
+
+    fn params(&mut self) -> Result<Vec<Param>, ParseError> {
+        let start = self.start();
+        self.args()?
+            .into_iter()
+            .map(|arg| match arg {
+                Arg {
+                    name: Some(name),
+                    value,
+                    ..
+                } => Ok(Param {
+                    name,
+                    default: Some(value),
+                }),
+                Arg {
+                    value: Expr::Ident(name),
+                    ..
+                } => Ok(Param {
+                    name,
+                    default: None,
+                }),
+                _ => Err(ParseError::at(
+                    self.source,
+                    start,
+                    "expected a parameter name",
+                )),
+            })
+            .collect()
+    }
+
+    fn expr(&mut self) -> Result<Expr, ParseError> {
+        self.nested(Self::ternary)
+    }
+
+    fn ternary(&mut self) -> Result<Expr, ParseError> {
+        let cond = self.binary(0)?;
+        if !self.eat("?") {
+            return Ok(cond);
+        }
+        let a = self.expr()?;
+        self.expect(":")?;
+        let b = self.expr()?;
+        Ok(Expr::Ternary(Box::new(cond), Box::new(a), Box::new(b)))
+    }
+
+    /// Parses operators that bind at least as tightly as `level`, by precedence climbing so
+    /// parentheses only nest a few calls deep
+    fn binary(&mut self, level: usize) -> Result<Expr, ParseError> {
+        const LEVELS: [&[&str]; 6] = [
+            &["||"],
+            &["&&"],
+            &["==", "!="],
+            &["<", "<=", ">", ">="],
+            &["+", "-"],
+            &["*", "/", "%"],
+        ];
+
+        let depth = self.depth;
+        let mut lhs = self.unary()?;
+        while let Tok::Sym(sym) = self.peek() {
+            let Some((op_level, op)) = LEVELS
+                .iter()
+                .enumerate()
+                .skip(level)
+                .find_map(|(idx, ops)| Some((idx, *ops.iter().find(|op| *op == sym)?)))
+            else {
+                break;
+            };
+            self.pos += 1;
+            // chains of operators nest the left side, which is walked recursively later on
+            self.deeper()?;
+            // everything is left associative, so the right side only takes tighter operators
+            let rhs = self.binary(op_level + 1)?;
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(rhs));
+        }
+        self.depth = depth;
+        Ok(lhs)
+    }
+
+    fn unary(&mut self) -> Result<Expr, ParseError> {
+        if let Tok::Sym(op @ ("-" | "+" | "!")) = self.peek() {
+            let op = *op;
+            self.pos += 1;
+            return Ok(Expr::Unary(op, Box::new(self.nested(Self::unary)?)));
+        }
+
+        let base = self.postfix()?;
+        if self.eat("^") {
+            // exponents are right associative
+            let exponent = self.nested(Self::unary)?;
+            return Ok(Expr::Binary("^", Box::new(base), Box::new(exponent)));
+        }
+        Ok(base)
+    }
+
+    fn postfix(&mut self) -> Result<Expr, ParseError> {
+        let depth = self.depth;
+        let mut expr = self.primary()?;
+        loop {
+            // like operators, chained calls and indices nest the left side
+            if matches!(self.peek(), Tok::Sym("(" | "[" | ".")) {
+                self.deeper()?;
+            }
+            if self.eat("(") {
+                expr = Expr::Call(Box::new(expr), self.args()?);
+            } else if self.eat("[") {
+                let idx = self.expr()?;
+                self.expect("]")?;
+                expr = Expr::Index(Box::new(expr), Box::new(idx));
+            } else if self.eat(".") {
+                expr = Expr::Member(Box::new(expr), self.ident()?);
+            } else {
+                self.depth = depth;
+                return Ok(expr);
+            }
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expr, ParseError> {
+        let call = matches!(self.peek_at(1), Tok::Sym("("));
+
+        Ok(match self.peek().clone() {
+            Tok::Num(value) => {
+                self.pos += 1;
+                Expr::Num(value)
+            }
+            Tok::Str(value) => {
+                self.pos += 1;
+                Expr::Str(value)
+            }
+            Tok::Ident(ident) => {
+                self.pos += 1;
+                match ident.as_str() {
+                    "true" => Expr::Bool(true),
+                    "false" => Expr::Bool(false),
+                    "undef" => Expr::Undef,
+                    "let" if call => {
+                        self.pos += 1;
+                        let args = self.args()?;
+                        Expr::Let(args, Box::new(self.expr()?))
+                    }
+                    "assert" | "echo" if call => {
+                        self.pos += 1;
+                        self.args()?;
+                        let ends = matches!(
+                            self.peek(),
+                            Tok::Sym(";" | ")" | "," | "]" | "}" | ":") | Tok::Eof
+                        );
+                        let expr = if ends {
+                            None
+                        } else {
+                            Some(Box::new(self.expr()?))
+                        };
+                        Expr::Check(expr)
+                    }
+                    "function" if call => {
+                        self.pos += 1;
+                        self.params()?;
+                        self.expr()?;
+                        Expr::Lambda
+                    }
+                    _ => Expr::Ident(ident),
+                }
+            }
+            Tok::Sym("(") => {
+                self.pos += 1;
+                let expr = self.expr()?;
+                self.expect(")")?;
+                expr
+            }
+            Tok::Sym("[") => {
+                self.pos += 1;
+                self.vector()?
+            }
+            _ => return Err(self.error("expected an expression")),
+        })
+    }
+
+    /// Parses a vector or range after the opening bracket
+    fn vector(&mut self) -> Result<Expr, ParseError> {
+        if self.eat("]") {
+            return Ok(Expr::Vector(vec![]));
+        }
+
+        let start = self.start();
+        let first = self.element()?;
+
+        if self.eat(":") {
+            let Element::Expr(first) = first else {
+                return Err(ParseError::at(self.source, start, "expected a range"));
+            };
+            let second = self.expr()?;
+            let range = if self.eat(":") {
+                let end = self.expr()?;
+                Expr::Range(Box::new(first), Some(Box::new(second)), Box::new(end))
+            } else {
+                Expr::Range(Box::new(first), None, Box::new(second))
+            };
+            self.expect("]")?;
+            return Ok(range);
+        }
+
+        let mut elements = vec![first];
+        while self.eat(",") {
+            if self.is_sym("]") {
+                break;
+            }
+            elements.push(self.element()?);
+        }
+        self.expect("]")?;
+        Ok(Expr::Vector(elements))
+    }
+
+    fn element(&mut self) -> Result<Element, ParseError> {
+        let call = matches!(self.peek_at(1), Tok::Sym("("));
+        let Tok::Ident(ident) = self.peek() else {
+            return Ok(Element::Expr(self.expr()?));
+        };
+
+        Ok(match ident.as_str() {
+            "for" if call => {
+                self.pos += 2;
+                let args = self.args()?;
+                Element::For(args, Box::new(self.nested(Self::element)?))
+            }
+            "if" if call => {
+                self.pos += 2;
+                let cond = self.expr()?;
+                self.expect(")")?;
+                let then = Box::new(self.nested(Self::element)?);
+                let otherwise = if self.is_ident("else") {
+                    self.pos += 1;
+                    Some(Box::new(self.nested(Self::element)?))
+                } else {
+                    None
+                };
+                Element::If(cond, then, otherwise)
+            }
+            "each" => {
+                self.pos += 1;
+                Element::Each(Box::new(self.nested(Self::element)?))
+            }
+            "let" if call => {
+                self.pos += 2;
+                let args = self.args()?;
+                Element::Let(args, Box::new(self.nested(Self::element)?))
+            }
+            _ => Element::Expr(self.expr()?),
+        })
+    }
+}
@@ -1 +1,6 @@
+pub mod curve;
+pub mod geometry;
+pub mod loft;
+mod skin;
+pub mod sweep;
 pub mod triangle;
@@ -0,0 +1,441 @@
+//! Smooth curves for organic outlines, flattened into points for `polygon`
+//!
+//! Each curve is split into as few straight lines as keep it within its
+//! [tolerance](Bezier::tolerance) of the true curve, so tight bends get more points than gentle
+//! ones. Curves turn into points wherever a list of points is expected:
+//!
+//! ```
+//! use rsolid::{curve::{CatmullRom, Curve}, *};
+//!
+//! let outline = CatmullRom::new([[0.0, 0.0], [20.0, 5.0], [25.0, 20.0], [5.0, 15.0]]).closed(true);
+//! let _ = polygon(outline) >> linear_extrude(3);
+//!
+//! // a vase, from the outline of its side
+//! let side = CatmullRom::new([[10.0, 0.0], [14.0, 20.0], [6.0, 40.0], [9.0, 60.0]]);
+//! let _ = polygon(side.profile()) >> rotate_extrude();
+//! ```
+
+use crate::math::Vec2;
+
+/// How far flattened curves may stray from the true curve, in mm, unless set otherwise
+pub(crate) const TOLERANCE: f64 = 0.01;
+
+/// A curve that can be flattened into points
+pub trait Curve {
+    /// The points along the curve, from its start to its end
+    ///
+    /// Closed curves don't repeat their first point at the end.
+    fn points(&self) -> Vec<[f64; 2]>;
+
+    /// The points closed back along the Y axis, as the profile of a solid for `rotate_extrude`
+    ///
+    /// The curve has to stay on one side of the Y axis, as `rotate_extrude` needs.
+    fn profile(&self) -> Vec<[f64; 2]> {
+        let mut points = self.points();
+        let (Some(&[x0, y0]), Some(&[x1, y1])) = (points.first(), points.last()) else {
+            return points;
+        };
+        if x1 != 0.0 {
+            points.push([0.0, y1]);
+        }
+        if x0 != 0.0 {
+            points.push([0.0, y0]);
+        }
+        points
+    }
+}
+
+macro_rules! impl_curve {
+    ($name:ident) => {
+        impl $name {
+            /// Sets how far the points may stray from the true curve, in mm
+            #[inline]
+            pub fn tolerance(mut self, tolerance: f64) -> Self {
+                self.tolerance = tolerance;
+                self
+            }
+        }
+
+        impl Curve for $name {
+            fn points(&self) -> Vec<[f64; 2]> {
+                flatten(&self.pieces(), self.is_closed(), self.tolerance)
+            }
+        }
+
+        impl IntoIterator for $name {
+            type Item = [f64; 2];
+            type IntoIter = std::vec::IntoIter<[f64; 2]>;
+
+            #[inline]
+            fn into_iter(self) -> Self::IntoIter {
+                self.points().into_iter()
+            }
+        }
+
+        impl IntoIterator for &$name {
+            type Item = [f64; 2];
+            type IntoIter = std::vec::IntoIter<[f64; 2]>;
+
+            #[inline]
+            fn into_iter(self) -> Self::IntoIter {
+                self.points().into_iter()
+            }
+        }
+    };
+}
+
+impl_curve!(Bezier);
+impl_curve!(CatmullRom);
+impl_curve!(BSpline);
+
+/// A Bézier curve, which starts and ends on its first and last control points and is pulled
+/// towards the others
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bezier {
+    controls: Vec<Vec2>,
+    tolerance: f64,
+}
+
+impl Bezier {
+    #[inline]
+    pub fn quadratic(start: [f64; 2], control: [f64; 2], end: [f64; 2]) -> Self {
+        Self::new([start, control, end])
+    }
+
+    #[inline]
+    pub fn cubic(start: [f64; 2], control1: [f64; 2], control2: [f64; 2], end: [f64; 2]) -> Self {
+        Self::new([start, control1, control2, end])
+    }
+
+    fn new(controls: impl IntoIterator<Item = [f64; 2]>) -> Self {
+        Self {
+            controls: controls.into_iter().map(Vec2::from).collect(),
+            tolerance: TOLERANCE,
+        }
+    }
+
+    /// The point on the curve at `t`, which goes from 0 at the start to 1 at the end
+    pub fn at(&self, t: f64) -> [f64; 2] {
+        let mut points = self.controls.clone();
+        while points.len() > 1 {
+            points = points
+                .windows(2)
+                .map(|pair| pair[0] * (1.0 - t) + pair[1] * t)
+                .collect();
+        }
+        points[0].into()
+    }
+
+    fn pieces(&self) -> Vec<Vec<Vec2>> {
+        vec![self.controls.clone()]
+    }
+
+    #[inline]
+    fn is_closed(&self) -> bool {
+        false
+    }
+}
+
+/// A Catmull-Rom spline, which goes through all of its points
+///
+/// It's centripetal by default, which keeps it from looping or making cusps where the points
+/// are unevenly spaced.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CatmullRom {
+    points: Vec<Vec2>,
+    alpha: f64,
+    closed: bool,
+    tolerance: f64,
+}
+
+impl CatmullRom {
+    /// # Panics
+    ///
+    /// Panics if there are fewer than 2 different points.
+    pub fn new(points: impl IntoIterator<Item = [f64; 2]>) -> Self {
+        let mut points: Vec<Vec2> = points.into_iter().map(Vec2::from).collect();
+        // a repeated point has no direction to curve through
+        points.dedup();
+        assert!(points.len() >= 2, "a spline needs at least 2 points");
+
+        Self {
+            points,
+            alpha: 0.5,
+            closed: false,
+            tolerance: TOLERANCE,
+        }
+    }
+
+    /// How much the spacing of the points shapes the curve, from 0 for a uniform spline, to 0.5
+    /// for a centripetal one, to 1 for a chordal one
+    #[inline]
+    pub fn alpha(mut self, alpha: f64) -> Self {
+        self.alpha = alpha;
+        self
+    }
+
+    /// Joins the last point back to the first one, for an outline
+    #[inline]
+    pub fn closed(mut self, closed: bool) -> Self {
+        self.closed = closed;
+        self
+    }
+
+    fn pieces(&self) -> Vec<Vec<Vec2>> {
+        let mut points = self.points.clone();
+        if self.closed && points.len() > 2 && points.first() == points.last() {
+            points.pop();
+        }
+        let count = points.len();
+
+        // open splines carry on past their ends in a straight line
+        let point = |i: isize| -> Vec2 {
+            if self.closed {
+                points[i.rem_euclid(count as isize) as usize]
+            } else if i < 0 {
+                points[0] * 2.0 - points[1]
+            } else if i as usize >= count {
+                points[count - 1] * 2.0 - points[count - 2]
+            } else {
+                points[i as usize]
+            }
+        };
+
+        let spans = if self.closed { count } else { count - 1 };
+        (0..spans as isize)
+            .map(|i| {
+                let (p0, p1, p2, p3) = (point(i - 1), point(i), point(i + 1), point(i + 2));
+                let knot = |a: Vec2, b: Vec2| (b - a).length().powf(self.alpha);
+                let (t01, t12, t23) = (knot(p0, p1), knot(p1, p2), knot(p2, p3));
+
+                // the tangents at either end of the span, scaled to the span, from "On the
+                // Parameterization of Catmull-Rom Curves" by Yuksel et al.
+                let m1 = (p2 - p1) + ((p1 - p0) / t01 - (p2 - p0) / (t01 + t12)) * t12;
+                let m2 = (p2 - p1) + ((p3 - p2) / t23 - (p3 - p1) / (t12 + t23)) * t12;
+                vec![p1, p1 + m1 / 3.0, p2 - m2 / 3.0, p2]
+            })
+            .collect()
+    }
+
+    #[inline]
+    fn is_closed(&self) -> bool {
+        self.closed
+    }
+}
+
+/// A uniform B-spline, which is pulled towards its control points without going through them
+///
+/// Open splines start and end on their first and last control points.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BSpline {
+    controls: Vec<Vec2>,
+    degree: usize,
+    closed: bool,
+    tolerance: f64,
+}
+
+impl BSpline {
+    /// A cubic spline
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are fewer than 2 control points.
+    pub fn new(controls: impl IntoIterator<Item = [f64; 2]>) -> Self {
+        let controls: Vec<Vec2> = controls.into_iter().map(Vec2::from).collect();
+        assert!(controls.len() >= 2, "a spline needs at least 2 points");
+
+        Self {
+            controls,
+            degree: 3,
+            closed: false,
+            tolerance: TOLERANCE,
+        }
+    }
+
+    /// Sets the degree of the spline, like 2 for quadratic or 3 for cubic
+    ///
+    /// It's lowered to one less than the number of control points when there aren't enough.
+    #[inline]
+    pub fn degree(mut self, degree: usize) -> Self {
+        self.degree = degree;
+        self
+    }
+
+    /// Joins the spline back around to its start, for an outline
+    #[inline]
+    pub fn closed(mut self, closed: bool) -> Self {
+        self.closed = closed;
+        self
+    }
+
+    fn pieces(&self) -> Vec<Vec<Vec2>> {
+        let count = self.controls.len();
+        let degree = self.degree.clamp(1, count - 1);
+
+        // closed splines wrap around with evenly spaced knots, while open ones repeat their end
+        // knots so they start and end on their end points
+        let (controls, knots): (Vec<Vec2>, Vec<f64>) = if self.closed {
+            let controls = self.controls.iter().cycle().take(count + degree);
+            let knots = (0..=count + 2 * degree).map(|i| i as f64);
+            (controls.copied().collect(), knots.collect())
+        } else {
+            let knots = (0..=count + degree).map(|i| i.clamp(degree, count) as f64);
+            (self.controls.clone(), knots.collect())
+        };
+
+        // the Bézier control points of each span are the blossoms of its knots, from "Blossoming:
+        // A Connect-the-Dots Approach to Splines" by Ramshaw
+        (degree..controls.len())
+            .filter(|&span| knots[span] < knots[span + 1])
+            .map(|span| {
+                let (start, end) = (knots[span], knots[span + 1]);
+                (0..=degree)
+                    .map(|i| {
+                        let args: Vec<f64> = (0..degree)
+                            .map(|j| if j < degree - i { start } else { end })
+                            .collect();
+                        blossom(&controls, &knots, degree, span, &args)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[inline]
+    fn is_closed(&self) -> bool {
+        self.closed
+    }
+}
+
+/// De Boor's algorithm, with a different parameter at each step
+fn blossom(controls: &[Vec2], knots: &[f64], degree: usize, span: usize, args: &[f64]) -> Vec2 {
+    let mut points = controls[span - degree..=span].to_vec();
+    for step in 1..=degree {
+        for j in (step..=degree).rev() {
+            let i = span - degree + j;
+            let (low, high) = (knots[i], knots[i + degree + 1 - step]);
+            let alpha = (args[step - 1] - low) / (high - low);
+            points[j] = points[j - 1] * (1.0 - alpha) + points[j] * alpha;
+        }
+    }
+    points[degree]
+}
+
+/// Flattens a chain of Bézier curves, leaving out the end of the last one when the chain is
+/// closed
+fn flatten(pieces: &[Vec<Vec2>], closed: bool, tolerance: f64) -> Vec<[f64; 2]> {
+    let mut points = vec![pieces[0][0]];
+    for piece in pieces {
+        bezier(piece, tolerance, &mut points);
+    }
+    if closed && points.len() > 1 {
+        points.pop();
+    }
+    points.into_iter().map(Into::into).collect()
+}
+
+/// Adds the points after the first of a Bézier curve, splitting it in half until its control
+/// points are within the tolerance of a line
+pub(crate) fn bezier(controls: &[Vec2], tolerance: f64, out: &mut Vec<Vec2>) {
+    bezier_depth(controls, tolerance, 0, out);
+}
+
+fn bezier_depth(controls: &[Vec2], tolerance: f64, depth: usize, out: &mut Vec<Vec2>) {
+    let (start, end) = (controls[0], controls[controls.len() - 1]);
+    let chord = end - start;
+    let flat = controls[1..controls.len() - 1].iter().all(|p| {
+        let distance = if chord.length() > 0.0 {
+            chord.cross(*p - start).abs() / chord.length()
+        } else {
+            (*p - start).length()
+        };
+        distance <= tolerance
+    });
+    if flat || depth >= 16 {
+        out.push(end);
+        return;
+    }
+
+    // de Casteljau's algorithm, keeping the first and last point of each round
+    let mut left = vec![start];
+    let mut right = vec![end];
+    let mut points = controls.to_vec();
+    while points.len() > 1 {
+        points = points
+            .windows(2)
+            .map(|pair| (pair[0] + pair[1]) / 2.0)
+            .collect();
+        left.push(points[0]);
+        right.push(points[points.len() - 1]);
+    }
+    right.reverse();
+
+    bezier_depth(&left, tolerance, depth + 1, out);
+    bezier_depth(&right, tolerance, depth + 1, out);
+}
+
+#[test]
+fn bezier_test() {
+    let arch = Bezier::quadratic([0.0, 0.0], [1.0, 2.0], [2.0, 0.0]);
+    assert_eq!(arch.at(0.5), [1.0, 1.0]);
+
+    // more points for a tighter tolerance, all on the curve
+    let coarse = arch.clone().tolerance(0.1).points();
+    let fine = arch.clone().tolerance(0.001).points();
+    assert!(coarse.len() < fine.len());
+    assert_eq!(fine.first(), Some(&[0.0, 0.0]));
+    assert_eq!(fine.last(), Some(&[2.0, 0.0]));
+    assert!(fine.iter().all(|&[x, y]| (y - x * (2.0 - x)).abs() < 1e-9));
+
+    // a straight curve needs no points in between
+    let line = Bezier::cubic([0.0, 0.0], [1.0, 1.0], [2.0, 2.0], [3.0, 3.0]);
+    assert_eq!(line.points().len(), 2);
+}
+
+#[test]
+fn spline_test() {
+    let points = [[0.0, 0.0], [10.0, 5.0], [20.0, 0.0], [30.0, 10.0]];
+
+    // goes through every point, in order
+    let spline = CatmullRom::new(points).points();
+    let found: Vec<_> = points
+        .iter()
+        .map(|p| spline.iter().position(|q| q == p).unwrap())
+        .collect();
+    assert!(found.windows(2).all(|pair| pair[0] < pair[1]));
+    assert_eq!(
+        CatmullRom::new([[0.0, 0.0], [1.0, 0.0], [2.0, 0.0]])
+            .alpha(0.0)
+            .points(),
+        [[0.0, 0.0], [1.0, 0.0], [2.0, 0.0]]
+    );
+    let closed = CatmullRom::new(points).closed(true).points();
+    assert_ne!(closed.first(), closed.last());
+    assert!(closed.len() > spline.len());
+
+    // a cubic spline through 4 points is the Bézier curve with them as control points
+    let [a, b, c, d] = points;
+    assert_eq!(
+        BSpline::new(points).points(),
+        Bezier::cubic(a, b, c, d).points()
+    );
+    // a linear one is the lines between them
+    assert_eq!(BSpline::new(points).degree(1).points(), points);
+
+    // a closed quadratic spline touches the middle of each edge between its control points
+    let square = [[0.0, 0.0], [2.0, 0.0], [2.0, 2.0], [0.0, 2.0]];
+    let round = BSpline::new(square).degree(2).closed(true).points();
+    for middle in [[1.0, 0.0], [2.0, 1.0], [1.0, 2.0], [0.0, 1.0]] {
+        assert!(round.contains(&middle), "{middle:?}");
+    }
+}
+
+#[test]
+fn profile_test() {
+    use crate::*;
+
+    let side = Bezier::quadratic([2.0, 0.0], [4.0, 2.0], [1.0, 4.0]).tolerance(0.1);
+    let profile = side.profile();
+    assert_eq!(profile[profile.len() - 2..], [[0.0, 4.0], [0.0, 0.0]]);
+    insta::assert_snapshot!(polygon(profile) >> rotate_extrude());
+}
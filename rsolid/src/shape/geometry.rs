@@ -0,0 +1,544 @@
+//! Exact 2D outlines built in Rust, for profiles with rounded corners, offsets and cut-outs that
+//! look the same in preview and render
+//!
+//! ```
+//! use rsolid::{geometry::{Join, Path2, Polygon2}, *};
+//!
+//! let plate = Polygon2::new([[0.0, 0.0], [40.0, 0.0], [40.0, 20.0], [0.0, 20.0]]).round_corners(4.0);
+//! let slot = Path2::new([12.0, 8.0])
+//!     .line_to([28.0, 8.0])
+//!     .arc_to([28.0, 12.0], 2.0)
+//!     .line_to([12.0, 12.0])
+//!     .arc_to([12.0, 8.0], 2.0)
+//!     .close();
+//!
+//! let profile = (plate - slot).offset(0.5, Join::Round);
+//! let _ = profile.into_object() >> linear_extrude(3);
+//! ```
+
+mod boolean;
+mod offset;
+
+pub use offset::Join;
+
+use super::curve::{bezier, TOLERANCE};
+use crate::{
+    math::{round, sin_cos_degrees, Vec2},
+    polygon, IntoObject, Object,
+};
+
+/// An outline made of lines, arcs and Bézier curves, which is flattened into points
+///
+/// Curves are split into as few lines as keep them within the [`tolerance`](Path2::tolerance).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Path2 {
+    start: Vec2,
+    segments: Vec<Segment>,
+    tolerance: f64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Segment {
+    Line(Vec2),
+    /// Turns around the center by the given radians, counter-clockwise when positive
+    Arc {
+        center: Vec2,
+        radians: f64,
+        end: Vec2,
+    },
+    Quadratic(Vec2, Vec2),
+    Cubic(Vec2, Vec2, Vec2),
+}
+
+impl Path2 {
+    #[inline]
+    pub fn new(start: [f64; 2]) -> Self {
+        Self {
+            start: start.into(),
+            segments: vec![],
+            tolerance: TOLERANCE,
+        }
+    }
+
+    /// Sets how far flattened curves may stray from the true curve, in mm
+    #[inline]
+    pub fn tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Where the path currently ends
+    pub fn end(&self) -> [f64; 2] {
+        let end = match self.segments.last() {
+            Some(Segment::Line(end) | Segment::Arc { end, .. }) => *end,
+            Some(Segment::Quadratic(_, end) | Segment::Cubic(_, _, end)) => *end,
+            None => self.start,
+        };
+        end.into()
+    }
+
+    #[inline]
+    pub fn line_to(mut self, end: [f64; 2]) -> Self {
+        self.segments.push(Segment::Line(end.into()));
+        self
+    }
+
+    /// Turns around the center by the given degrees, counter-clockwise when positive
+    pub fn arc(mut self, center: [f64; 2], degrees: f64) -> Self {
+        let center = Vec2::from(center);
+        // quarter turns land exactly, so arcs line up with straight edges
+        let (sin, cos) = sin_cos_degrees(degrees);
+        let from = Vec2::from(self.end()) - center;
+        let end = center + Vec2::new(from.x * cos - from.y * sin, from.x * sin + from.y * cos);
+        self.segments.push(Segment::Arc {
+            center,
+            radians: degrees.to_radians(),
+            end,
+        });
+        self
+    }
+
+    /// Takes the shorter arc of the given radius to the end, counter-clockwise when the radius is
+    /// positive and clockwise when it's negative
+    ///
+    /// A radius shorter than half the distance to the end is stretched to reach it.
+    pub fn arc_to(mut self, end: [f64; 2], radius: f64) -> Self {
+        let (start, end) = (Vec2::from(self.end()), Vec2::from(end));
+        let chord = end - start;
+        let half = chord.length() / 2.0;
+        let rise = (radius * radius - half * half).max(0.0).sqrt();
+        let center = start + chord / 2.0 + chord.unit().perp() * rise * radius.signum();
+
+        let (from, to) = (start - center, end - center);
+        let radians = from.cross(to).atan2(from.dot(to)).abs() * radius.signum();
+        self.segments.push(Segment::Arc {
+            center,
+            radians,
+            end,
+        });
+        self
+    }
+
+    #[inline]
+    pub fn quad_to(mut self, control: [f64; 2], end: [f64; 2]) -> Self {
+        self.segments
+            .push(Segment::Quadratic(control.into(), end.into()));
+        self
+    }
+
+    #[inline]
+    pub fn cubic_to(mut self, control1: [f64; 2], control2: [f64; 2], end: [f64; 2]) -> Self {
+        self.segments
+            .push(Segment::Cubic(control1.into(), control2.into(), end.into()));
+        self
+    }
+
+    /// The flattened points of the path, starting with its start
+    pub fn points(&self) -> Vec<[f64; 2]> {
+        self.flatten().into_iter().map(Into::into).collect()
+    }
+
+    /// Joins the end of the path back to its start
+    pub fn close(self) -> Polygon2 {
+        let mut points = self.flatten();
+        if points.len() > 1 && points.first() == points.last() {
+            points.pop();
+        }
+        Polygon2::from_ring(points, self.tolerance)
+    }
+
+    fn flatten(&self) -> Vec<Vec2> {
+        let mut points = vec![self.start];
+        for segment in &self.segments {
+            let start = *points.last().unwrap();
+            match *segment {
+                Segment::Line(end) => points.push(end),
+                Segment::Arc {
+                    center,
+                    radians,
+                    end,
+                } => {
+                    points.extend(arc(center, start, radians, self.tolerance));
+                    // the end is kept exact, so the next segment starts where it was meant to
+                    *points.last_mut().unwrap() = end;
+                }
+                Segment::Quadratic(control, end) => {
+                    bezier(&[start, control, end], self.tolerance, &mut points);
+                }
+                Segment::Cubic(control1, control2, end) => {
+                    bezier(
+                        &[start, control1, control2, end],
+                        self.tolerance,
+                        &mut points,
+                    );
+                }
+            }
+        }
+        points
+    }
+}
+
+/// A region of the plane, bounded by closed rings
+///
+/// The rings are kept counter-clockwise around the region and clockwise around its holes, which
+/// is what the boolean operations and [`offset`](Polygon2::offset) give back.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Polygon2 {
+    rings: Vec<Vec<Vec2>>,
+    tolerance: f64,
+}
+
+impl Default for Polygon2 {
+    /// An empty region, to start unions from
+    #[inline]
+    fn default() -> Self {
+        Self {
+            rings: vec![],
+            tolerance: TOLERANCE,
+        }
+    }
+}
+
+impl Polygon2 {
+    /// Creates a region from the points of a simple polygon, in either winding
+    pub fn new(points: impl IntoIterator<Item = [f64; 2]>) -> Self {
+        let mut points: Vec<Vec2> = points.into_iter().map(Vec2::from).collect();
+        if points.len() > 1 && points.first() == points.last() {
+            points.pop();
+        }
+        Self::from_ring(points, TOLERANCE)
+    }
+
+    fn from_ring(mut points: Vec<Vec2>, tolerance: f64) -> Self {
+        points.dedup();
+        if area(&points) < 0.0 {
+            points.reverse();
+        }
+        Self {
+            rings: vec![points],
+            tolerance,
+        }
+    }
+
+    /// Sets how far the arcs added by [`round_corners`](Polygon2::round_corners) and
+    /// [`offset`](Polygon2::offset) may stray from the true curve, in mm
+    #[inline]
+    pub fn tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// The points of each ring
+    pub fn rings(&self) -> Vec<Vec<[f64; 2]>> {
+        self.rings
+            .iter()
+            .map(|ring| ring.iter().copied().map(Into::into).collect())
+            .collect()
+    }
+
+    /// The area of the region, without its holes
+    pub fn area(&self) -> f64 {
+        self.rings.iter().map(|ring| area(ring)).sum::<f64>() / 2.0
+    }
+
+    /// Whether the point is inside the region
+    pub fn contains(&self, point: [f64; 2]) -> bool {
+        let point = Vec2::from(point);
+        self.rings
+            .iter()
+            .map(|ring| winding(ring, point))
+            .sum::<i32>()
+            > 0
+    }
+
+    /// Rounds every corner with an arc of the given radius
+    ///
+    /// The radius is reduced where it doesn't fit in half of the edges on either side. Corners
+    /// so slight that the arc would be a single line, like the points of an already flattened
+    /// curve, are left as they are.
+    pub fn round_corners(self, radius: f64) -> Self {
+        let tolerance = self.tolerance;
+        self.corners(|before, corner, after, out| {
+            let cut = Cut::new(before, corner, after, |angle| radius / (angle / 2.0).tan());
+            let Some(cut) = cut else {
+                out.push(corner);
+                return;
+            };
+            let radius = cut.distance * (cut.angle / 2.0).tan();
+            let turn = core::f64::consts::PI - cut.angle;
+            if fragments(radius, turn, tolerance) <= 1 {
+                out.push(corner);
+                return;
+            }
+
+            let center =
+                corner + (cut.before + cut.after).unit() * (radius / (cut.angle / 2.0).sin());
+            let (start, end) = (cut.start(corner), cut.end(corner));
+            let (from, to) = (start - center, end - center);
+            out.push(start);
+            out.extend(arc(
+                center,
+                start,
+                from.cross(to).atan2(from.dot(to)),
+                tolerance,
+            ));
+            *out.last_mut().unwrap() = end;
+        })
+    }
+
+    /// Cuts off every corner, the given distance along the edges on either side
+    ///
+    /// The distance is reduced where it doesn't fit in half of the edges.
+    pub fn chamfer_corners(self, distance: f64) -> Self {
+        self.corners(|before, corner, after, out| {
+            match Cut::new(before, corner, after, |_| distance) {
+                Some(cut) => out.extend([cut.start(corner), cut.end(corner)]),
+                None => out.push(corner),
+            }
+        })
+    }
+
+    /// Replaces each corner with the points pushed by `f`, given the corners before and after it
+    fn corners(mut self, f: impl Fn(Vec2, Vec2, Vec2, &mut Vec<Vec2>)) -> Self {
+        for ring in &mut self.rings {
+            let count = ring.len();
+            let mut out = vec![];
+            for i in 0..count {
+                f(
+                    ring[(i + count - 1) % count],
+                    ring[i],
+                    ring[(i + 1) % count],
+                    &mut out,
+                );
+            }
+            // corners cut back to the middle of the same edge both end up there
+            out.dedup();
+            if out.len() > 1 && out.first() == out.last() {
+                out.pop();
+            }
+            *ring = out;
+        }
+        self
+    }
+
+    /// The outlines of the region, each with the holes inside it
+    fn regions(&self) -> Vec<(&[Vec2], Vec<&[Vec2]>)> {
+        let (outlines, holes): (Vec<_>, Vec<_>) =
+            self.rings.iter().partition(|ring| area(ring) > 0.0);
+        let mut regions: Vec<_> = outlines
+            .iter()
+            .map(|ring| (ring.as_slice(), vec![]))
+            .collect();
+
+        for hole in holes {
+            if hole.len() < 2 {
+                continue;
+            }
+            // a point just inside the hole, away from any corners it shares with an outline
+            let (a, b) = (hole[0], hole[1]);
+            let probe = (a + b) / 2.0 - (b - a).unit().perp() * 1e-6;
+            let outline = (0..outlines.len())
+                .filter(|&i| winding(outlines[i], probe) != 0)
+                .min_by(|&i, &j| area(outlines[i]).total_cmp(&area(outlines[j])));
+            if let Some(i) = outline {
+                regions[i].1.push(hole.as_slice());
+            }
+        }
+        regions
+    }
+}
+
+impl IntoObject<2> for Polygon2 {
+    fn into_object(self) -> Object<2> {
+        let ring = |ring: &[Vec2]| polygon(ring.iter().map(|p| [p.x, p.y].map(round)));
+        let mut regions: Vec<_> = self
+            .regions()
+            .into_iter()
+            .map(|(outline, holes)| {
+                let outline = ring(outline).into_object();
+                match holes.len() {
+                    0 => outline,
+                    1 => outline - ring(holes[0]),
+                    _ => outline - holes.into_iter().map(ring).collect::<Object<2>>(),
+                }
+            })
+            .collect();
+
+        if regions.len() == 1 {
+            regions.remove(0)
+        } else {
+            regions.into_iter().collect()
+        }
+    }
+}
+
+impl From<Polygon2> for Object<2> {
+    #[inline]
+    fn from(value: Polygon2) -> Self {
+        value.into_object()
+    }
+}
+
+/// Where a corner is cut, as the distance along the edges on either side
+struct Cut {
+    before: Vec2,
+    after: Vec2,
+    /// The angle between the edges
+    angle: f64,
+    distance: f64,
+}
+
+impl Cut {
+    /// Cuts the corner at the distance given for the angle between the edges, or returns `None`
+    /// for corners that are straight or fold back on themselves
+    fn new(
+        before: Vec2,
+        corner: Vec2,
+        after: Vec2,
+        distance: impl FnOnce(f64) -> f64,
+    ) -> Option<Self> {
+        let (to_before, to_after) = (before - corner, after - corner);
+        let (before, after) = (to_before.unit(), to_after.unit());
+        let angle = before.dot(after).clamp(-1.0, 1.0).acos();
+        if !(1e-9..=core::f64::consts::PI - 1e-9).contains(&angle) {
+            return None;
+        }
+
+        let distance = distance(angle)
+            .min(to_before.length() / 2.0)
+            .min(to_after.length() / 2.0);
+        if distance <= 0.0 {
+            return None;
+        }
+        Some(Self {
+            before,
+            after,
+            angle,
+            distance,
+        })
+    }
+
+    #[inline]
+    fn start(&self, corner: Vec2) -> Vec2 {
+        corner + self.before * self.distance
+    }
+
+    #[inline]
+    fn end(&self, corner: Vec2) -> Vec2 {
+        corner + self.after * self.distance
+    }
+}
+
+/// Twice the signed area of a ring, which is positive when it's counter-clockwise
+fn area(ring: &[Vec2]) -> f64 {
+    (0..ring.len())
+        .map(|i| ring[i].cross(ring[(i + 1) % ring.len()]))
+        .sum()
+}
+
+/// How many times the ring goes counter-clockwise around the point
+fn winding(ring: &[Vec2], point: Vec2) -> i32 {
+    (0..ring.len())
+        .map(|i| crossing(ring[i], ring[(i + 1) % ring.len()], point))
+        .sum()
+}
+
+/// Whether the edge crosses the line going right from the point, upwards when it's 1 and
+/// downwards when it's -1
+fn crossing(a: Vec2, b: Vec2, point: Vec2) -> i32 {
+    let side = (b - a).cross(point - a);
+    if a.y <= point.y && point.y < b.y && side > 0.0 {
+        1
+    } else if b.y <= point.y && point.y < a.y && side < 0.0 {
+        -1
+    } else {
+        0
+    }
+}
+
+#[inline]
+fn rotate(v: Vec2, radians: f64) -> Vec2 {
+    let (sin, cos) = radians.sin_cos();
+    Vec2::new(v.x * cos - v.y * sin, v.x * sin + v.y * cos)
+}
+
+/// The most lines an arc is split into, as many as the 16 halvings of a Bézier curve give
+const MAX_FRAGMENTS: f64 = 65536.0;
+
+/// How many lines an arc needs to stay within the tolerance
+fn fragments(radius: f64, radians: f64, tolerance: f64) -> usize {
+    // each line cuts across the arc, so its middle is furthest from it
+    let step = 2.0 * (1.0 - tolerance / radius).clamp(-1.0, 1.0).acos();
+    if radians == 0.0 {
+        return 1;
+    }
+    // a tolerance of 0 or less can't be met, so those arcs are split as finely as they can be
+    (radians.abs() / step).ceil().clamp(1.0, MAX_FRAGMENTS) as usize
+}
+
+/// The points after `start` on an arc around the center, ending where it has turned by
+/// `radians`
+fn arc(center: Vec2, start: Vec2, radians: f64, tolerance: f64) -> impl Iterator<Item = Vec2> {
+    let count = fragments((start - center).length(), radians, tolerance);
+    (1..=count).map(move |i| center + rotate(start - center, radians * i as f64 / count as f64))
+}
+
+#[test]
+fn path_test() {
+    let half = Path2::new([1.0, 0.0]).arc([0.0, 0.0], 180.0);
+    assert_eq!(half.end(), [-1.0, 0.0]);
+    let points = half.clone().tolerance(0.1).points();
+    // 2 * acos(0.9) is a little over 0.9 radians, so pi needs 4 lines
+    assert_eq!(points.len(), 5);
+    assert_eq!(points[4], [-1.0, 0.0]);
+    // like Bézier curves, arcs that can't be flattened within the tolerance are split up finely
+    let finest = half.tolerance(0.0).points();
+    assert_eq!(finest.len(), MAX_FRAGMENTS as usize + 1);
+
+    // the shorter arc, on either side of the line between the ends
+    let left = Path2::new([0.0, 0.0]).arc_to([2.0, 0.0], 2.0).points();
+    assert!(left.iter().all(|p| p[1] <= 1e-12));
+    let right = Path2::new([0.0, 0.0]).arc_to([2.0, 0.0], -2.0).points();
+    assert!(right.iter().all(|p| p[1] >= -1e-12));
+    // a radius that's too short is stretched into a semicircle
+    let short = Path2::new([0.0, 0.0]).arc_to([2.0, 0.0], 0.5).points();
+    assert!(short.iter().any(|p| (p[1] + 1.0).abs() < 1e-3));
+
+    let curve = Path2::new([0.0, 0.0])
+        .cubic_to([0.0, 1.0], [1.0, 1.0], [1.0, 0.0])
+        .tolerance(0.001)
+        .points();
+    assert_eq!(curve[curve.len() - 1], [1.0, 0.0]);
+    // the top of the curve is at 3/4 of the control points' height
+    let top = curve.iter().map(|p| p[1]).fold(0.0, f64::max);
+    assert!((top - 0.75).abs() < 0.001, "{top}");
+}
+
+#[test]
+fn corners_test() {
+    let square = || Polygon2::new([[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]]);
+
+    let chamfered = square().chamfer_corners(2.0);
+    assert_eq!(chamfered.rings()[0].len(), 8);
+    assert!((chamfered.area() - (100.0 - 4.0 * 2.0)).abs() < 1e-9);
+
+    let rounded = square().tolerance(0.001).round_corners(2.0);
+    let expected = 100.0 - (4.0 - core::f64::consts::PI) * 4.0;
+    assert!(
+        (rounded.area() - expected).abs() < 0.01,
+        "{}",
+        rounded.area()
+    );
+    // rounding again doesn't touch the arcs
+    assert_eq!(rounded.clone().round_corners(2.0), rounded);
+
+    // the radius shrinks to fit between the corners of a narrow slot
+    let slot = Polygon2::new([[0.0, 0.0], [10.0, 0.0], [10.0, 2.0], [0.0, 2.0]]).round_corners(5.0);
+    let points = slot.rings()[0].clone();
+    assert!((0..points.len()).all(|i| points[i] != points[(i + 1) % points.len()]));
+    assert!(slot.rings()[0].iter().all(|p| p[1] >= 0.0 && p[1] <= 2.0));
+    assert!(!slot.contains([0.01, 0.01]));
+
+    let triangle = Polygon2::new([[0.0, 0.0], [0.0, 2.0], [2.0, 0.0]]).tolerance(0.05);
+    insta::assert_snapshot!(triangle.round_corners(0.5).into_object());
+}
@@ -0,0 +1,352 @@
+//! Boolean operations, done by splitting every edge where it meets another one and keeping the
+//! pieces that have the region on their left and nothing on their right
+
+use super::{area, crossing, Polygon2};
+use crate::math::Vec2;
+use core::ops;
+use std::collections::{HashMap, HashSet};
+
+/// How close points have to be to count as the same, in mm
+const EPSILON: f64 = 1e-9;
+
+/// How far to either side of an edge the region is looked for, in mm
+const PROBE: f64 = 1e-6;
+
+impl Polygon2 {
+    /// The parts of the plane in either region
+    pub fn union(&self, other: &Self) -> Self {
+        self.boolean(other, |a, b| a || b)
+    }
+
+    /// The parts of the plane in both regions
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.boolean(other, |a, b| a && b)
+    }
+
+    /// The parts of this region that aren't in the other one
+    pub fn difference(&self, other: &Self) -> Self {
+        self.boolean(other, |a, b| a && !b)
+    }
+
+    fn boolean(&self, other: &Self, op: impl Fn(bool, bool) -> bool) -> Self {
+        let rings = fill(&[&self.rings, &other.rings], |winding| {
+            op(winding[0] > 0, winding[1] > 0)
+        });
+        Self {
+            rings,
+            tolerance: self.tolerance,
+        }
+    }
+}
+
+impl ops::Add for Polygon2 {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, other: Self) -> Self {
+        self.union(&other)
+    }
+}
+
+impl ops::AddAssign for Polygon2 {
+    #[inline]
+    fn add_assign(&mut self, other: Self) {
+        *self = self.union(&other);
+    }
+}
+
+impl ops::BitOr for Polygon2 {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, other: Self) -> Self {
+        self.union(&other)
+    }
+}
+
+impl ops::Sub for Polygon2 {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, other: Self) -> Self {
+        self.difference(&other)
+    }
+}
+
+impl ops::SubAssign for Polygon2 {
+    #[inline]
+    fn sub_assign(&mut self, other: Self) {
+        *self = self.difference(&other);
+    }
+}
+
+impl ops::BitAnd for Polygon2 {
+    type Output = Self;
+
+    #[inline]
+    fn bitand(self, other: Self) -> Self {
+        self.intersection(&other)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Edge {
+    a: Vec2,
+    b: Vec2,
+    group: usize,
+}
+
+/// The rings around the parts of the plane where `inside` holds, given how many times the rings
+/// of each group wind around each point
+///
+/// The rings can cross themselves and each other. The rings that come back go counter-clockwise
+/// around the region and clockwise around its holes.
+pub(super) fn fill(groups: &[&[Vec<Vec2>]], inside: impl Fn(&[i32]) -> bool) -> Vec<Vec<Vec2>> {
+    let edges: Vec<Edge> = groups
+        .iter()
+        .enumerate()
+        .flat_map(|(group, rings)| {
+            rings.iter().flat_map(move |ring| {
+                (0..ring.len()).map(move |i| Edge {
+                    a: ring[i],
+                    b: ring[(i + 1) % ring.len()],
+                    group,
+                })
+            })
+        })
+        .filter(|edge| (edge.b - edge.a).length() > EPSILON)
+        .collect();
+
+    // where each edge meets the others, as how far along the edge it is and the point itself,
+    // which is shared by both edges so the pieces line up exactly
+    let mut splits: Vec<Vec<(f64, Vec2)>> = edges
+        .iter()
+        .map(|edge| vec![(0.0, edge.a), (1.0, edge.b)])
+        .collect();
+    for i in 0..edges.len() {
+        for j in i + 1..edges.len() {
+            for (t, u, point) in intersections(&edges[i], &edges[j]) {
+                splits[i].push((t, point));
+                splits[j].push((u, point));
+            }
+        }
+    }
+
+    let mut pieces: Vec<Edge> = vec![];
+    for (edge, mut splits) in edges.iter().zip(splits) {
+        splits.sort_by(|a, b| a.0.total_cmp(&b.0));
+        splits.dedup_by(|b, a| (b.1 - a.1).length() <= EPSILON);
+        pieces.extend(splits.windows(2).map(|pair| Edge {
+            a: pair[0].1,
+            b: pair[1].1,
+            group: edge.group,
+        }));
+    }
+
+    // each piece is kept facing so that the region is on its left, and pieces that are on the
+    // boundary of both groups only once
+    let windings = |point: Vec2| -> Vec<i32> {
+        let mut windings = vec![0; groups.len()];
+        for edge in &edges {
+            windings[edge.group] += crossing(edge.a, edge.b, point);
+        }
+        windings
+    };
+    let mut seen = HashSet::new();
+    let mut kept: Vec<(Vec2, Vec2)> = vec![];
+    for piece in &pieces {
+        let middle = (piece.a + piece.b) / 2.0;
+        let normal = (piece.b - piece.a).unit().perp() * PROBE;
+        let left = inside(&windings(middle + normal));
+        let right = inside(&windings(middle - normal));
+        let (a, b) = match (left, right) {
+            (true, false) => (piece.a, piece.b),
+            (false, true) => (piece.b, piece.a),
+            _ => continue,
+        };
+        if seen.insert((key(a), key(b))) {
+            kept.push((a, b));
+        }
+    }
+
+    stitch(&kept)
+}
+
+/// Where two edges meet, as how far along each edge it is and the point itself
+///
+/// Besides edges crossing, this finds the ends of one edge that are on the other, which is how
+/// edges that touch or overlap get split at the same points.
+fn intersections(e: &Edge, f: &Edge) -> Vec<(f64, f64, Vec2)> {
+    let outside = |axis: fn(Vec2) -> f64| {
+        let (e0, e1) = (axis(e.a).min(axis(e.b)), axis(e.a).max(axis(e.b)));
+        let (f0, f1) = (axis(f.a).min(axis(f.b)), axis(f.a).max(axis(f.b)));
+        e1 + EPSILON < f0 || f1 + EPSILON < e0
+    };
+    if outside(|p| p.x) || outside(|p| p.y) {
+        return vec![];
+    }
+
+    let mut out = vec![];
+    for (u, point) in [(0.0, f.a), (1.0, f.b)] {
+        if let Some(t) = along(e, point) {
+            out.push((t, u, point));
+        }
+    }
+    for (t, point) in [(0.0, e.a), (1.0, e.b)] {
+        if let Some(u) = along(f, point) {
+            out.push((t, u, point));
+        }
+    }
+    if !out.is_empty() {
+        return out;
+    }
+
+    let (d, g) = (e.b - e.a, f.b - f.a);
+    let denominator = d.cross(g);
+    if denominator == 0.0 {
+        return out;
+    }
+    let t = (f.a - e.a).cross(g) / denominator;
+    let u = (f.a - e.a).cross(d) / denominator;
+    let within = |t: f64, length: f64| t * length > EPSILON && (1.0 - t) * length > EPSILON;
+    if within(t, d.length()) && within(u, g.length()) {
+        out.push((t, u, e.a + d * t));
+    }
+    out
+}
+
+/// How far along the edge the point is, if it's on the edge and not at either end
+fn along(edge: &Edge, point: Vec2) -> Option<f64> {
+    let d = edge.b - edge.a;
+    let length = d.length();
+    let t = (point - edge.a).dot(d) / (length * length);
+    let on_line = d.cross(point - edge.a).abs() / length <= EPSILON;
+    (on_line && t * length > EPSILON && (1.0 - t) * length > EPSILON).then_some(t)
+}
+
+type Key = (i64, i64);
+
+#[inline]
+fn key(point: Vec2) -> Key {
+    let scale = 1.0 / EPSILON;
+    (
+        (point.x * scale).round() as i64,
+        (point.y * scale).round() as i64,
+    )
+}
+
+/// Joins the pieces into rings, following each piece with the one that turns the furthest
+/// clockwise where several start at the same point
+fn stitch(pieces: &[(Vec2, Vec2)]) -> Vec<Vec<Vec2>> {
+    let mut starting: HashMap<Key, Vec<usize>> = HashMap::new();
+    for (i, (a, _)) in pieces.iter().enumerate() {
+        starting.entry(key(*a)).or_default().push(i);
+    }
+
+    let mut used = vec![false; pieces.len()];
+    let mut rings = vec![];
+    for first in 0..pieces.len() {
+        if used[first] {
+            continue;
+        }
+        used[first] = true;
+        let mut ring = vec![first];
+        let closed = loop {
+            let (a, b) = pieces[*ring.last().unwrap()];
+            if key(b) == key(pieces[first].0) {
+                break true;
+            }
+            let direction = b - a;
+            let turn = |i: &usize| {
+                let next = pieces[*i].1 - pieces[*i].0;
+                direction.cross(next).atan2(direction.dot(next))
+            };
+            let next = starting
+                .get(&key(b))
+                .into_iter()
+                .flatten()
+                .filter(|i| !used[**i])
+                .min_by(|i, j| turn(i).total_cmp(&turn(j)));
+            match next {
+                Some(&next) => {
+                    used[next] = true;
+                    ring.push(next);
+                }
+                // only happens when rounding leaves a gap, in which case the ring is dropped
+                None => break false,
+            }
+        };
+
+        if closed {
+            let ring = simplify(ring.into_iter().map(|i| pieces[i].0).collect());
+            if ring.len() >= 3 && area(&ring).abs() > EPSILON {
+                rings.push(ring);
+            }
+        }
+    }
+    rings
+}
+
+/// Removes the points in the middle of straight lines, which splitting leaves behind
+fn simplify(ring: Vec<Vec2>) -> Vec<Vec2> {
+    let mut ring = ring;
+    loop {
+        let count = ring.len();
+        let straight = (0..count).find(|&i| {
+            let (a, b, c) = (
+                ring[(i + count - 1) % count],
+                ring[i],
+                ring[(i + 1) % count],
+            );
+            let (before, after) = (b - a, c - b);
+            before.cross(after).abs() <= EPSILON * (before.length() + after.length())
+                && before.dot(after) > 0.0
+        });
+        match straight {
+            Some(i) if count > 3 => {
+                ring.remove(i);
+            }
+            _ => return ring,
+        }
+    }
+}
+
+#[test]
+fn boolean_test() {
+    let square = |x: f64, y: f64, size: f64| {
+        Polygon2::new([[x, y], [x + size, y], [x + size, y + size], [x, y + size]])
+    };
+    let (a, b) = (square(0.0, 0.0, 2.0), square(1.0, 1.0, 2.0));
+
+    assert!((a.union(&b).area() - 7.0).abs() < 1e-9);
+    assert_eq!(a.union(&b).rings()[0].len(), 8);
+    assert!((a.intersection(&b).area() - 1.0).abs() < 1e-9);
+    assert!((a.difference(&b).area() - 3.0).abs() < 1e-9);
+    assert!((b.clone() - a.clone()).contains([2.5, 2.5]));
+
+    // shared edges merge away
+    let joined = square(0.0, 0.0, 1.0) + square(1.0, 0.0, 1.0);
+    assert_eq!(
+        joined.rings(),
+        [[[0.0, 0.0], [2.0, 0.0], [2.0, 1.0], [0.0, 1.0]]]
+    );
+    assert!(a.difference(&a).rings().is_empty());
+    assert!(square(0.0, 0.0, 1.0)
+        .intersection(&square(2.0, 0.0, 1.0))
+        .rings()
+        .is_empty());
+
+    // a hole goes clockwise, and is cut out of the outline it's in
+    let frame = square(0.0, 0.0, 4.0) - square(1.0, 1.0, 2.0);
+    assert!((frame.area() - 12.0).abs() < 1e-9);
+    assert!(!frame.contains([2.0, 2.0]));
+    let regions = frame.regions();
+    assert_eq!(regions.len(), 1);
+    assert_eq!(regions[0].1.len(), 1);
+    assert!(area(regions[0].1[0]) < 0.0);
+
+    // an island in the hole is a region of its own
+    let target = frame + square(1.5, 1.5, 1.0);
+    assert_eq!(target.regions().len(), 2);
+    assert!((target.area() - 13.0).abs() < 1e-9);
+}
@@ -0,0 +1,112 @@
+use super::{arc, boolean::fill, Polygon2};
+use crate::math::Vec2;
+
+/// How the corners are filled in where an [`offset`](Polygon2::offset) pulls the edges apart
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Join {
+    /// An arc around the corner, like `offset(r=...)`
+    Round,
+    /// The edges are extended until they meet, like `offset(delta=...)`
+    Miter,
+    /// The gap is bridged with a straight line, like `offset(delta=..., chamfer=true)`
+    Chamfer,
+}
+
+impl Polygon2 {
+    /// Grows the region by the distance, or shrinks it when the distance is negative
+    ///
+    /// Parts narrower than twice the distance disappear when shrinking, and so do holes when
+    /// growing.
+    pub fn offset(&self, distance: f64, join: Join) -> Self {
+        if distance == 0.0 {
+            return self.clone();
+        }
+
+        // each edge is moved out on its own, with the vertex put back between edges that now
+        // overlap, which makes loops that go the wrong way around and are dropped by the fill
+        let rings: Vec<_> = self
+            .rings
+            .iter()
+            .map(|ring| self.offset_ring(ring, distance, join))
+            .collect();
+        Self {
+            rings: fill(&[&rings], |winding| winding[0] > 0),
+            tolerance: self.tolerance,
+        }
+    }
+
+    fn offset_ring(&self, ring: &[Vec2], distance: f64, join: Join) -> Vec<Vec2> {
+        let count = ring.len();
+        // the region is on the left of every edge, so out is to the right
+        let normals: Vec<Vec2> = (0..count)
+            .map(|i| -(ring[(i + 1) % count] - ring[i]).unit().perp())
+            .collect();
+
+        let mut out = vec![];
+        for i in 0..count {
+            let corner = ring[i];
+            let (before, after) = (normals[(i + count - 1) % count], normals[i]);
+            let (start, end) = (corner + before * distance, corner + after * distance);
+            let (turn, cos) = (before.cross(after), before.dot(after));
+
+            if cos > 1.0 - 1e-12 {
+                out.push(start);
+            } else if turn * distance < 0.0 {
+                out.extend([start, corner, end]);
+            } else {
+                match join {
+                    Join::Round => {
+                        out.push(start);
+                        out.extend(arc(corner, start, turn.atan2(cos), self.tolerance));
+                    }
+                    // a corner that folds straight back would put the miter at infinity
+                    Join::Miter if cos > -1.0 + 1e-9 => {
+                        out.push(corner + (before + after) * (distance / (1.0 + cos)));
+                    }
+                    Join::Miter | Join::Chamfer => out.extend([start, end]),
+                }
+            }
+        }
+        out
+    }
+}
+
+#[test]
+fn offset_test() {
+    use core::f64::consts::PI;
+
+    let square = Polygon2::new([[0.0, 0.0], [2.0, 0.0], [2.0, 2.0], [0.0, 2.0]]).tolerance(0.001);
+
+    let round = square.offset(1.0, Join::Round);
+    assert!(
+        (round.area() - (4.0 + 8.0 + PI)).abs() < 0.01,
+        "{}",
+        round.area()
+    );
+    assert_eq!(square.offset(1.0, Join::Miter).area(), 16.0);
+    assert!((square.offset(1.0, Join::Chamfer).area() - 14.0).abs() < 1e-9);
+    assert!((square.offset(-0.5, Join::Round).area() - 1.0).abs() < 1e-9);
+    assert!(square.offset(-1.5, Join::Miter).rings().is_empty());
+
+    // the inside corner of an L is sharp when growing, and rounded when shrinking
+    let l = Polygon2::new([
+        [0.0, 0.0],
+        [4.0, 0.0],
+        [4.0, 1.0],
+        [1.0, 1.0],
+        [1.0, 4.0],
+        [0.0, 4.0],
+    ]);
+    let grown = l.offset(0.5, Join::Miter);
+    assert_eq!(grown.rings()[0].len(), 6);
+    assert!((grown.area() - 16.0).abs() < 1e-9, "{}", grown.area());
+    let shrunk = l.offset(-0.25, Join::Miter).area();
+    assert!((shrunk - 3.25).abs() < 1e-9, "{shrunk}");
+    assert!(l.offset(-0.25, Join::Round).area() > shrunk);
+
+    // a hole that's grown over disappears
+    let frame = Polygon2::new([[0.0, 0.0], [4.0, 0.0], [4.0, 4.0], [0.0, 4.0]])
+        - Polygon2::new([[1.0, 1.0], [3.0, 1.0], [3.0, 3.0], [1.0, 3.0]]);
+    assert_eq!(frame.offset(0.5, Join::Miter).rings().len(), 2);
+    assert_eq!(frame.offset(1.5, Join::Miter).rings().len(), 1);
+}
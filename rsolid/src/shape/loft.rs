@@ -0,0 +1,314 @@
+//! Skinning a solid between 2D cross-sections, for transitions like a round duct into a
+//! rectangular port
+//!
+//! ```
+//! use rsolid::{loft::{loft, Section}, *};
+//!
+//! let round: Vec<_> = (0..32)
+//!     .map(|i| {
+//!         let a = i as f64 / 32.0 * std::f64::consts::TAU;
+//!         [5.0 * a.cos(), 5.0 * a.sin()]
+//!     })
+//!     .collect();
+//! let port = [[-8.0, -3.0], [8.0, -3.0], [8.0, 3.0], [-8.0, 3.0]];
+//!
+//! let duct = loft([
+//!     Section::new(&round),
+//!     Section::new(&round).at(5.0),
+//!     Section::new(&port).at(20.0).rotate([10.0, 0.0, 0.0]),
+//! ]);
+//! let _ = duct.into_object() + cube(1);
+//! ```
+
+use super::skin::skin;
+use crate::{
+    math::{Affine, Vec3},
+    IntoObject, Object,
+};
+
+/// Skins a solid through the sections, in order
+///
+/// The sections are resampled to the same number of points by adding points along their
+/// edges, so their corners are kept. Each section's points are then matched up with the
+/// closest points of the one before it.
+///
+/// # Panics
+///
+/// Panics if there are fewer than 2 sections.
+pub fn loft(sections: impl IntoIterator<Item = Section>) -> Loft {
+    let sections: Vec<_> = sections.into_iter().collect();
+    assert!(sections.len() >= 2, "a loft needs at least 2 sections");
+
+    Loft {
+        sections,
+        points: 0,
+    }
+}
+
+/// A cross-section of a [`loft`], placed in 3D
+///
+/// Sections start on the XY plane, and are moved in the order the methods are called.
+#[derive(Clone, Debug)]
+pub struct Section {
+    points: Vec<[f64; 2]>,
+    transform: Affine,
+}
+
+impl Section {
+    /// Creates a section from the points of a polygon, in either winding
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are fewer than 3 points.
+    pub fn new(points: &[[f64; 2]]) -> Self {
+        let mut points = points.to_vec();
+        // a repeated first point would be a zero length edge
+        if points.len() > 1 && points.first() == points.last() {
+            points.pop();
+        }
+        assert!(points.len() >= 3, "a loft section needs at least 3 points");
+
+        // the rest of the loft works with counter-clockwise points
+        if area(&points) < 0.0 {
+            points.reverse();
+        }
+
+        Self {
+            points,
+            transform: Affine::IDENTITY,
+        }
+    }
+
+    /// Moves the section up to the given height
+    #[inline]
+    pub fn at(self, z: f64) -> Self {
+        self.translate([0.0, 0.0, z])
+    }
+
+    #[inline]
+    pub fn translate(mut self, v: [f64; 3]) -> Self {
+        self.transform = Affine::translate(v.into()) * self.transform;
+        self
+    }
+
+    /// Rotates around X, then Y, then Z, like `rotate([x, y, z])`
+    #[inline]
+    pub fn rotate(mut self, degrees: [f64; 3]) -> Self {
+        self.transform = Affine::rotate_xyz(degrees.into()) * self.transform;
+        self
+    }
+
+    #[inline]
+    pub fn scale(mut self, v: [f64; 3]) -> Self {
+        self.transform = Affine::scale(v.into()) * self.transform;
+        self
+    }
+}
+
+#[derive(Clone, Debug)]
+#[must_use = "Objects must be returned in order to be rendered"]
+pub struct Loft {
+    sections: Vec<Section>,
+    points: usize,
+}
+
+impl Loft {
+    /// Resamples the sections to at least this many points, for a smoother skin
+    ///
+    /// Defaults to the number of points in the largest section.
+    #[inline]
+    pub fn points(mut self, points: usize) -> Self {
+        self.points = points;
+        self
+    }
+
+    /// The points of each section, resampled and matched up
+    fn rings(&self) -> Vec<Vec<[f64; 2]>> {
+        let count = self
+            .sections
+            .iter()
+            .map(|section| section.points.len())
+            .fold(self.points, usize::max);
+
+        let mut rings: Vec<Vec<[f64; 2]>> = vec![];
+        for section in &self.sections {
+            let ring = resample(&section.points, count);
+            let shift = match rings.last() {
+                Some(previous) => closest_shift(previous, &ring),
+                None => start(&ring),
+            };
+            rings.push((0..count).map(|i| ring[(i + shift) % count]).collect());
+        }
+        rings
+    }
+}
+
+impl IntoObject<3> for Loft {
+    fn into_object(self) -> Object<3> {
+        let rings = self
+            .rings()
+            .into_iter()
+            .zip(&self.sections)
+            .map(|(ring, section)| {
+                ring.iter()
+                    .map(|[x, y]| section.transform.apply(Vec3::new(*x, *y, 0.0)))
+                    .collect()
+            })
+            .collect();
+        skin(rings, false)
+    }
+}
+
+impl From<Loft> for Object<3> {
+    #[inline]
+    fn from(value: Loft) -> Self {
+        value.into_object()
+    }
+}
+
+/// Twice the signed area of a polygon, which is positive when it's counter-clockwise
+fn area(points: &[[f64; 2]]) -> f64 {
+    (0..points.len())
+        .map(|i| {
+            let ([x0, y0], [x1, y1]) = (points[i], points[(i + 1) % points.len()]);
+            x0 * y1 - x1 * y0
+        })
+        .sum()
+}
+
+fn centroid(points: &[[f64; 2]]) -> [f64; 2] {
+    let n = points.len() as f64;
+    let [x, y] = points
+        .iter()
+        .fold([0.0; 2], |[x, y], p| [x + p[0], y + p[1]]);
+    [x / n, y / n]
+}
+
+/// Adds points along the edges of a polygon until it has `count` points, giving longer edges
+/// more of them
+fn resample(points: &[[f64; 2]], count: usize) -> Vec<[f64; 2]> {
+    let n = points.len();
+    let extra = count.saturating_sub(n);
+    if extra == 0 {
+        return points.to_vec();
+    }
+
+    let lengths: Vec<f64> = (0..n)
+        .map(|i| {
+            let (a, b) = (points[i], points[(i + 1) % n]);
+            (b[0] - a[0]).hypot(b[1] - a[1])
+        })
+        .collect();
+    let total: f64 = lengths.iter().sum();
+
+    // splits the extra points by length, handing out the remainders to the edges that lost the
+    // most to rounding
+    let shares: Vec<f64> = lengths
+        .iter()
+        .map(|length| {
+            if total > 0.0 {
+                extra as f64 * length / total
+            } else {
+                extra as f64 / n as f64
+            }
+        })
+        .collect();
+    let mut splits: Vec<usize> = shares.iter().map(|share| *share as usize).collect();
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&a, &b| {
+        let remainder = |i: usize| shares[i] - splits[i] as f64;
+        remainder(b).total_cmp(&remainder(a))
+    });
+    let given: usize = splits.iter().sum();
+    for &i in order.iter().cycle().take(extra - given) {
+        splits[i] += 1;
+    }
+
+    (0..n)
+        .flat_map(|i| {
+            let (a, b) = (points[i], points[(i + 1) % n]);
+            let pieces = splits[i] + 1;
+            (0..pieces).map(move |piece| {
+                let t = piece as f64 / pieces as f64;
+                [a[0] + (b[0] - a[0]) * t, a[1] + (b[1] - a[1]) * t]
+            })
+        })
+        .collect()
+}
+
+/// The point closest to the +X direction from the center, where the first section starts
+fn start(points: &[[f64; 2]]) -> usize {
+    let [cx, cy] = centroid(points);
+    let angle = |p: &[f64; 2]| (p[1] - cy).atan2(p[0] - cx).abs();
+    (0..points.len())
+        .min_by(|&a, &b| angle(&points[a]).total_cmp(&angle(&points[b])))
+        .unwrap_or_default()
+}
+
+/// The rotation of the points that best lines them up with the previous section, relative to
+/// each section's center
+fn closest_shift(previous: &[[f64; 2]], points: &[[f64; 2]]) -> usize {
+    let (p, q) = (centroid(previous), centroid(points));
+    let count = points.len();
+    let cost = |shift: usize| -> f64 {
+        (0..count)
+            .map(|i| {
+                let (a, b) = (previous[i], points[(i + shift) % count]);
+                let (dx, dy) = ((b[0] - q[0]) - (a[0] - p[0]), (b[1] - q[1]) - (a[1] - p[1]));
+                dx * dx + dy * dy
+            })
+            .sum()
+    };
+    (0..count)
+        .min_by(|&a, &b| cost(a).total_cmp(&cost(b)))
+        .unwrap_or_default()
+}
+
+#[test]
+fn loft_test() {
+    let triangle = [[0.0, 0.0], [2.0, 0.0], [0.0, 2.0]];
+    let square = [[0.0, 0.0], [0.0, 2.0], [2.0, 2.0], [2.0, 0.0]];
+
+    let loft = loft([Section::new(&triangle), Section::new(&square).at(3.0)]);
+    let rings = loft.rings();
+    assert_eq!(rings[0].len(), 4);
+    // the square is counter-clockwise, starting from the corner that matches the triangle's
+    assert_eq!(rings[1], [[2.0, 0.0], [2.0, 2.0], [0.0, 2.0], [0.0, 0.0]]);
+    insta::assert_snapshot!(loft.into_object());
+
+    assert_eq!(resample(&square, 6).len(), 6);
+    assert_eq!(
+        resample(&[[0.0, 0.0], [4.0, 0.0], [4.0, 1.0]], 5),
+        [[0.0, 0.0], [2.0, 0.0], [4.0, 0.0], [4.0, 1.0], [2.0, 0.5]]
+    );
+}
+
+#[cfg(feature = "mesh")]
+#[test]
+fn loft_volume_test() {
+    let volume = |loft: Loft| loft.into_object().to_mesh().unwrap().volume();
+    let square = [[-1.0, -1.0], [1.0, -1.0], [1.0, 1.0], [-1.0, 1.0]];
+    let small = square.map(|[x, y]| [x / 2.0, y / 2.0]);
+
+    let prism = volume(loft([Section::new(&square), Section::new(&square).at(3.0)]));
+    assert!((prism - 12.0).abs() < 1e-9, "{prism}");
+
+    // a frustum, stacked in either direction
+    let frustum = volume(loft([Section::new(&square), Section::new(&small).at(3.0)]));
+    assert!((frustum - 7.0).abs() < 1e-9, "{frustum}");
+    let frustum = volume(loft([Section::new(&square).at(3.0), Section::new(&small)]));
+    assert!((frustum - 7.0).abs() < 1e-9, "{frustum}");
+
+    // a round duct into a square port, which stays between the two
+    let round: Vec<_> = (0..32)
+        .map(|i| {
+            let (sin, cos) = (i as f64 / 32.0 * core::f64::consts::TAU).sin_cos();
+            [cos, sin]
+        })
+        .collect();
+    let duct = volume(loft([
+        Section::new(&round),
+        Section::new(&square).at(2.0).rotate([0.0, 0.0, 45.0]),
+    ]));
+    assert!(duct > 6.0 && duct < 8.0, "{duct}");
+}
@@ -0,0 +1,64 @@
+use crate::{
+    math::{round, Vec3},
+    polyhedron, IntoObject, Object,
+};
+
+/// Joins rings of matching points into a polyhedron, capping the first and last rings unless
+/// the rings loop back around
+pub(crate) fn skin(rings: Vec<Vec<Vec3>>, closed: bool) -> Object<3> {
+    let count = rings[0].len();
+    let index = |ring: usize, point: usize| ring * count + point % count;
+    let mut faces: Vec<Vec<usize>> = vec![];
+
+    let sides = if closed { rings.len() } else { rings.len() - 1 };
+    for ring in 0..sides {
+        let next = (ring + 1) % rings.len();
+        for point in 0..count {
+            let (a, b) = (index(ring, point), index(ring, point + 1));
+            let (c, d) = (index(next, point + 1), index(next, point));
+            let corners = [a, b, c, d].map(|i| rings[i / count][i % count]);
+
+            // twisted sides aren't flat, so they're split into triangles
+            let normal = (corners[3] - corners[0]).cross(corners[2] - corners[0]);
+            let flat = (corners[1] - corners[0]).dot(normal.unit()).abs() < 1e-9;
+            if flat {
+                faces.push(vec![a, d, c, b]);
+            } else {
+                faces.push(vec![a, d, c]);
+                faces.push(vec![a, c, b]);
+            }
+        }
+    }
+
+    if !closed {
+        let last = rings.len() - 1;
+        faces.push((0..count).map(|point| index(0, point)).collect());
+        faces.push((0..count).rev().map(|point| index(last, point)).collect());
+    }
+
+    let points: Vec<_> = rings.into_iter().flatten().collect();
+
+    // faces need to be clockwise when seen from outside, which depends on the winding of the
+    // rings and the direction they're stacked in
+    let volume: f64 = faces
+        .iter()
+        .flat_map(|face| {
+            let points = &points;
+            let first = points[face[0]];
+            face.windows(2)
+                .skip(1)
+                .map(move |pair| first.dot(points[pair[0]].cross(points[pair[1]])))
+        })
+        .sum();
+    if volume > 0.0 {
+        for face in &mut faces {
+            face.reverse();
+        }
+    }
+
+    let points: Vec<_> = points
+        .into_iter()
+        .map(|p| [p.x, p.y, p.z].map(round))
+        .collect();
+    polyhedron(points, faces).into_object()
+}
@@ -0,0 +1,288 @@
+//! Sweeping a 2D profile along a 3D path, for pipes, handles and cable channels
+//!
+//! ```
+//! use rsolid::{sweep::sweep, *};
+//!
+//! let profile = [[-1.0, -1.0], [1.0, -1.0], [1.0, 1.0], [-1.0, 1.0]];
+//! let path = [[0.0, 0.0, 0.0], [0.0, 0.0, 10.0], [10.0, 0.0, 20.0]];
+//!
+//! let channel = sweep(&profile, &path).twist(90.0).scale(0.5);
+//! let _ = channel.into_object() - cube(5);
+//! ```
+
+use super::skin::skin;
+use crate::{math::Vec3, IntoObject, Object};
+
+/// Sweeps a profile along a path
+///
+/// The profile is placed on a plane perpendicular to the path at each of its points. Its frame
+/// turns as little as it can along the path, using rotation minimizing frames, so it doesn't
+/// spin around bends. It starts with the profile's Y axis pointing as close to +Z as it can, or
+/// along ±Y when the path starts out vertical.
+///
+/// At each point the profile is perpendicular to the average direction of the segments on either
+/// side, so it narrows around sharp corners. Paths with more points bend more smoothly.
+///
+/// # Panics
+///
+/// Panics if the profile has fewer than 3 points or the path has fewer than 2.
+pub fn sweep(profile: &[[f64; 2]], path: &[[f64; 3]]) -> Sweep {
+    assert!(
+        profile.len() >= 3,
+        "a sweep profile needs at least 3 points"
+    );
+    assert!(path.len() >= 2, "a sweep path needs at least 2 points");
+
+    Sweep {
+        profile: profile.to_vec(),
+        path: path.iter().copied().map(Vec3::from).collect(),
+        twist: 0.0,
+        scale: [1.0; 2],
+        closed: false,
+    }
+}
+
+#[derive(Clone, Debug)]
+#[must_use = "Objects must be returned in order to be rendered"]
+pub struct Sweep {
+    profile: Vec<[f64; 2]>,
+    path: Vec<Vec3>,
+    twist: f64,
+    scale: [f64; 2],
+    closed: bool,
+}
+
+impl Sweep {
+    /// Turns the profile by the given degrees over the length of the path
+    ///
+    /// Positive twists turn clockwise when looking back down the path from its end, like
+    /// `linear_extrude`. Closed sweeps need a multiple of 360 to line up.
+    #[inline]
+    pub fn twist(mut self, degrees: f64) -> Self {
+        self.twist = degrees;
+        self
+    }
+
+    /// Scales the profile by the given factor by the end of the path
+    #[inline]
+    pub fn scale(self, scale: f64) -> Self {
+        self.scale_xy(scale, scale)
+    }
+
+    /// Scales the profile along its X and Y axes by the end of the path
+    #[inline]
+    pub fn scale_xy(mut self, x: f64, y: f64) -> Self {
+        self.scale = [x, y];
+        self
+    }
+
+    /// Joins the end of the path back to its start, like for a ring, instead of capping the ends
+    #[inline]
+    pub fn closed(mut self, closed: bool) -> Self {
+        self.closed = closed;
+        self
+    }
+
+    /// The points of each ring of the skin, in path order
+    fn rings(&self) -> Vec<Vec<Vec3>> {
+        let frames = self.frames();
+        let distances = self.distances();
+        let total = distances.last().copied().unwrap_or_default();
+
+        self.path
+            .iter()
+            .zip(frames)
+            .zip(&distances[..self.path.len()])
+            .map(|((point, (u, v)), distance)| {
+                let t = if total > 0.0 { distance / total } else { 0.0 };
+                let [sx, sy] = self.scale.map(|scale| 1.0 + (scale - 1.0) * t);
+                let (sin, cos) = (-self.twist * t).to_radians().sin_cos();
+
+                self.profile
+                    .iter()
+                    .map(|[x, y]| {
+                        let (x, y) = (x * sx, y * sy);
+                        let (x, y) = (x * cos - y * sin, x * sin + y * cos);
+                        *point + u * x + v * y
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// The distance along the path to each point, with the length of the closing segment at the
+    /// end for closed paths
+    fn distances(&self) -> Vec<f64> {
+        let mut points = self.path.clone();
+        if self.closed {
+            points.push(self.path[0]);
+        }
+
+        let mut distance = 0.0;
+        let mut out = vec![0.0];
+        for pair in points.windows(2) {
+            distance += (pair[1] - pair[0]).length();
+            out.push(distance);
+        }
+        out
+    }
+
+    /// The profile's X and Y axes at each point of the path
+    fn frames(&self) -> Vec<(Vec3, Vec3)> {
+        let path = &self.path;
+        let count = path.len();
+        let tangents: Vec<_> = (0..count).map(|i| self.tangent(i)).collect();
+
+        let z = Vec3::new(0.0, 0.0, 1.0);
+        let first = tangents[0];
+        let mut u = if first.z.abs() > 1.0 - 1e-9 {
+            Vec3::new(1.0, 0.0, 0.0)
+        } else {
+            let v = (z - first * z.dot(first)).unit();
+            v.cross(first)
+        };
+
+        // propagates the frame with the double reflection method, from "Computation of Rotation
+        // Minimizing Frames" by Wang et al.
+        let reflect = |u: Vec3, from: usize, to: usize| {
+            let (a, b) = (path[from], path[to]);
+            let v1 = b - a;
+            let c1 = v1.dot(v1);
+            if c1 == 0.0 {
+                return u;
+            }
+            let u_l = u - v1 * (2.0 / c1 * v1.dot(u));
+            let t_l = tangents[from] - v1 * (2.0 / c1 * v1.dot(tangents[from]));
+            let v2 = tangents[to] - t_l;
+            let c2 = v2.dot(v2);
+            if c2 < 1e-18 {
+                return u_l;
+            }
+            u_l - v2 * (2.0 / c2 * v2.dot(u_l))
+        };
+
+        let mut axes = vec![u];
+        for i in 1..count {
+            u = reflect(u, i - 1, i);
+            axes.push(u);
+        }
+
+        if self.closed {
+            // the frame doesn't line up after going around a loop, so the difference is spread
+            // out over the length of the path
+            let end = reflect(u, count - 1, 0);
+            let angle = end.cross(axes[0]).dot(first).atan2(end.dot(axes[0]));
+            let distances = self.distances();
+            let total = distances[count];
+            for (i, u) in axes.iter_mut().enumerate() {
+                let t = tangents[i];
+                let (sin, cos) = (angle * distances[i] / total).sin_cos();
+                *u = *u * cos + t.cross(*u) * sin;
+            }
+        }
+
+        axes.into_iter()
+            .zip(tangents)
+            .map(|(u, t)| {
+                let u = (u - t * u.dot(t)).unit();
+                (u, t.cross(u))
+            })
+            .collect()
+    }
+
+    /// The direction of the path at a point, halfway between the segments on either side
+    fn tangent(&self, i: usize) -> Vec3 {
+        let path = &self.path;
+        let count = path.len();
+        let before = if i > 0 {
+            Some(path[i] - path[i - 1])
+        } else if self.closed {
+            Some(path[0] - path[count - 1])
+        } else {
+            None
+        };
+        let after = if i + 1 < count {
+            Some(path[i + 1] - path[i])
+        } else if self.closed {
+            Some(path[0] - path[i])
+        } else {
+            None
+        };
+
+        match (before.map(Vec3::unit), after.map(Vec3::unit)) {
+            (Some(before), Some(after)) if (before + after).length() > 1e-9 => {
+                (before + after).unit()
+            }
+            (_, Some(after)) => after,
+            (Some(before), None) => before,
+            (None, None) => Vec3::new(0.0, 0.0, 1.0),
+        }
+    }
+}
+
+impl IntoObject<3> for Sweep {
+    #[inline]
+    fn into_object(self) -> Object<3> {
+        skin(self.rings(), self.closed)
+    }
+}
+
+impl From<Sweep> for Object<3> {
+    #[inline]
+    fn from(value: Sweep) -> Self {
+        value.into_object()
+    }
+}
+
+#[test]
+fn sweep_test() {
+    use crate::{scad::Scad, *};
+
+    let square = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]];
+    let bend = [[0.0, 0.0, 0.0], [0.0, 0.0, 4.0], [4.0, 0.0, 4.0]];
+    insta::assert_snapshot!(sweep(&square, &bend).into_object());
+
+    // a square path around the Z axis, with the profile reversed
+    let ring = [
+        [5.0, 0.0, 0.0],
+        [0.0, 5.0, 0.0],
+        [-5.0, 0.0, 0.0],
+        [0.0, -5.0, 0.0],
+    ];
+    let profile: Vec<_> = square.iter().rev().copied().collect();
+    let ring = sweep(&profile, &ring).closed(true).into_object();
+    assert_eq!(ring.children().len(), 0);
+    assert!(ring.to_scad().contains("polyhedron("));
+}
+
+#[cfg(feature = "mesh")]
+#[test]
+fn sweep_volume_test() {
+    let square = [[-1.0, -1.0], [1.0, -1.0], [1.0, 1.0], [-1.0, 1.0]];
+    let volume = |sweep: Sweep| sweep.into_object().to_mesh().unwrap().volume();
+
+    let straight = [[0.0, 0.0, 0.0], [0.0, 0.0, 5.0], [0.0, 0.0, 10.0]];
+    assert!((volume(sweep(&square, &straight)) - 40.0).abs() < 1e-9);
+    // a frustum, ending at half the size
+    let frustum = volume(sweep(&square, &straight).scale(0.5));
+    assert!(
+        (frustum - 10.0 / 3.0 * (4.0 + 1.0 + 2.0)).abs() < 1e-9,
+        "{frustum}"
+    );
+
+    // a twisted bar keeps the volume of its profile, apart from the sides being split into
+    // triangles
+    let path: Vec<_> = (0..=40).map(|i| [0.0, 0.0, i as f64 / 4.0]).collect();
+    let twisted = volume(sweep(&square, &path).twist(90.0));
+    assert!((twisted - 40.0).abs() < 1.0, "{twisted}");
+
+    // a ring, with the profile's center going around a square of side 10
+    let ring = [
+        [5.0, 5.0, 0.0],
+        [-5.0, 5.0, 0.0],
+        [-5.0, -5.0, 0.0],
+        [5.0, -5.0, 0.0],
+    ];
+    let ring = volume(sweep(&square, &ring).closed(true));
+    assert!(ring > 0.0, "{ring}");
+}
@@ -1,12 +1,15 @@
 macro_rules! assert_2d_snapshot {
-    ($expr:expr) => {{
+    ($expr:expr) => {
+        assert_2d_snapshot!($expr, $crate::export::Drawing::Svg)
+    };
+    ($expr:expr, $format:expr) => {{
         insta::assert_snapshot!($expr);
 
         struct __LOCATION__;
 
         $crate::testing::persist_snapshot(
             $expr,
-            "svg",
+            $format,
             file!(),
             core::any::type_name::<__LOCATION__>(),
         );
@@ -15,19 +18,30 @@ macro_rules! assert_2d_snapshot {
 
 macro_rules! assert_3d_snapshot {
     ($expr:expr) => {
+        assert_3d_snapshot!($expr, $crate::export::Mesh::StlAscii)
+    };
+    ($expr:expr, $format:expr) => {
         insta::assert_snapshot!($expr);
         struct __LOCATION__;
 
         $crate::testing::persist_snapshot(
             $expr,
-            "stl",
+            $format,
             file!(),
             core::any::type_name::<__LOCATION__>(),
         );
     };
 }
 
-pub fn persist_snapshot(expr: impl core::fmt::Display, out: &str, source: &str, module: &str) {
+/// Renders the snapshot next to it, like [`Png::snapshot`](crate::export::Png::snapshot) for
+/// an image
+pub fn persist_snapshot(
+    expr: impl core::fmt::Display,
+    format: impl Into<crate::export::ExportFormat>,
+    source: &str,
+    module: &str,
+) {
+    let format = format.into();
     let mut module: Vec<_> = module.split("::").collect();
     module.pop();
     let module = module.join("__");
@@ -43,13 +57,12 @@ pub fn persist_snapshot(expr: impl core::fmt::Display, out: &str, source: &str,
     std::fs::create_dir_all(src.parent().unwrap()).unwrap();
     std::fs::write(&src, expr.to_string()).unwrap();
 
-    let dest = src.with_extension(out);
+    let dest = src.with_extension(format.ext());
 
     let out = std::process::Command::new("openscad")
         .arg("-o")
         .arg(&dest)
-        .arg("--export-format")
-        .arg(out)
+        .args(format.args())
         .arg(&src)
         .output()
         .unwrap();
@@ -0,0 +1,443 @@
+//! Introspection and rewriting of object trees
+//!
+//! Every object is a node with a module name, typed parameters and child objects. The
+//! children can have different dimensions from their parent, e.g. the 2D child of a
+//! `linear_extrude`, so they are returned as [`AnyObject`]s.
+//!
+//! ```
+//! use rsolid::*;
+//!
+//! let part = cube(10) + sphere(5).up(10);
+//!
+//! // count the spheres in the model
+//! let mut spheres = 0;
+//! part.visit(|node| {
+//!     spheres += (node.name() == "sphere") as usize;
+//!     true
+//! });
+//! assert_eq!(spheres, 1);
+//!
+//! // replace them with cubes
+//! let part = part.fold(|node| match node.downcast_ref::<Sphere>() {
+//!     Some(_) => cube(10).into_object().into(),
+//!     None => node,
+//! });
+//! assert!(!part.to_string().contains("sphere"));
+//! ```
+
+use crate::{
+    block::{Block, Boolean},
+    object::ObjectValue,
+    operator::Wrapped,
+    scad::{self, Scad},
+    IntoObject, Object,
+};
+use core::{any::Any, fmt};
+use std::sync::Arc;
+
+/// The role of a node in the object tree
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NodeKind {
+    /// A module without children, like `cube`
+    Shape,
+    /// A module applied to its children, like `translate`
+    Operator,
+    /// A boolean operation over its children, like `difference`
+    Boolean,
+    /// An implicit union of its children
+    Group,
+}
+
+/// Describes an object in the tree
+///
+/// This is implemented by the generated modules. Objects which contain other objects
+/// also implement [`Node::children`] and [`Node::with_children`] so the tree can be
+/// walked and rebuilt.
+pub trait Node {
+    /// The name of the module, like `cube` or `translate`
+    fn name(&self) -> &str;
+
+    #[inline]
+    fn kind(&self) -> NodeKind {
+        NodeKind::Shape
+    }
+
+    /// The parameters that have been set, by the name of their builder method
+    #[inline]
+    fn parameters(&self) -> Vec<(&'static str, &dyn Any)> {
+        Vec::new()
+    }
+
+    #[inline]
+    fn children(&self) -> Vec<AnyObject> {
+        Vec::new()
+    }
+
+    /// Returns a copy of the node with new children, or `None` if the number or
+    /// dimensions of the children don't fit the node
+    #[inline]
+    fn with_children(&self, children: Vec<AnyObject>) -> Option<AnyObject> {
+        let _ = children;
+        None
+    }
+
+    /// Records problems with the parameters while the node is exported, like an extrusion with a
+    /// negative height, using [`scad::Formatter::warn`]
+    #[inline]
+    fn report(&self, f: &mut scad::Formatter) {
+        let _ = f;
+    }
+}
+
+/// An object of any dimension
+#[derive(Clone)]
+pub struct AnyObject {
+    dimensions: usize,
+    value: Arc<dyn ObjectValue>,
+}
+
+impl<const DIMENSIONS: usize> From<Object<DIMENSIONS>> for AnyObject {
+    #[inline]
+    fn from(value: Object<DIMENSIONS>) -> Self {
+        Self {
+            dimensions: DIMENSIONS,
+            value: value.0,
+        }
+    }
+}
+
+impl<const DIMENSIONS: usize> From<&Object<DIMENSIONS>> for AnyObject {
+    #[inline]
+    fn from(value: &Object<DIMENSIONS>) -> Self {
+        value.clone().into()
+    }
+}
+
+impl fmt::Debug for AnyObject {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+impl fmt::Display for AnyObject {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+impl Scad for AnyObject {
+    fn assign(&self, f: &mut scad::Formatter) -> scad::Assignment {
+        self.value.assign(f)
+    }
+}
+
+impl AnyObject {
+    #[inline]
+    pub fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    /// Returns the object if it has the given dimensions
+    #[inline]
+    pub fn downcast<const DIMENSIONS: usize>(&self) -> Option<Object<DIMENSIONS>> {
+        (self.dimensions == DIMENSIONS).then(|| Object(self.value.clone()))
+    }
+
+    /// The name of the module, like `cube` or `translate`
+    #[inline]
+    pub fn name(&self) -> &str {
+        self.value.name()
+    }
+
+    #[inline]
+    pub fn kind(&self) -> NodeKind {
+        self.value.kind()
+    }
+
+    /// The parameters that have been set, by the name of their builder method
+    #[inline]
+    pub fn parameters(&self) -> Vec<(&'static str, &dyn Any)> {
+        self.value.parameters()
+    }
+
+    /// Returns the parameter with the given name, if it's set and has the type `T`
+    pub fn parameter<T: 'static>(&self, name: &str) -> Option<&T> {
+        let (_, value) = self.parameters().into_iter().find(|(n, _)| *n == name)?;
+        value.downcast_ref()
+    }
+
+    #[inline]
+    pub fn children(&self) -> Vec<AnyObject> {
+        self.value.children()
+    }
+
+    /// Returns the object as its concrete type, like [`Cube`](crate::Cube)
+    #[inline]
+    pub fn downcast_ref<T: 'static>(&self) -> Option<&T> {
+        self.value.as_any().downcast_ref()
+    }
+
+    /// Returns a copy of the object with new children
+    ///
+    /// Objects without children are returned as-is when `children` is empty.
+    pub fn with_children(&self, children: Vec<AnyObject>) -> Option<Self> {
+        if children.is_empty() && self.children().is_empty() {
+            return Some(self.clone());
+        }
+        self.value.with_children(children)
+    }
+
+    /// Calls `f` on the object and then on each of its descendants, depth first
+    ///
+    /// The children of a node are skipped if `f` returns `false`.
+    pub fn visit(&self, mut f: impl FnMut(&AnyObject) -> bool) {
+        fn visit(node: &AnyObject, f: &mut dyn FnMut(&AnyObject) -> bool) {
+            if f(node) {
+                for child in node.children() {
+                    visit(&child, f);
+                }
+            }
+        }
+
+        visit(self, &mut f)
+    }
+
+    /// Rebuilds the tree from the bottom up, replacing each node with the result of `f`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `f` returns a node with different dimensions than the one it replaces.
+    pub fn fold(&self, mut f: impl FnMut(AnyObject) -> AnyObject) -> Self {
+        fn fold(node: &AnyObject, f: &mut dyn FnMut(AnyObject) -> AnyObject) -> AnyObject {
+            let children = node.children();
+            let node = if children.is_empty() {
+                node.clone()
+            } else {
+                let children = children.iter().map(|child| fold(child, f)).collect();
+                node.with_children(children)
+                    .expect("the children of a node keep their dimensions")
+            };
+
+            let dimensions = node.dimensions;
+            let node = f(node);
+            assert_eq!(
+                node.dimensions, dimensions,
+                "a {dimensions}D object was replaced with a {}D object",
+                node.dimensions
+            );
+            node
+        }
+
+        fold(self, &mut f)
+    }
+}
+
+impl<const DIMENSIONS: usize> Object<DIMENSIONS> {
+    /// The name of the module, like `cube` or `translate`
+    #[inline]
+    pub fn name(&self) -> &str {
+        self.0.name()
+    }
+
+    #[inline]
+    pub fn kind(&self) -> NodeKind {
+        self.0.kind()
+    }
+
+    /// The parameters that have been set, by the name of their builder method
+    #[inline]
+    pub fn parameters(&self) -> Vec<(&'static str, &dyn Any)> {
+        self.0.parameters()
+    }
+
+    /// Returns the parameter with the given name, if it's set and has the type `T`
+    pub fn parameter<T: 'static>(&self, name: &str) -> Option<&T> {
+        let (_, value) = self.parameters().into_iter().find(|(n, _)| *n == name)?;
+        value.downcast_ref()
+    }
+
+    #[inline]
+    pub fn children(&self) -> Vec<AnyObject> {
+        self.0.children()
+    }
+
+    /// Returns a copy of the object with new children
+    ///
+    /// Objects without children are returned as-is when `children` is empty.
+    #[inline]
+    pub fn with_children(&self, children: Vec<AnyObject>) -> Option<Self> {
+        AnyObject::from(self).with_children(children)?.downcast()
+    }
+
+    /// Calls `f` on the object and then on each of its descendants, depth first
+    ///
+    /// The children of a node are skipped if `f` returns `false`.
+    #[inline]
+    pub fn visit(&self, f: impl FnMut(&AnyObject) -> bool) {
+        AnyObject::from(self).visit(f)
+    }
+
+    /// Rebuilds the tree from the bottom up, replacing each node with the result of `f`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `f` returns a node with different dimensions than the one it replaces.
+    #[inline]
+    pub fn fold(&self, f: impl FnMut(AnyObject) -> AnyObject) -> Self {
+        AnyObject::from(self).fold(f).downcast().unwrap()
+    }
+}
+
+impl<const DIMENSIONS_IN: usize, const DIMENSIONS_OUT: usize> Node
+    for Wrapped<DIMENSIONS_IN, DIMENSIONS_OUT>
+{
+    #[inline]
+    fn name(&self) -> &str {
+        self.parent.name()
+    }
+
+    #[inline]
+    fn kind(&self) -> NodeKind {
+        self.parent.kind()
+    }
+
+    #[inline]
+    fn parameters(&self) -> Vec<(&'static str, &dyn Any)> {
+        self.parent.parameters()
+    }
+
+    #[inline]
+    fn children(&self) -> Vec<AnyObject> {
+        vec![self.child.clone().into()]
+    }
+
+    fn with_children(&self, children: Vec<AnyObject>) -> Option<AnyObject> {
+        let [child] = <[AnyObject; 1]>::try_from(children).ok()?;
+        let wrapped = Self {
+            parent: self.parent.clone(),
+            child: child.downcast()?,
+        };
+        Some(wrapped.into_object().into())
+    }
+}
+
+impl<const DIMENSIONS: usize> Node for Block<DIMENSIONS> {
+    #[inline]
+    fn name(&self) -> &str {
+        "group"
+    }
+
+    #[inline]
+    fn kind(&self) -> NodeKind {
+        NodeKind::Group
+    }
+
+    #[inline]
+    fn children(&self) -> Vec<AnyObject> {
+        self.0.iter().map(AnyObject::from).collect()
+    }
+
+    fn with_children(&self, children: Vec<AnyObject>) -> Option<AnyObject> {
+        let children = children
+            .iter()
+            .map(AnyObject::downcast::<DIMENSIONS>)
+            .collect::<Option<_>>()?;
+        Some(Block(children).into_object().into())
+    }
+}
+
+impl<const DIMENSIONS: usize> Node for Boolean<DIMENSIONS> {
+    #[inline]
+    fn name(&self) -> &str {
+        self.kind.name()
+    }
+
+    #[inline]
+    fn kind(&self) -> NodeKind {
+        NodeKind::Boolean
+    }
+
+    #[inline]
+    fn children(&self) -> Vec<AnyObject> {
+        self.children.iter().map(AnyObject::from).collect()
+    }
+
+    fn with_children(&self, children: Vec<AnyObject>) -> Option<AnyObject> {
+        let children = children
+            .iter()
+            .map(AnyObject::downcast::<DIMENSIONS>)
+            .collect::<Option<_>>()?;
+        let boolean = Self {
+            kind: self.kind,
+            children,
+        };
+        Some(boolean.into_object().into())
+    }
+}
+
+#[test]
+fn visit_test() {
+    use crate::*;
+
+    let part = (cube(10) - cylinder(12, 3).right(5)) + (square(4) >> linear_extrude(2));
+
+    let mut names = vec![];
+    part.visit(|node| {
+        names.push(format!("{:?} {}", node.kind(), node.name()));
+        true
+    });
+    insta::assert_debug_snapshot!(names);
+
+    let mut shapes = 0;
+    part.visit(|node| {
+        shapes += (node.kind() == NodeKind::Shape) as usize;
+        node.name() != "difference"
+    });
+    assert_eq!(shapes, 1);
+
+    let moved = cube(10).right(5).into_object();
+    assert_eq!(moved.kind(), NodeKind::Operator);
+    assert_eq!(moved.parameters().len(), 1);
+    let child = &moved.children()[0];
+    assert!(child.downcast_ref::<Cube>().is_some());
+    assert!(matches!(
+        child.parameter::<CubeSize>("size"),
+        Some(CubeSize::Length(_))
+    ));
+    assert!(child.parameter::<Length>("size").is_none());
+}
+
+#[test]
+fn fold_test() {
+    use crate::*;
+
+    let part = cube(10).translate([1, 0, 0]).translate([0, 2, 0])
+        + (circle(5) >> linear_extrude(2)).translate([0, 0, 3]);
+
+    // merge nested translations into a single one
+    let part = part.fold(|node| {
+        let v = |node: &AnyObject| match node.name() {
+            "translate" => node.parameter::<Length3>("value").cloned(),
+            _ => None,
+        };
+        let Some(outer) = v(&node) else {
+            return node;
+        };
+        let inner = node.children().remove(0);
+        let Some(v) = v(&inner) else {
+            return node;
+        };
+        let v = Length3(core::array::from_fn(|idx| {
+            outer.0[idx].clone() + v.0[idx].clone()
+        }));
+        let child = inner.children().remove(0).downcast::<3>().unwrap();
+        (child >> translate(v)).into()
+    });
+    insta::assert_snapshot!(part);
+
+    // rebuilding with children that don't fit fails
+    let square = AnyObject::from(square(1).into_object());
+    assert!(part.with_children(vec![square.clone()]).is_none());
+    let extrude = &part.children()[1].children()[0];
+    assert!(extrude.with_children(vec![square]).is_some());
+}
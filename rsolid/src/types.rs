@@ -1,6 +1,12 @@
-use crate::scad::Scad;
+use crate::{
+    expr::{BinaryOp, Condition, Expr},
+    scad::Scad,
+};
 use core::{fmt, ops};
 
+mod color;
+pub use color::*;
+
 impl Scad for bool {
     fn assign(&self, f: &mut crate::scad::Formatter) -> crate::scad::Assignment {
         f.value(self)
@@ -15,20 +21,20 @@ impl Scad for String {
 
 macro_rules! impl_simple {
     ($name:ident) => {
-        #[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
-        pub struct $name(pub f64);
+        #[derive(Clone, Debug, Default, PartialEq)]
+        pub struct $name(pub Expr);
 
         impl From<f64> for $name {
             #[inline]
             fn from(value: f64) -> Self {
-                Self(value)
+                Self(value.into())
             }
         }
 
         impl From<i64> for $name {
             #[inline]
             fn from(value: i64) -> Self {
-                Self(value as f64)
+                Self(value.into())
             }
         }
 
@@ -40,7 +46,7 @@ macro_rules! impl_simple {
 
         impl Scad for $name {
             fn assign(&self, f: &mut crate::scad::Formatter) -> crate::scad::Assignment {
-                f.value(self)
+                self.0.assign(f)
             }
         }
     };
@@ -48,6 +54,57 @@ macro_rules! impl_simple {
 
 macro_rules! impl_measure_ops {
     ($name:ident) => {
+        impl From<Expr> for $name {
+            #[inline]
+            fn from(value: Expr) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<$name> for Expr {
+            #[inline]
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl $name {
+            #[inline]
+            pub fn min(self, other: impl Into<Self>) -> Self {
+                Self(Expr::call("min", vec![self.0, other.into().0]))
+            }
+
+            #[inline]
+            pub fn max(self, other: impl Into<Self>) -> Self {
+                Self(Expr::call("max", vec![self.0, other.into().0]))
+            }
+
+            #[inline]
+            pub fn abs(self) -> Self {
+                Self(Expr::call("abs", vec![self.0]))
+            }
+
+            #[inline]
+            pub fn lt(self, other: impl Into<Self>) -> Condition {
+                Condition(Expr::binary(BinaryOp::Lt, self.0, other.into().0))
+            }
+
+            #[inline]
+            pub fn le(self, other: impl Into<Self>) -> Condition {
+                Condition(Expr::binary(BinaryOp::Le, self.0, other.into().0))
+            }
+
+            #[inline]
+            pub fn gt(self, other: impl Into<Self>) -> Condition {
+                Condition(Expr::binary(BinaryOp::Gt, self.0, other.into().0))
+            }
+
+            #[inline]
+            pub fn ge(self, other: impl Into<Self>) -> Condition {
+                Condition(Expr::binary(BinaryOp::Ge, self.0, other.into().0))
+            }
+        }
+
         impl ops::Neg for $name {
             type Output = Self;
 
@@ -56,6 +113,60 @@ macro_rules! impl_measure_ops {
                 Self(-self.0)
             }
         }
+
+        impl<R: Into<$name>> ops::Add<R> for $name {
+            type Output = Self;
+
+            #[inline]
+            fn add(self, other: R) -> Self {
+                Self(Expr::binary(BinaryOp::Add, self.0, other.into().0))
+            }
+        }
+
+        impl<R: Into<$name>> ops::Sub<R> for $name {
+            type Output = Self;
+
+            #[inline]
+            fn sub(self, other: R) -> Self {
+                Self(Expr::binary(BinaryOp::Sub, self.0, other.into().0))
+            }
+        }
+
+        impl<R: Into<Scalar>> ops::Mul<R> for $name {
+            type Output = Self;
+
+            #[inline]
+            fn mul(self, other: R) -> Self {
+                Self(Expr::binary(BinaryOp::Mul, self.0, other.into().0))
+            }
+        }
+
+        impl<R: Into<Scalar>> ops::Div<R> for $name {
+            type Output = Self;
+
+            #[inline]
+            fn div(self, other: R) -> Self {
+                Self(Expr::binary(BinaryOp::Div, self.0, other.into().0))
+            }
+        }
+
+        impl<R: Into<Scalar>> ops::Rem<R> for $name {
+            type Output = Self;
+
+            #[inline]
+            fn rem(self, other: R) -> Self {
+                Self(Expr::binary(BinaryOp::Rem, self.0, other.into().0))
+            }
+        }
+
+        impl ops::Mul<$name> for f64 {
+            type Output = $name;
+
+            #[inline]
+            fn mul(self, other: $name) -> $name {
+                other * self
+            }
+        }
     };
 }
 
@@ -67,9 +178,88 @@ impl_simple!(Scalar);
 impl_measure_ops!(Scalar);
 impl_simple!(FragmentResolution);
 
+impl ops::Mul<Length> for Scalar {
+    type Output = Length;
+
+    #[inline]
+    fn mul(self, other: Length) -> Length {
+        Length(Expr::binary(BinaryOp::Mul, self.0, other.0))
+    }
+}
+
+impl ops::Mul<Angle> for Scalar {
+    type Output = Angle;
+
+    #[inline]
+    fn mul(self, other: Angle) -> Angle {
+        Angle(Expr::binary(BinaryOp::Mul, self.0, other.0))
+    }
+}
+
+impl ops::Div<Length> for Length {
+    type Output = Scalar;
+
+    #[inline]
+    fn div(self, other: Length) -> Scalar {
+        Scalar(Expr::binary(BinaryOp::Div, self.0, other.0))
+    }
+}
+
+impl Angle {
+    #[inline]
+    pub fn sin(self) -> Scalar {
+        Scalar(Expr::call("sin", vec![self.0]))
+    }
+
+    #[inline]
+    pub fn cos(self) -> Scalar {
+        Scalar(Expr::call("cos", vec![self.0]))
+    }
+
+    #[inline]
+    pub fn tan(self) -> Scalar {
+        Scalar(Expr::call("tan", vec![self.0]))
+    }
+}
+
+impl Scalar {
+    #[inline]
+    pub fn asin(self) -> Angle {
+        Angle(Expr::call("asin", vec![self.0]))
+    }
+
+    #[inline]
+    pub fn acos(self) -> Angle {
+        Angle(Expr::call("acos", vec![self.0]))
+    }
+
+    #[inline]
+    pub fn atan(self) -> Angle {
+        Angle(Expr::call("atan", vec![self.0]))
+    }
+
+    #[inline]
+    pub fn sqrt(self) -> Self {
+        Self(Expr::call("sqrt", vec![self.0]))
+    }
+
+    #[inline]
+    pub fn pow(self, exponent: impl Into<Self>) -> Self {
+        Self(Expr::call("pow", vec![self.0, exponent.into().0]))
+    }
+}
+
+impl Length {
+    /// The angle between the positive X axis and the point `(x, y)`
+    #[inline]
+    pub fn atan2(y: impl Into<Self>, x: impl Into<Self>) -> Angle {
+        Angle(Expr::call("atan2", vec![y.into().0, x.into().0]))
+    }
+}
+
 macro_rules! impl_vec {
     ($name:ident, $vec:ident, $inner:ty, $count:literal) => {
-        #[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+        #[derive(Clone, Debug, Default, PartialEq)]
         pub struct $name(pub [$inner; $count]);
 
         impl fmt::Display for $name {
@@ -103,21 +293,21 @@ macro_rules! impl_vec {
         impl From<f64> for $name {
             #[inline]
             fn from(value: f64) -> Self {
-                Self([value.into(); $count])
+                <$inner>::from(value).into()
             }
         }
 
         impl From<i64> for $name {
             #[inline]
             fn from(value: i64) -> Self {
-                Self([value.into(); $count])
+                <$inner>::from(value).into()
             }
         }
 
         impl From<$inner> for $name {
             #[inline]
             fn from(value: $inner) -> Self {
-                Self([value; $count])
+                Self(core::array::from_fn(|_| value.clone()))
             }
         }
 
@@ -142,7 +332,7 @@ macro_rules! impl_vec {
             }
         }
 
-        #[derive(Clone, Debug, Default, PartialEq, PartialOrd)]
+        #[derive(Clone, Debug, Default, PartialEq)]
         pub struct $vec(pub std::vec::Vec<$name>);
 
         impl fmt::Display for $vec {
@@ -195,3 +385,94 @@ impl_vec!(Length2, VecLength2, Length, 2);
 impl_vec!(Length3, VecLength3, Length, 3);
 impl_vec!(Scalar2, VecScalar2, Scalar, 2);
 impl_vec!(Scalar3, VecScalar3, Scalar, 3);
+
+/// A list of point indices describing a single face of a polyhedron or path of a polygon
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Face(pub std::vec::Vec<usize>);
+
+impl fmt::Display for Face {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.0.iter()).finish()
+    }
+}
+
+impl Scad for Face {
+    fn assign(&self, f: &mut crate::scad::Formatter) -> crate::scad::Assignment {
+        f.value(self)
+    }
+}
+
+impl<const N: usize> From<[usize; N]> for Face {
+    #[inline]
+    fn from(value: [usize; N]) -> Self {
+        Self(value.to_vec())
+    }
+}
+
+impl From<&[usize]> for Face {
+    #[inline]
+    fn from(value: &[usize]) -> Self {
+        Self(value.to_vec())
+    }
+}
+
+impl From<std::vec::Vec<usize>> for Face {
+    #[inline]
+    fn from(value: std::vec::Vec<usize>) -> Self {
+        Self(value)
+    }
+}
+
+impl std::iter::FromIterator<usize> for Face {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        Self(iter.into_iter().collect())
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct VecFace(pub std::vec::Vec<Face>);
+
+impl fmt::Display for VecFace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.0.iter()).finish()
+    }
+}
+
+impl Scad for VecFace {
+    fn assign(&self, f: &mut crate::scad::Formatter) -> crate::scad::Assignment {
+        let mut out = "[".to_string();
+        for (idx, arg) in self.0.iter().enumerate() {
+            if idx != 0 {
+                out.push_str(", ");
+            }
+            out += &arg.assign(f).to_string();
+        }
+        out.push(']');
+        f.value(out)
+    }
+}
+
+impl<T> std::iter::FromIterator<T> for VecFace
+where
+    Face: From<T>,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        Self(iter.into_iter().map(|v| v.into()).collect())
+    }
+}
+
+impl<I, T> From<I> for VecFace
+where
+    I: IntoIterator<Item = T>,
+    Face: From<T>,
+{
+    fn from(v: I) -> Self {
+        Self::from_iter(v)
+    }
+}
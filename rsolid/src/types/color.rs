@@ -0,0 +1,398 @@
+use crate::scad::Scad;
+use core::{fmt, str::FromStr};
+
+/// A color that can be passed to OpenSCAD's `color()` module
+///
+/// Colors are validated when they are built, so an unknown name or an out-of-range
+/// channel is reported at the call site instead of being silently ignored by OpenSCAD.
+/// Literals like `"red"` or `[1.0, 0.0, 0.0]` convert with `From` and panic if they are
+/// invalid, while values only known at runtime should go through [`str::parse`],
+/// `try_from` or [`Color::try_rgba`].
+///
+/// ```
+/// use rsolid::*;
+///
+/// let part = cube(1).color("red") + cube(2).color([0.0, 0.0, 1.0, 0.5]);
+/// let name = String::from("bogus");
+/// assert!(Color::try_from(name).is_err());
+/// assert!(Color::try_rgba(2.0, 0.0, 0.0, 1.0).is_err());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct Color(Inner);
+
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+enum Inner {
+    Named(&'static str),
+    Rgba([f64; 4]),
+}
+
+impl Color {
+    /// Creates an opaque color from red, green and blue channels in the range `0.0..=1.0`
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the channels are out of range.
+    #[inline]
+    pub fn rgb(r: f64, g: f64, b: f64) -> Self {
+        Self::rgba(r, g, b, 1.0)
+    }
+
+    /// Creates a color from red, green, blue and alpha channels in the range `0.0..=1.0`
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the channels are out of range.
+    #[inline]
+    pub fn rgba(r: f64, g: f64, b: f64, a: f64) -> Self {
+        Self::try_rgba(r, g, b, a).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Creates a color from red, green, blue and alpha channels in the range `0.0..=1.0`
+    pub fn try_rgba(r: f64, g: f64, b: f64, a: f64) -> Result<Self, ColorError> {
+        let channels = [r, g, b, a];
+        for value in channels {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(ColorError::OutOfRange(value));
+            }
+        }
+        Ok(Self(Inner::Rgba(channels)))
+    }
+
+    /// Looks up a CSS/SVG color name, ignoring case
+    pub fn named(name: &str) -> Result<Self, ColorError> {
+        NAMES
+            .iter()
+            .find(|known| known.eq_ignore_ascii_case(name))
+            .map(|name| Self(Inner::Named(name)))
+            .ok_or_else(|| ColorError::UnknownName(name.to_string()))
+    }
+
+    /// Parses a hex color in the `#rgb`, `#rgba`, `#rrggbb` or `#rrggbbaa` forms
+    pub fn hex(value: &str) -> Result<Self, ColorError> {
+        let invalid = || ColorError::InvalidHex(value.to_string());
+
+        let digits = value.strip_prefix('#').ok_or_else(invalid)?;
+
+        if !digits.is_ascii() {
+            return Err(invalid());
+        }
+
+        let (width, count) = match digits.len() {
+            3 => (1, 3),
+            4 => (1, 4),
+            6 => (2, 3),
+            8 => (2, 4),
+            _ => return Err(invalid()),
+        };
+
+        let mut channels = [1.0; 4];
+        for (idx, channel) in channels.iter_mut().enumerate().take(count) {
+            let digit = &digits[idx * width..(idx + 1) * width];
+            let digit = u8::from_str_radix(digit, 16).map_err(|_| invalid())?;
+            // expand the short form so `#f00` is the same as `#ff0000`
+            let digit = if width == 1 { digit * 17 } else { digit };
+            *channel = digit as f64 / 255.0;
+        }
+
+        Ok(Self(Inner::Rgba(channels)))
+    }
+
+    /// The lowercase CSS/SVG name, if the color was built from one
+    #[inline]
+    pub fn name(&self) -> Option<&'static str> {
+        match self.0 {
+            Inner::Named(name) => Some(name),
+            Inner::Rgba(_) => None,
+        }
+    }
+
+    /// The red, green, blue and alpha channels, if the color was built from them or a hex string
+    #[inline]
+    pub fn channels(&self) -> Option<[f64; 4]> {
+        match self.0 {
+            Inner::Named(_) => None,
+            Inner::Rgba(channels) => Some(channels),
+        }
+    }
+}
+
+impl FromStr for Color {
+    type Err = ColorError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value.starts_with('#') {
+            Self::hex(value)
+        } else {
+            Self::named(value)
+        }
+    }
+}
+
+impl From<&str> for Color {
+    /// # Panics
+    ///
+    /// Panics if the value is not a known color name or a valid hex color. Use
+    /// [`str::parse`] for names that aren't known ahead of time.
+    #[inline]
+    fn from(value: &str) -> Self {
+        value.parse().unwrap_or_else(|err| panic!("{err}"))
+    }
+}
+
+impl TryFrom<String> for Color {
+    type Error = ColorError;
+
+    #[inline]
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<[f64; 3]> for Color {
+    /// # Panics
+    ///
+    /// Panics if any of the channels are out of range. Use [`Color::try_rgba`] for channels
+    /// that aren't known ahead of time.
+    #[inline]
+    fn from([r, g, b]: [f64; 3]) -> Self {
+        Self::rgb(r, g, b)
+    }
+}
+
+impl From<[f64; 4]> for Color {
+    /// # Panics
+    ///
+    /// Panics if any of the channels are out of range. Use [`Color::try_rgba`] for channels
+    /// that aren't known ahead of time.
+    #[inline]
+    fn from([r, g, b, a]: [f64; 4]) -> Self {
+        Self::rgba(r, g, b, a)
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Inner::Named(name) => write!(f, "{name:?}"),
+            Inner::Rgba([r, g, b, a]) => write!(f, "[{r}, {g}, {b}, {a}]"),
+        }
+    }
+}
+
+impl Scad for Color {
+    fn assign(&self, f: &mut crate::scad::Formatter) -> crate::scad::Assignment {
+        f.value(self)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ColorError {
+    UnknownName(String),
+    InvalidHex(String),
+    OutOfRange(f64),
+}
+
+impl fmt::Display for ColorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownName(name) => write!(f, "unknown color name {name:?}"),
+            Self::InvalidHex(value) => write!(f, "invalid hex color {value:?}"),
+            Self::OutOfRange(value) => {
+                write!(f, "color channel {value} is outside of the range 0.0..=1.0")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ColorError {}
+
+/// The color names supported by OpenSCAD, taken from the SVG 1.1/CSS3 color keywords
+static NAMES: &[&str] = &[
+    "aliceblue",
+    "antiquewhite",
+    "aqua",
+    "aquamarine",
+    "azure",
+    "beige",
+    "bisque",
+    "black",
+    "blanchedalmond",
+    "blue",
+    "blueviolet",
+    "brown",
+    "burlywood",
+    "cadetblue",
+    "chartreuse",
+    "chocolate",
+    "coral",
+    "cornflowerblue",
+    "cornsilk",
+    "crimson",
+    "cyan",
+    "darkblue",
+    "darkcyan",
+    "darkgoldenrod",
+    "darkgray",
+    "darkgreen",
+    "darkgrey",
+    "darkkhaki",
+    "darkmagenta",
+    "darkolivegreen",
+    "darkorange",
+    "darkorchid",
+    "darkred",
+    "darksalmon",
+    "darkseagreen",
+    "darkslateblue",
+    "darkslategray",
+    "darkslategrey",
+    "darkturquoise",
+    "darkviolet",
+    "deeppink",
+    "deepskyblue",
+    "dimgray",
+    "dimgrey",
+    "dodgerblue",
+    "firebrick",
+    "floralwhite",
+    "forestgreen",
+    "fuchsia",
+    "gainsboro",
+    "ghostwhite",
+    "gold",
+    "goldenrod",
+    "gray",
+    "green",
+    "greenyellow",
+    "grey",
+    "honeydew",
+    "hotpink",
+    "indianred",
+    "indigo",
+    "ivory",
+    "khaki",
+    "lavender",
+    "lavenderblush",
+    "lawngreen",
+    "lemonchiffon",
+    "lightblue",
+    "lightcoral",
+    "lightcyan",
+    "lightgoldenrodyellow",
+    "lightgray",
+    "lightgreen",
+    "lightgrey",
+    "lightpink",
+    "lightsalmon",
+    "lightseagreen",
+    "lightskyblue",
+    "lightslategray",
+    "lightslategrey",
+    "lightsteelblue",
+    "lightyellow",
+    "lime",
+    "limegreen",
+    "linen",
+    "magenta",
+    "maroon",
+    "mediumaquamarine",
+    "mediumblue",
+    "mediumorchid",
+    "mediumpurple",
+    "mediumseagreen",
+    "mediumslateblue",
+    "mediumspringgreen",
+    "mediumturquoise",
+    "mediumvioletred",
+    "midnightblue",
+    "mintcream",
+    "mistyrose",
+    "moccasin",
+    "navajowhite",
+    "navy",
+    "oldlace",
+    "olive",
+    "olivedrab",
+    "orange",
+    "orangered",
+    "orchid",
+    "palegoldenrod",
+    "palegreen",
+    "paleturquoise",
+    "palevioletred",
+    "papayawhip",
+    "peachpuff",
+    "peru",
+    "pink",
+    "plum",
+    "powderblue",
+    "purple",
+    "rebeccapurple",
+    "red",
+    "rosybrown",
+    "royalblue",
+    "saddlebrown",
+    "salmon",
+    "sandybrown",
+    "seagreen",
+    "seashell",
+    "sienna",
+    "silver",
+    "skyblue",
+    "slateblue",
+    "slategray",
+    "slategrey",
+    "snow",
+    "springgreen",
+    "steelblue",
+    "tan",
+    "teal",
+    "thistle",
+    "tomato",
+    "transparent",
+    "turquoise",
+    "violet",
+    "wheat",
+    "white",
+    "whitesmoke",
+    "yellow",
+    "yellowgreen",
+];
+
+#[test]
+fn color_parse_test() {
+    assert_eq!(Color::from("Red").name(), Some("red"));
+    assert_eq!(
+        "#f00".parse::<Color>().map(|c| c.channels()),
+        Ok(Some([1.0, 0.0, 0.0, 1.0]))
+    );
+    assert_eq!(
+        "#ff000000".parse::<Color>().map(|c| c.channels()),
+        Ok(Some([1.0, 0.0, 0.0, 0.0]))
+    );
+    assert_eq!(
+        Color::from([0.5, 0.5, 0.5]),
+        Color::rgba(0.5, 0.5, 0.5, 1.0)
+    );
+    assert_eq!(
+        Color::try_rgba(0.5, 0.5, 2.0, 1.0),
+        Err(ColorError::OutOfRange(2.0))
+    );
+    assert_eq!(
+        Color::try_from(String::from("nope")),
+        Err(ColorError::UnknownName("nope".into()))
+    );
+
+    assert!("not-a-color".parse::<Color>().is_err());
+    assert!("#ff00".parse::<Color>().is_ok());
+    assert!("#ff0".parse::<Color>().is_ok());
+    assert!("#ff00f".parse::<Color>().is_err());
+    assert!("#gg0000".parse::<Color>().is_err());
+    assert!(Color::try_rgba(1.5, 0.0, 0.0, 1.0).is_err());
+}
+
+#[test]
+#[should_panic = "unknown color name \"bogus\""]
+fn color_literal_panic_test() {
+    let _ = Color::from("bogus");
+}
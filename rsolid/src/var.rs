@@ -1,19 +1,33 @@
+use crate::{
+    expr::{Condition, Expr, Variable},
+    scad,
+    types::{Angle, Length, Scalar},
+};
 use core::{fmt, marker::PhantomData};
+use std::sync::Arc;
 
 // TODO move these to a toml file
 pub const fn preview() -> Var<bool> {
     Var::new("$preview")
 }
 
-pub const fn t() -> Var<f64> {
+pub const fn t() -> Var<Scalar> {
     Var::new("$t")
 }
 
-pub struct Var<T>(&'static str, PhantomData<T>);
+/// A typed reference to an OpenSCAD variable
+///
+/// Variables can be passed to any builder in place of a literal of the same type.
+pub struct Var<T>(Variable, PhantomData<T>);
 
 impl<T> Var<T> {
     pub const fn new(name: &'static str) -> Var<T> {
-        Var(name, PhantomData)
+        Var(Variable::Builtin(name), PhantomData)
+    }
+
+    #[inline]
+    pub fn name(&self) -> &str {
+        self.0.name()
     }
 }
 
@@ -25,8 +39,234 @@ impl<T> fmt::Debug for Var<T> {
 
 impl<T> Clone for Var<T> {
     fn clone(&self) -> Self {
-        *self
+        Self(self.0.clone(), PhantomData)
+    }
+}
+
+macro_rules! impl_var {
+    ($name:ident) => {
+        impl From<Var<$name>> for $name {
+            #[inline]
+            fn from(value: Var<$name>) -> Self {
+                Self(Expr::Var(value.0))
+            }
+        }
+
+        impl From<&Var<$name>> for $name {
+            #[inline]
+            fn from(value: &Var<$name>) -> Self {
+                Self(Expr::Var(value.0.clone()))
+            }
+        }
+    };
+}
+
+impl From<Var<bool>> for Condition {
+    #[inline]
+    fn from(value: Var<bool>) -> Self {
+        Self(Expr::Var(value.0))
+    }
+}
+
+impl From<&Var<bool>> for Condition {
+    #[inline]
+    fn from(value: &Var<bool>) -> Self {
+        Self(Expr::Var(value.0.clone()))
+    }
+}
+
+impl_var!(Angle);
+impl_var!(Length);
+impl_var!(Scalar);
+
+/// Declares a top-level parameter that can be changed in the OpenSCAD Customizer
+///
+/// ```
+/// use rsolid::*;
+///
+/// let width = param::<Length>("width", 40)
+///     .range(10, 100)
+///     .step(5)
+///     .description("Width of the box")
+///     .group("Size")
+///     .var();
+///
+/// let _ = cube([Length::from(width), 20.into(), 10.into()]);
+/// ```
+#[inline]
+pub fn param<T>(name: impl Into<String>, default: impl Into<T>) -> Param<T> {
+    Param {
+        name: name.into(),
+        default: default.into(),
+        range: None,
+        step: None,
+        description: None,
+        group: None,
     }
 }
 
-impl<T> Copy for Var<T> {}
+#[derive(Clone, Debug)]
+#[must_use = "Parameters must be converted into a `Var` in order to be used"]
+pub struct Param<T> {
+    name: String,
+    default: T,
+    range: Option<(f64, f64)>,
+    step: Option<f64>,
+    description: Option<String>,
+    group: Option<String>,
+}
+
+impl<T> Param<T> {
+    /// Shows the parameter as a slider between `min` and `max`
+    #[inline]
+    pub fn range(mut self, min: impl Into<f64>, max: impl Into<f64>) -> Self {
+        self.range = Some((min.into(), max.into()));
+        self
+    }
+
+    /// The increment of the slider or spin box
+    #[inline]
+    pub fn step(mut self, step: impl Into<f64>) -> Self {
+        self.step = Some(step.into());
+        self
+    }
+
+    /// The description shown next to the parameter
+    #[inline]
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// The tab the parameter is shown under
+    #[inline]
+    pub fn group(mut self, group: impl Into<String>) -> Self {
+        self.group = Some(group.into());
+        self
+    }
+}
+
+impl<T> Param<T>
+where
+    Self: scad::Parameter,
+{
+    /// Declares the parameter and returns a reference to it
+    #[inline]
+    pub fn var(self) -> Var<T> {
+        Var(Variable::Parameter(Arc::new(self)), PhantomData)
+    }
+}
+
+/// A type that can be the default value of a [`Param`]
+pub trait ParamValue: 'static + Send + Sync + fmt::Debug {
+    /// Writes the value as an OpenSCAD literal
+    fn fmt_literal(&self, f: &mut fmt::Formatter) -> fmt::Result;
+}
+
+macro_rules! impl_param_value {
+    ($name:ty) => {
+        impl ParamValue for $name {
+            #[inline]
+            fn fmt_literal(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                fmt::Display::fmt(self, f)
+            }
+        }
+    };
+}
+
+impl_param_value!(Angle);
+impl_param_value!(Length);
+impl_param_value!(Scalar);
+impl_param_value!(bool);
+
+impl ParamValue for String {
+    #[inline]
+    fn fmt_literal(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // strings are quoted and escaped the same way as other string arguments
+        write!(f, "{self:?}")
+    }
+}
+
+impl<T: ParamValue> fmt::Display for Param<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(description) = self.description.as_ref() {
+            for line in description.lines() {
+                writeln!(f, "// {line}")?;
+            }
+        }
+
+        write!(f, "{} = ", self.name)?;
+        self.default.fmt_literal(f)?;
+        f.write_str(";")?;
+
+        match (self.range, self.step) {
+            (Some((min, max)), Some(step)) => write!(f, " // [{min}:{step}:{max}]"),
+            (Some((min, max)), None) => write!(f, " // [{min}:{max}]"),
+            (None, Some(step)) => write!(f, " // {step}"),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+impl<T: ParamValue> scad::Parameter for Param<T> {
+    #[inline]
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[inline]
+    fn group(&self) -> Option<&str> {
+        self.group.as_deref()
+    }
+}
+
+#[test]
+fn param_test() {
+    use crate::*;
+
+    let width = param::<Length>("width", 40)
+        .range(10, 100)
+        .step(5)
+        .description("Width of the box")
+        .group("Size")
+        .var();
+    let height = param::<Length>("height", 10).var();
+    let spin = param::<Angle>("spin", 0).group("Rotation").var();
+
+    let parts =
+        cube([Length::from(width), 20.into(), (&height).into()]).down(height) >> rotate_z(spin);
+    insta::assert_snapshot!(parts);
+}
+
+#[test]
+fn string_param_test() {
+    use crate::*;
+
+    let label = param::<String>("label", r#"say "hi" \ wave"#).description("Engraved text");
+    insta::assert_snapshot!(label);
+}
+
+#[test]
+fn param_conflict_test() {
+    use crate::{scad::Scad, *};
+
+    let small = param::<Length>("size", 10).var();
+    let large = param::<Length>("size", 20).var();
+    let (source, warnings) = (cube(small) + cube(large)).to_scad_with_warnings();
+    assert!(source.starts_with("size = 10;"), "{source}");
+    assert_eq!(
+        warnings,
+        ["conflicting declarations for parameter \"size\", only the first one is used"]
+    );
+}
+
+#[test]
+fn copy_test() {
+    use crate::*;
+
+    // builders stay `Copy` as long as none of their parameters hold an `Expr`
+    fn copy<T: Copy>(_: T) {}
+    copy(projection().cut(true));
+    copy(hull::<3>());
+    copy(Color::rgb(1.0, 0.0, 0.0));
+}